@@ -13,6 +13,7 @@ use crate::{
     model::{
         config::{Config, load_config},
         mode::AppMode,
+        session::load_session,
         state::AppState,
     },
     os::current_platform,
@@ -119,17 +120,22 @@ impl CloverApp {
             hwnd_usize,
             &state.common.tray_restore_requested,
             &state.common.tray_screenshot_requested,
+            &state.common.path_sender,
             &config_manager.config().hotkeys.show_screenshot,
         );
 
         // 创建 ViewerFeature（持有自己的 ViewerState 副本）
         let mut viewer_feature = ViewerFeature::new();
 
-        // 打开启动路径
+        viewer_feature.state.session = load_session();
+
+        // 打开启动路径，没有时恢复上次的浏览位置
         if let Some(path) = start_path {
             viewer_feature
                 .state
                 .open_new_context(cc.egui_ctx.clone(), path);
+        } else {
+            viewer_feature.state.restore_session(&cc.egui_ctx);
         }
 
         Self {
@@ -292,6 +298,20 @@ impl CloverApp {
             );
         }
     }
+
+    /// 同步查看器内直接调整的显示设置，走防抖保存
+    fn handle_viewer_preferences(&mut self) {
+        if let Some(grid) = self.viewer_feature.take_pending_grid_config() {
+            let mut new_config = (*self.config_manager.config()).clone();
+            new_config.grid = grid;
+            self.config_manager.update_and_save(new_config);
+        }
+        if let Some(viewer) = self.viewer_feature.take_pending_viewer_config() {
+            let mut new_config = (*self.config_manager.config()).clone();
+            new_config.viewer = viewer;
+            self.config_manager.update_and_save(new_config);
+        }
+    }
 }
 
 impl eframe::App for CloverApp {
@@ -323,6 +343,12 @@ impl eframe::App for CloverApp {
             self.state.mode = AppMode::Screenshot;
         }
 
+        // 最近打开记录变化后同步到托盘菜单
+        self.tray.refresh_recent(
+            self.config_manager.config().language,
+            &self.viewer_feature.state.session,
+        );
+
         let common = &mut self.state.common;
         match self.state.mode {
             AppMode::Viewer => {
@@ -343,6 +369,7 @@ impl eframe::App for CloverApp {
                 self.viewer_feature.ui(ui, common);
                 // 处理配置应用（从 overlay 状态）
                 self.handle_update_config();
+                self.handle_viewer_preferences();
             }
             AppMode::Screenshot => {
                 self.screenshot_feature.ui(ui, common, &mut self.state.mode);
@@ -351,7 +378,8 @@ impl eframe::App for CloverApp {
     }
 
     fn on_exit(&mut self) {
-        // 应用退出时强制保存配置
+        // 应用退出时强制保存配置和浏览位置
         self.config_manager.save_now();
+        self.viewer_feature.state.save_session();
     }
 }
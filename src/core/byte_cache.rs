@@ -0,0 +1,187 @@
+use lru::LruCache;
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+/// 缓存的统计信息，用于调试面板
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub bytes: usize,
+    pub budget: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    pub fn hit_rate(&self) -> f32 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f32 / total as f32
+        }
+    }
+}
+
+/// 按字节数而非条目数限制容量的 LRU 缓存。
+/// 超出预算时从最久未用的条目开始淘汰，但至少保留最新放入的一项，避免单张大图无法显示
+pub struct ByteLruCache<K: Hash + Eq, V> {
+    entries: LruCache<K, (V, usize)>,
+    bytes: usize,
+    budget: usize,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K: Hash + Eq, V> ByteLruCache<K, V> {
+    pub fn new(budget: usize) -> Self {
+        Self {
+            entries: LruCache::unbounded(),
+            bytes: 0,
+            budget,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// 读取并刷新使用顺序，用于每帧绘制，不计入命中率
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.get(key).map(|(value, _)| value)
+    }
+
+    /// 决定是否需要解码时的查询，计入命中率
+    pub fn lookup<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match self.entries.get(key) {
+            Some((value, _)) => {
+                self.hits += 1;
+                Some(value)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// 只判断是否存在，不影响使用顺序和命中率
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.contains(key)
+    }
+
+    pub fn put(&mut self, key: K, value: V, bytes: usize) {
+        if let Some((_, old)) = self.entries.put(key, (value, bytes)) {
+            self.bytes -= old;
+        }
+        self.bytes += bytes;
+        self.evict();
+    }
+
+    pub fn pop<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (value, bytes) = self.entries.pop(key)?;
+        self.bytes -= bytes;
+        Some(value)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.bytes = 0;
+    }
+
+    pub fn set_budget(&mut self, budget: usize) {
+        if self.budget != budget {
+            self.budget = budget;
+            self.evict();
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.entries.len(),
+            bytes: self.bytes,
+            budget: self.budget,
+            hits: self.hits,
+            misses: self.misses,
+        }
+    }
+
+    fn evict(&mut self) {
+        while self.bytes > self.budget && self.entries.len() > 1 {
+            match self.entries.pop_lru() {
+                Some((_, (_, bytes))) => self.bytes -= bytes,
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ByteLruCache;
+
+    #[test]
+    fn evicts_least_recently_used_until_within_budget() {
+        let mut cache = ByteLruCache::new(100);
+        cache.put("a", 1, 40);
+        cache.put("b", 2, 40);
+        assert_eq!(cache.get("a"), Some(&1));
+        cache.put("c", 3, 40);
+        assert_eq!(cache.stats().hits, 0);
+
+        assert!(cache.contains("a"));
+        assert!(!cache.contains("b"));
+        assert!(cache.contains("c"));
+        assert_eq!(cache.stats().bytes, 80);
+    }
+
+    #[test]
+    fn keeps_single_entry_larger_than_budget() {
+        let mut cache = ByteLruCache::new(100);
+        cache.put("small", 1, 10);
+        cache.put("huge", 2, 500);
+
+        assert!(!cache.contains("small"));
+        assert!(cache.contains("huge"));
+        assert_eq!(cache.stats().entries, 1);
+    }
+
+    #[test]
+    fn replacing_and_popping_keep_byte_count_consistent() {
+        let mut cache = ByteLruCache::new(1000);
+        cache.put("a", 1, 100);
+        cache.put("a", 2, 300);
+        assert_eq!(cache.stats().bytes, 300);
+        assert_eq!(cache.pop("a"), Some(2));
+        assert_eq!(cache.stats().bytes, 0);
+    }
+
+    #[test]
+    fn hit_rate_counts_lookups() {
+        let mut cache = ByteLruCache::new(1000);
+        cache.put("a", 1, 1);
+        cache.lookup("a");
+        cache.lookup("a");
+        cache.lookup("a");
+        cache.lookup("missing");
+        cache.get("missing");
+        assert_eq!(cache.stats().hit_rate(), 0.75);
+
+        cache.set_budget(0);
+        assert_eq!(cache.stats().entries, 1);
+    }
+}
@@ -1,7 +1,7 @@
 use global_hotkey::hotkey::Code;
 
 /// 解析后的热键中间表示。
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ParsedHotkey {
     pub ctrl: bool,
     pub alt: bool,
@@ -103,6 +103,16 @@ pub fn parsed_key_to_code(key_name: &str) -> Option<Code> {
         "Escape" => Some(Code::Escape),
         "Tab" => Some(Code::Tab),
         "Backspace" => Some(Code::Backspace),
+        "ArrowLeft" => Some(Code::ArrowLeft),
+        "ArrowRight" => Some(Code::ArrowRight),
+        "ArrowUp" => Some(Code::ArrowUp),
+        "ArrowDown" => Some(Code::ArrowDown),
+        "Delete" => Some(Code::Delete),
+        "Insert" => Some(Code::Insert),
+        "Home" => Some(Code::Home),
+        "End" => Some(Code::End),
+        "PageUp" => Some(Code::PageUp),
+        "PageDown" => Some(Code::PageDown),
         _ => {
             tracing::debug!("Unknown key name for Code: {}", key_name);
             None
@@ -167,10 +177,35 @@ pub fn parsed_key_to_egui_key(key_name: &str) -> Option<egui::Key> {
         "Escape" => Some(Key::Escape),
         "Tab" => Some(Key::Tab),
         "Backspace" => Some(Key::Backspace),
+        "ArrowLeft" => Some(Key::ArrowLeft),
+        "ArrowRight" => Some(Key::ArrowRight),
+        "ArrowUp" => Some(Key::ArrowUp),
+        "ArrowDown" => Some(Key::ArrowDown),
+        "Delete" => Some(Key::Delete),
+        "Insert" => Some(Key::Insert),
+        "Home" => Some(Key::Home),
+        "End" => Some(Key::End),
+        "PageUp" => Some(Key::PageUp),
+        "PageDown" => Some(Key::PageDown),
         _ => None,
     }
 }
 
+/// 判断配置的快捷键本帧是否按下。修饰键需完全一致，避免 Ctrl+E 和 Ctrl+Shift+E 同时触发
+pub fn egui_hotkey_pressed(input: &egui::InputState, hotkey_str: &str) -> bool {
+    let Some(parsed) = parse_hotkey_str(hotkey_str) else {
+        return false;
+    };
+    let Some(key) = parsed_key_to_egui_key(&parsed.key_name) else {
+        return false;
+    };
+    input.modifiers.ctrl == parsed.ctrl
+        && input.modifiers.alt == parsed.alt
+        && input.modifiers.shift == parsed.shift
+        && input.modifiers.mac_cmd == parsed.cmd
+        && input.key_pressed(key)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,19 +1,38 @@
+use crate::core::load_queue::{LoadJob, LoadPriority, LoadQueue};
+use crate::core::resize;
+use crate::i18n::lang::TextBundle;
+use crate::model::config::get_context_config;
 use crate::model::image_meta::ImageProperties;
 use crate::os::current_platform;
+use crate::utils::image::{extension_mismatch, sniff_format};
+use crate::utils::time::format_system_time;
 use egui::{ColorImage, Context, TextureHandle};
 use exif::Tag;
-use image::{DynamicImage, ImageBuffer, Rgb, imageops::FilterType, metadata::Orientation};
+use image::{
+    ColorType, DynamicImage, ImageBuffer, ImageDecoder, ImageError, ImageFormat, ImageReader, Rgb,
+    Rgba, error::UnsupportedErrorKind, metadata::Orientation,
+};
+use jpeg_decoder::PixelFormat;
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use std::{
     fs,
     io::Cursor,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
-        Arc,
+        Arc, Mutex,
         mpsc::{Receiver, Sender, channel},
     },
 };
 use zune_jpeg::JpegDecoder;
+use zune_jpeg::errors::DecodeErrors;
+
+/// 像素数达到该值的 JPEG 才先显示内嵌缩略图作为预览
+const PREVIEW_MIN_PIXELS: u64 = 12_000_000;
+/// 预览至少要覆盖的边长，更小的内嵌缩略图放大后太糊
+const PREVIEW_MIN_SIDE: u32 = 160;
+
+/// 单边超过该像素数的图片不解码，与 zune-jpeg 的默认上限一致
+pub const MAX_IMAGE_SIDE: u32 = 16384;
 
 /// 图片加载错误类型
 #[derive(Debug, Clone, PartialEq)]
@@ -26,10 +45,57 @@ pub enum ImageLoadError {
     UnsupportedFormat(String),
     /// 图片解码失败
     DecodeError(String),
+    /// 文件不完整，且没有能解码出的部分
+    Truncated,
+    /// 格式支持，但用到了解码器不支持的特性
+    UnsupportedFeature(String),
+    /// 尺寸超过 MAX_IMAGE_SIDE
+    TooLarge { width: u32, height: u32 },
     // /// 创建纹理失败
     // TextureError(String),
 }
 
+impl ImageLoadError {
+    /// 界面上显示的本地化说明
+    pub fn localized(&self, text: &TextBundle) -> String {
+        let t = &text.load_error;
+        match self {
+            Self::FileNotFound(detail) => t.file_not_found.replace("{detail}", detail),
+            Self::ReadError(detail) => t.read_error.replace("{detail}", detail),
+            Self::UnsupportedFormat(detail) => t.unsupported_format.replace("{detail}", detail),
+            Self::DecodeError(detail) => t.decode_error.replace("{detail}", detail),
+            Self::Truncated => t.truncated.to_string(),
+            Self::UnsupportedFeature(detail) => t.unsupported_feature.replace("{detail}", detail),
+            Self::TooLarge { width, height } => t
+                .too_large
+                .replace("{width}", &width.to_string())
+                .replace("{height}", &height.to_string())
+                .replace("{max}", &MAX_IMAGE_SIDE.to_string()),
+        }
+    }
+
+    fn from_image_error(e: ImageError) -> Self {
+        match e {
+            ImageError::Unsupported(e) => match e.kind() {
+                UnsupportedErrorKind::Format(hint) => Self::UnsupportedFormat(hint.to_string()),
+                _ => Self::UnsupportedFeature(e.to_string()),
+            },
+            ImageError::IoError(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                Self::Truncated
+            }
+            e => Self::DecodeError(e.to_string()),
+        }
+    }
+
+    fn from_jpeg_error(e: DecodeErrors) -> Self {
+        match e {
+            DecodeErrors::ExhaustedData => Self::Truncated,
+            DecodeErrors::Unsupported(scheme) => Self::UnsupportedFeature(format!("{scheme:?}")),
+            e => Self::DecodeError(format!("JPEG解码失败: {}", e)),
+        }
+    }
+}
+
 impl std::fmt::Display for ImageLoadError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -37,7 +103,11 @@ impl std::fmt::Display for ImageLoadError {
             ImageLoadError::ReadError(msg) => write!(f, "文件读取失败: {}", msg),
             ImageLoadError::UnsupportedFormat(ext) => write!(f, "不支持的图片格式: {}", ext),
             ImageLoadError::DecodeError(msg) => write!(f, "图片解码失败: {}", msg),
-            // ImageLoadError::TextureError(msg) => write!(f, "创建纹理失败: {}", msg),
+            ImageLoadError::Truncated => write!(f, "文件不完整"),
+            ImageLoadError::UnsupportedFeature(msg) => write!(f, "不支持的编码特性: {}", msg),
+            ImageLoadError::TooLarge { width, height } => {
+                write!(f, "图片尺寸过大: {}x{}", width, height)
+            } // ImageLoadError::TextureError(msg) => write!(f, "创建纹理失败: {}", msg),
         }
     }
 }
@@ -60,15 +130,26 @@ pub struct LoadMessage {
     pub result: LoadResult,
     pub is_priority: bool,  // 加载优先级
     pub is_thumbnail: bool, // 缩略图
+    /// 原图解码完成前先送出的低分辨率预览，随后还会收到同一路径的原图
+    pub is_preview: bool,
+}
+
+/// 读入内存的文件内容和已解析的 EXIF，预览和原图解码共用，避免重复读盘
+struct SourceFile {
+    data: Vec<u8>,
+    properties: ImageProperties,
+    orientation: u32,
 }
 
 pub struct ImageLoader {
     tx: Sender<LoadMessage>,
     pub rx: Receiver<LoadMessage>,
     pub is_loading: bool,
-    // 双线程池：主图池和缩略图池
+    // 双线程池：主图池和缩略图池，各自从对应的队列中按优先级取任务
     main_pool: ThreadPool,
     thumb_pool: ThreadPool,
+    main_queue: Arc<Mutex<LoadQueue>>,
+    thumb_queue: Arc<Mutex<LoadQueue>>,
 }
 
 impl ImageLoader {
@@ -118,72 +199,194 @@ impl ImageLoader {
             is_loading: false,
             main_pool,
             thumb_pool,
+            main_queue: Arc::default(),
+            thumb_queue: Arc::default(),
+        }
+    }
+
+    /// 切换图片时调用，之前排队、尚未开始解码的原图任务全部作废
+    pub fn supersede_full(&mut self) {
+        if let Ok(mut queue) = self.main_queue.lock() {
+            queue.supersede();
         }
     }
 
-    /// 异步加载
-    /// is_priority 优先级
-    pub fn load_async(
+    /// 加载正在查看的图片
+    pub fn load_current(&mut self, ctx: &Context, path: PathBuf) {
+        // 只有当前图片会触发全局加载状态锁定
+        self.is_loading = true;
+        self.load_full(ctx, path, LoadPriority::Current);
+    }
+
+    /// 在主池中加载原图
+    pub fn load_full(&mut self, ctx: &Context, path: PathBuf, priority: LoadPriority) {
+        Self::enqueue(
+            &self.main_pool,
+            &self.main_queue,
+            &self.tx,
+            ctx,
+            path,
+            None,
+            priority,
+        );
+    }
+
+    /// 在缩略图池中加载不超过 `size` 的缩略图
+    pub fn load_thumbnail(
         &mut self,
-        ctx: Context,
+        ctx: &Context,
         path: PathBuf,
-        is_priority: bool,
-        size: Option<(u32, u32)>,
+        size: (u32, u32),
+        priority: LoadPriority,
     ) {
-        let tx = self.tx.clone();
-        let path_clone = path.clone();
-        let is_thumbnail = size.is_some();
+        Self::enqueue(
+            &self.thumb_pool,
+            &self.thumb_queue,
+            &self.tx,
+            ctx,
+            path,
+            Some(size),
+            priority,
+        );
+    }
+
+    /// 按可见性重新评估排队中的缩略图，返回被取消的路径
+    pub fn reprioritize_thumbnails(
+        &self,
+        priority_of: impl Fn(&Path) -> Option<LoadPriority>,
+    ) -> Vec<PathBuf> {
+        self.thumb_queue
+            .lock()
+            .map(|mut queue| queue.reprioritize_thumbnails(priority_of))
+            .unwrap_or_default()
+    }
 
-        // 只有高优先级任务会触发全局加载状态锁定
-        if is_priority {
-            self.is_loading = true;
+    /// 切换文件夹时丢弃所有排队中的任务
+    pub fn cancel_pending(&mut self) {
+        for queue in [&self.main_queue, &self.thumb_queue] {
+            if let Ok(mut queue) = queue.lock() {
+                queue.supersede();
+            }
         }
+        self.is_loading = false;
+    }
 
-        let target_pool = if is_priority {
-            &self.main_pool
-        } else {
-            &self.thumb_pool
-        };
+    /// 任务进入队列后，线程池中的每个工作任务只取出当时优先级最高的一项，
+    /// 因此排队期间提升或取消的任务都能生效
+    fn enqueue(
+        pool: &ThreadPool,
+        queue: &Arc<Mutex<LoadQueue>>,
+        tx: &Sender<LoadMessage>,
+        ctx: &Context,
+        path: PathBuf,
+        size: Option<(u32, u32)>,
+        priority: LoadPriority,
+    ) {
+        let added = queue
+            .lock()
+            .map(|mut queue| queue.push(path, size, priority))
+            .unwrap_or(false);
+        if !added {
+            return;
+        }
 
-        target_pool.spawn(move || {
-            let result = if is_thumbnail {
-                // 尝试使用系统 API 加载缩略图
-                let platform = current_platform();
-                match platform.load_thumbnail(&path_clone, size.unwrap_or((160, 120))) {
-                    Ok(color_image) => {
-                        let raw_pixels = Arc::new(color_image.pixels.clone());
-                        let tex = ctx.load_texture(
-                            format!("thumb_{}", path_clone.display()),
-                            color_image,
-                            Default::default(),
-                        );
-                        LoadResult::Ok(Box::new(LoadSuccess {
-                            texture: tex,
-                            raw_pixels,
-                            properties: ImageProperties::default(), // 缩略图不需要详细属性
-                        }))
-                    }
-                    Err(_) => {
-                        // 降级到普通加载
-                        Self::load_normal(&ctx, &path_clone, size)
-                    }
-                }
+        let queue = Arc::clone(queue);
+        let tx = tx.clone();
+        let ctx = ctx.clone();
+        pool.spawn(move || {
+            let Some(job) = queue.lock().ok().and_then(|mut queue| queue.pop()) else {
+                return;
+            };
+            let is_priority = job.priority == LoadPriority::Current;
+            let result = if is_priority && !job.is_thumbnail() {
+                Self::load_with_preview(&ctx, &tx, &job.path)
             } else {
-                Self::load_normal(&ctx, &path_clone, size)
+                Self::run_job(&ctx, &job)
             };
-
             let _ = tx.send(LoadMessage {
-                path: path_clone,
-                result,
                 is_priority,
-                is_thumbnail,
+                is_thumbnail: job.is_thumbnail(),
+                is_preview: false,
+                path: job.path,
+                result,
             });
             ctx.request_repaint(); // 唤醒 UI 渲染
         });
     }
 
+    /// 与缩略图任务相同的解码顺序（先系统缩略图，失败再自行解码），但不创建纹理，供导出等后台任务使用
+    pub fn decode_thumbnail(
+        path: &PathBuf,
+        size: (u32, u32),
+    ) -> Result<(ColorImage, ImageProperties), ImageLoadError> {
+        match current_platform().load_thumbnail(path, size) {
+            Ok(color_image) => {
+                let properties = Self::thumbnail_properties(path, color_image.size);
+                Ok((color_image, properties))
+            }
+            Err(_) => Self::decode_image(path, Some(size)),
+        }
+    }
+
+    fn run_job(ctx: &Context, job: &LoadJob) -> LoadResult {
+        let Some(size) = job.size else {
+            return Self::load_normal(ctx, &job.path, None);
+        };
+        // 尝试使用系统 API 加载缩略图
+        let platform = current_platform();
+        match platform.load_thumbnail(&job.path, size) {
+            Ok(color_image) => {
+                let properties = Self::thumbnail_properties(&job.path, color_image.size);
+                let raw_pixels = Arc::new(color_image.pixels.clone());
+                let tex = ctx.load_texture(
+                    format!("thumb_{}", job.path.display()),
+                    color_image,
+                    Default::default(),
+                );
+                LoadResult::Ok(Box::new(LoadSuccess {
+                    texture: tex,
+                    raw_pixels,
+                    properties,
+                }))
+            }
+            Err(_) => {
+                // 降级到普通加载
+                Self::load_normal(ctx, &job.path, Some(size))
+            }
+        }
+    }
+
+    /// 先送出预览再解码原图，两者共用一次读取的文件内容和 EXIF
+    fn load_with_preview(ctx: &Context, tx: &Sender<LoadMessage>, path: &PathBuf) -> LoadResult {
+        let source = match Self::read_source(path) {
+            Ok(source) => source,
+            Err(e) => return LoadResult::Err(e),
+        };
+        if let Some(preview) = Self::load_preview(ctx, &source) {
+            let _ = tx.send(LoadMessage {
+                path: path.clone(),
+                result: preview,
+                is_priority: true,
+                is_thumbnail: false,
+                is_preview: true,
+            });
+            ctx.request_repaint();
+        }
+        Self::upload(ctx, path, None, Self::decode_source(path, source, None))
+    }
+
     fn load_normal(ctx: &Context, path: &PathBuf, size: Option<(u32, u32)>) -> LoadResult {
-        match Self::decode_image(path, size) {
+        Self::upload(ctx, path, size, Self::decode_image(path, size))
+    }
+
+    /// 把解码结果上传为纹理
+    fn upload(
+        ctx: &Context,
+        path: &Path,
+        size: Option<(u32, u32)>,
+        decoded: Result<(ColorImage, ImageProperties), ImageLoadError>,
+    ) -> LoadResult {
+        match decoded {
             Ok((color_image, properties)) => {
                 // 1. 在主线程创建纹理之前，先保留像素引用
                 let raw_pixels = Arc::new(color_image.pixels.clone());
@@ -196,7 +399,13 @@ impl ImageLoader {
                         .into()
                 };
                 // 2. 创建纹理
-                let tex = ctx.load_texture(name, color_image, Default::default());
+                // 缩略图在单图中会被拉伸作为占位，始终平滑采样
+                let options = if size.is_some() {
+                    Default::default()
+                } else {
+                    get_context_config(ctx).viewer.texture_options()
+                };
+                let tex = ctx.load_texture(name, color_image, options);
 
                 // 3. 返回组合结构
                 LoadResult::Ok(Box::new(LoadSuccess {
@@ -209,6 +418,212 @@ impl ImageLoader {
         }
     }
 
+    /// 大尺寸 JPEG 先用内嵌缩略图出一帧预览，小图直接解码足够快，不发预览
+    fn load_preview(ctx: &Context, source: &SourceFile) -> Option<LoadResult> {
+        let (color_image, properties) = Self::decode_preview(source)?;
+        let texture = ctx.load_texture(
+            format!("preview_{}", properties.path.display()),
+            color_image,
+            Default::default(),
+        );
+        // 预览不参与透明度蒙版等像素操作，不保留像素快照
+        Some(LoadResult::Ok(Box::new(LoadSuccess {
+            texture,
+            raw_pixels: Arc::default(),
+            properties,
+        })))
+    }
+
+    /// 系统缩略图不带原图信息，只读文件头补齐宽高；EXIF 旋转可能让宽高互换，按缩略图方向校正
+    fn thumbnail_properties(path: &Path, thumb_size: [usize; 2]) -> ImageProperties {
+        let mut properties = ImageProperties::default();
+        if let Ok((w, h)) = image::image_dimensions(path) {
+            let thumb_landscape = thumb_size[0] >= thumb_size[1];
+            let (w, h) = if (w >= h) == thumb_landscape {
+                (w, h)
+            } else {
+                (h, w)
+            };
+            properties.width = w;
+            properties.height = h;
+        }
+        properties
+    }
+
+    /// 等比缩小到不超过 (w, h)。缩小倍数大时先按整数倍区域平均，再用 CatmullRom 缩到目标尺寸，避免锯齿
+    fn downscale_to_fit(img: DynamicImage, w: u32, h: u32) -> DynamicImage {
+        if img.width() <= w && img.height() <= h {
+            return img;
+        }
+        let (target_w, target_h) = resize::fit_within(img.width(), img.height(), w, h);
+        let factor = (img.width() / target_w).min(img.height() / target_h) as usize;
+        let img = if factor >= 2 {
+            Self::box_reduce(img, factor)
+        } else {
+            img
+        };
+        if (img.width(), img.height()) == (target_w, target_h) {
+            img
+        } else {
+            let (out_w, out_h) = (target_w as usize, target_h as usize);
+            Self::map_pixels(img, |pixels, width, height, channels| {
+                let out = resize::resample(pixels, width, height, channels, out_w, out_h);
+                (out, out_w, out_h)
+            })
+        }
+    }
+
+    fn box_reduce(img: DynamicImage, factor: usize) -> DynamicImage {
+        Self::map_pixels(img, |pixels, width, height, channels| {
+            resize::box_reduce(pixels, width, height, channels, factor)
+        })
+    }
+
+    /// RGB8 直接处理，其余格式先转成 RGBA8；`f` 返回新的像素和宽高
+    fn map_pixels(
+        img: DynamicImage,
+        f: impl Fn(&[u8], usize, usize, usize) -> (Vec<u8>, usize, usize),
+    ) -> DynamicImage {
+        let (width, height) = (img.width() as usize, img.height() as usize);
+        match img {
+            DynamicImage::ImageRgb8(buf) => {
+                let (pixels, w, h) = f(buf.as_raw(), width, height, 3);
+                ImageBuffer::<Rgb<u8>, _>::from_raw(w as u32, h as u32, pixels)
+                    .map(DynamicImage::ImageRgb8)
+            }
+            other => {
+                let buf = other.to_rgba8();
+                let (pixels, w, h) = f(buf.as_raw(), width, height, 4);
+                ImageBuffer::<Rgba<u8>, _>::from_raw(w as u32, h as u32, pixels)
+                    .map(DynamicImage::ImageRgba8)
+            }
+        }
+        .expect("缩放输出的像素数与宽高一致")
+    }
+
+    /// 取出 EXIF 中嵌入的 JPEG 缩略图。`source` 为原图未旋转时的宽高，
+    /// 只有宽高比与原图一致、且不小于缩放到 `target` 后的尺寸时才使用，否则返回 None 走完整解码
+    fn embedded_thumbnail(
+        data: &[u8],
+        source: (u32, u32),
+        target: (u32, u32),
+    ) -> Option<DynamicImage> {
+        let exif = exif::Reader::new()
+            .read_from_container(&mut Cursor::new(data))
+            .ok()?;
+        let field = |tag| {
+            exif.get_field(tag, exif::In::THUMBNAIL)
+                .and_then(|f| f.value.get_uint(0))
+                .map(|v| v as usize)
+        };
+        let offset = field(Tag::JPEGInterchangeFormat)?;
+        let len = field(Tag::JPEGInterchangeFormatLength)?;
+        let bytes = exif.buf().get(offset..offset.checked_add(len)?)?;
+        let thumb = Self::decode_jpeg(bytes).ok()?;
+
+        // 部分相机把 16:9 的照片塞进 4:3 的带黑边缩略图，这种不能用
+        let thumb_aspect = thumb.width() as f32 / thumb.height() as f32;
+        let source_aspect = source.0 as f32 / source.1 as f32;
+        if (thumb_aspect / source_aspect - 1.0).abs() > 0.02 {
+            return None;
+        }
+        let (need_w, need_h) = resize::fit_within(source.0, source.1, target.0, target.1);
+        (thumb.width() >= need_w && thumb.height() >= need_h).then_some(thumb)
+    }
+
+    /// 只解析 JPEG 文件头得到宽高，不解码像素
+    fn jpeg_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+        let mut decoder = JpegDecoder::new(Cursor::new(data));
+        decoder.decode_headers().ok()?;
+        decoder
+            .info()
+            .map(|info| (info.width as u32, info.height as u32))
+    }
+
+    fn check_size(width: u32, height: u32) -> Result<(), ImageLoadError> {
+        if width > MAX_IMAGE_SIDE || height > MAX_IMAGE_SIDE {
+            return Err(ImageLoadError::TooLarge { width, height });
+        }
+        Ok(())
+    }
+
+    /// 解码到预先清零的缓冲区。中途出错时解码器已写入的行保留下来，
+    /// 只要解出了内容就返回残缺的图片，第二个返回值表示是否残缺
+    fn decode_salvaging(
+        data: &[u8],
+        format: ImageFormat,
+    ) -> Result<(DynamicImage, bool), ImageLoadError> {
+        let decoder = ImageReader::with_format(Cursor::new(data), format)
+            .into_decoder()
+            .map_err(ImageLoadError::from_image_error)?;
+        let (width, height) = decoder.dimensions();
+        Self::check_size(width, height)?;
+        let color_type = decoder.color_type();
+        let mut buf = vec![0u8; decoder.total_bytes() as usize];
+
+        let truncated = match decoder.read_image(&mut buf) {
+            Ok(()) => false,
+            Err(e @ (ImageError::IoError(_) | ImageError::Decoding(_)))
+                if buf.iter().any(|&b| b != 0) =>
+            {
+                tracing::warn!("图片数据不完整，显示已解码的部分: {}", e);
+                true
+            }
+            Err(ImageError::Limits(_)) => {
+                return Err(ImageLoadError::TooLarge { width, height });
+            }
+            Err(e) => return Err(ImageLoadError::from_image_error(e)),
+        };
+        let img = image_from_raw(width, height, color_type, buf)
+            .ok_or_else(|| ImageLoadError::UnsupportedFeature(format!("{color_type:?}")))?;
+        Ok((img, truncated))
+    }
+
+    /// 在 DCT 域按 1/2、1/4、1/8 缩小解码，直接得到不小于缩放到 `target` 后尺寸的图片。
+    /// 只在至少能缩小一半时使用；CMYK 等格式和解码失败时返回 None，由调用方完整解码
+    fn decode_jpeg_scaled(
+        data: &[u8],
+        source: (u32, u32),
+        target: (u32, u32),
+    ) -> Option<DynamicImage> {
+        let (need_w, need_h) = resize::fit_within(source.0, source.1, target.0, target.1);
+        if need_w * 2 > source.0 || need_h * 2 > source.1 {
+            return None;
+        }
+        let mut decoder = jpeg_decoder::Decoder::new(Cursor::new(data));
+        let (width, height) = decoder
+            .scale(
+                need_w.min(u16::MAX as u32) as u16,
+                need_h.min(u16::MAX as u32) as u16,
+            )
+            .ok()?;
+        let pixels = decoder.decode().ok()?;
+        let (width, height) = (width as u32, height as u32);
+        match decoder.info()?.pixel_format {
+            PixelFormat::RGB24 => {
+                ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgb8)
+            }
+            PixelFormat::L8 => {
+                ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageLuma8)
+            }
+            _ => None,
+        }
+    }
+
+    fn decode_jpeg(data: &[u8]) -> Result<DynamicImage, ImageLoadError> {
+        let mut decoder = JpegDecoder::new(Cursor::new(data));
+        let pixels = decoder.decode().map_err(ImageLoadError::from_jpeg_error)?;
+        let info = decoder
+            .info()
+            .ok_or_else(|| ImageLoadError::DecodeError("无法获取JPEG信息".to_string()))?;
+
+        let rgb_buf =
+            ImageBuffer::<Rgb<u8>, _>::from_raw(info.width as u32, info.height as u32, pixels)
+                .ok_or_else(|| ImageLoadError::DecodeError("创建图像缓冲区失败".to_string()))?;
+
+        Ok(DynamicImage::ImageRgb8(rgb_buf))
+    }
+
     // 将 EXIF 的数字映射到 image crate 的枚举
     fn map_exif_to_orientation(exif_val: u32) -> Orientation {
         match exif_val {
@@ -242,6 +657,23 @@ impl ImageLoader {
                 })
                 .unwrap_or_default();
 
+            // 拍摄参数带上单位，如 1/125 s、f/2.8、50 mm
+            let get_with_unit = |tag| {
+                exif.get_field(tag, exif::In::PRIMARY)
+                    .map(|f| f.display_value().with_unit(&exif).to_string())
+                    .unwrap_or_default()
+            };
+            properties.camera = get_val(Tag::Model)
+                .map(|model| model.trim_matches('"').trim().to_string())
+                .unwrap_or_default();
+            properties.exposure_time = get_with_unit(Tag::ExposureTime);
+            properties.f_number = get_with_unit(Tag::FNumber);
+            properties.iso = get_val(Tag::PhotographicSensitivity).unwrap_or_default();
+            properties.focal_length = get_with_unit(Tag::FocalLength);
+            properties.has_location = exif
+                .get_field(Tag::GPSLatitude, exif::In::PRIMARY)
+                .is_some();
+
             return exif
                 .get_field(Tag::Orientation, exif::In::PRIMARY)
                 .and_then(|field| field.value.get_uint(0))
@@ -250,52 +682,11 @@ impl ImageLoader {
         1
     }
 
-    fn unix_timestamp_to_ymd_hms(mut secs: u64) -> (u32, u32, u32, u32, u32, u32) {
-        let second = (secs % 60) as u32;
-        secs /= 60;
-        let minute = (secs % 60) as u32;
-        secs /= 60;
-        let hour = (secs % 24) as u32;
-        let mut days = secs / 24;
-
-        let mut year = 1970u32;
-        loop {
-            let yd = if Self::is_leap_year(year) { 366 } else { 365 };
-            if days < yd {
-                break;
-            }
-            days -= yd;
-            year += 1;
-        }
-
-        let leap = Self::is_leap_year(year);
-        let month_days = [31, 28 + leap as u64, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
-        let mut month = 1u32;
-        for &md in &month_days {
-            if days < md {
-                break;
-            }
-            days -= md;
-            month += 1;
-        }
-
-        (year, month, days as u32 + 1, hour, minute, second)
-    }
-
-    fn is_leap_year(year: u32) -> bool {
-        (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
-    }
-
-    fn decode_image(
+    /// 读取文件信息和 EXIF，返回属性与 EXIF 方向值；宽高由调用方在解码后填写
+    fn read_properties(
         path: &PathBuf,
-        size: Option<(u32, u32)>,
-    ) -> Result<(ColorImage, ImageProperties), ImageLoadError> {
-        // 检查文件是否存在
-        if !path.exists() {
-            return Err(ImageLoadError::FileNotFound(path.display().to_string()));
-        }
-
-        let data = fs::read(path).map_err(|e| ImageLoadError::ReadError(e.to_string()))?;
+        data: &[u8],
+    ) -> Result<(ImageProperties, u32), ImageLoadError> {
         let metadata = fs::metadata(path).map_err(|e| ImageLoadError::ReadError(e.to_string()))?;
 
         let mut properties = ImageProperties {
@@ -309,63 +700,151 @@ impl ImageLoader {
             ..Default::default()
         };
 
-        let orientation_value = Self::extract_exif_properties(&data, &mut properties);
+        let orientation_value = Self::extract_exif_properties(data, &mut properties);
+        properties.extension_mismatch =
+            sniff_format(data).and_then(|format| extension_mismatch(path, format));
 
         // 无 EXIF 日期时，使用文件修改时间兜底
         if properties.date.is_empty()
-            && let Ok(modified) = metadata.modified()
-            && let Ok(duration) = modified.duration_since(std::time::UNIX_EPOCH)
+            && let Some(date) = metadata.modified().ok().and_then(format_system_time)
         {
-            let secs = duration.as_secs();
-            let (y, mo, d, h, mi, s) = Self::unix_timestamp_to_ymd_hms(secs);
-            properties.date = format!("{y:04}-{mo:02}-{d:02} {h:02}:{mi:02}:{s:02}");
+            properties.date = date;
         }
 
-        let is_jpeg = data.len() > 2 && data[0] == 0xFF && data[1] == 0xD8;
+        Ok((properties, orientation_value))
+    }
 
-        let mut img = if is_jpeg {
-            let mut decoder = JpegDecoder::new(Cursor::new(&data));
-            let pixels = decoder
-                .decode()
-                .map_err(|e| ImageLoadError::DecodeError(format!("JPEG解码失败: {}", e)))?;
-            let info = decoder
-                .info()
-                .ok_or_else(|| ImageLoadError::DecodeError("无法获取JPEG信息".to_string()))?;
+    /// 只在原图像素数不少于 PREVIEW_MIN_PIXELS 且带有内嵌缩略图时返回预览，属性中的宽高为原图尺寸
+    fn decode_preview(source: &SourceFile) -> Option<(ColorImage, ImageProperties)> {
+        let data = &source.data;
+        if sniff_format(data) != Some(ImageFormat::Jpeg) {
+            return None;
+        }
+        let (source_w, source_h) = Self::jpeg_dimensions(data)?;
+        if (source_w as u64) * (source_h as u64) < PREVIEW_MIN_PIXELS {
+            return None;
+        }
+        let min_side = (PREVIEW_MIN_SIDE, PREVIEW_MIN_SIDE);
+        let mut thumb = Self::embedded_thumbnail(data, (source_w, source_h), min_side)?;
+        let mut properties = source.properties.clone();
+        (properties.width, properties.height) = if matches!(source.orientation, 5..=8) {
+            (source_h, source_w)
+        } else {
+            (source_w, source_h)
+        };
+        thumb.apply_orientation(Self::map_exif_to_orientation(source.orientation));
+        let rgba = thumb.to_rgba8();
+        let color_image = ColorImage::from_rgba_unmultiplied(
+            [rgba.width() as usize, rgba.height() as usize],
+            rgba.as_raw(),
+        );
+        Some((color_image, properties))
+    }
 
-            let rgb_buf =
-                ImageBuffer::<Rgb<u8>, _>::from_raw(info.width as u32, info.height as u32, pixels)
-                    .ok_or_else(|| ImageLoadError::DecodeError("创建图像缓冲区失败".to_string()))?;
+    /// 读入文件并解析属性和 EXIF 方向
+    fn read_source(path: &PathBuf) -> Result<SourceFile, ImageLoadError> {
+        // 检查文件是否存在
+        if !path.exists() {
+            return Err(ImageLoadError::FileNotFound(path.display().to_string()));
+        }
 
-            DynamicImage::ImageRgb8(rgb_buf)
-        } else {
-            image::load_from_memory(&data).map_err(|e| {
-                // 检查是否是格式不支持
-                let err_str = e.to_string();
-                if err_str.contains("Unsupported") || err_str.contains("unknown") {
+        let data = fs::read(path).map_err(|e| ImageLoadError::ReadError(e.to_string()))?;
+        let (properties, orientation) = Self::read_properties(path, &data)?;
+        Ok(SourceFile {
+            data,
+            properties,
+            orientation,
+        })
+    }
+
+    /// 在当前线程解码，`size` 为缩略图目标框，`None` 时解码原图
+    pub fn decode_image(
+        path: &PathBuf,
+        size: Option<(u32, u32)>,
+    ) -> Result<(ColorImage, ImageProperties), ImageLoadError> {
+        Self::decode_source(path, Self::read_source(path)?, size)
+    }
+
+    fn decode_source(
+        path: &Path,
+        source: SourceFile,
+        size: Option<(u32, u32)>,
+    ) -> Result<(ColorImage, ImageProperties), ImageLoadError> {
+        let SourceFile {
+            data,
+            mut properties,
+            orientation: orientation_value,
+        } = source;
+
+        // 按文件头而不是扩展名选择解码器，改错扩展名的文件也能打开
+        let format = sniff_format(&data);
+        let is_jpeg = format == Some(ImageFormat::Jpeg);
+
+        // 旋转 90° 的方向会让宽高互换，缩略图的目标框要换回原图方向再比较
+        let swaps_axes = matches!(orientation_value, 5..=8);
+        let target = size.map(|(w, h)| if swaps_axes { (h, w) } else { (w, h) });
+
+        // 缩略图优先使用 EXIF 内嵌的预览，省去解码整张大图
+        let embedded = match target {
+            Some(target) if is_jpeg => Self::jpeg_dimensions(&data).and_then(|source| {
+                Self::embedded_thumbnail(&data, source, target).map(|thumb| (thumb, source))
+            }),
+            _ => None,
+        };
+
+        let (img, (source_w, source_h)) = match embedded {
+            Some(found) => found,
+            None => match format {
+                Some(ImageFormat::Jpeg) => {
+                    let source = Self::jpeg_dimensions(&data);
+                    if let Some((width, height)) = source {
+                        Self::check_size(width, height)?;
+                    }
+                    // zune-jpeg 遇到数据不足时用灰色补齐剩余部分，不报错
+                    properties.truncated = jpeg_is_truncated(&data);
+                    // 缩略图先尝试缩小解码，得到的尺寸不是原图尺寸，宽高按文件头记录
+                    let scaled = target.zip(source).and_then(|(target, source)| {
+                        Self::decode_jpeg_scaled(&data, source, target).map(|img| (img, source))
+                    });
+                    match scaled {
+                        Some(found) => found,
+                        None => {
+                            let img = Self::decode_jpeg(&data)?;
+                            let dimensions = (img.width(), img.height());
+                            (img, dimensions)
+                        }
+                    }
+                }
+                Some(format) => {
+                    let (img, truncated) = Self::decode_salvaging(&data, format)?;
+                    properties.truncated = truncated;
+                    let dimensions = (img.width(), img.height());
+                    (img, dimensions)
+                }
+                None => {
                     let ext = path
                         .extension()
                         .and_then(|e| e.to_str())
                         .unwrap_or("unknown")
                         .to_string();
-                    ImageLoadError::UnsupportedFormat(ext)
-                } else {
-                    ImageLoadError::DecodeError(err_str)
+                    return Err(ImageLoadError::UnsupportedFormat(ext));
                 }
-            })?
+            },
         };
 
-        let img_orient = Self::map_exif_to_orientation(orientation_value);
-        img.apply_orientation(img_orient);
-
-        properties.width = img.width();
-        properties.height = img.height();
-
-        let processed_img = if let Some((w, h)) = size {
-            img.resize_to_fill(w, h, FilterType::Nearest)
+        (properties.width, properties.height) = if swaps_axes {
+            (source_h, source_w)
         } else {
-            img
+            (source_w, source_h)
         };
 
+        // 先缩小再旋转，旋转的开销只落在小图上
+        let mut processed_img = match target {
+            Some((w, h)) => Self::downscale_to_fit(img, w, h),
+            None => img,
+        };
+        processed_img.apply_orientation(Self::map_exif_to_orientation(orientation_value));
+
         let rgba = processed_img.to_rgba8();
         let color_image = ColorImage::from_rgba_unmultiplied(
             [rgba.width() as usize, rgba.height() as usize],
@@ -376,9 +855,72 @@ impl ImageLoader {
     }
 }
 
+/// 按解码器给出的颜色类型把原始字节包装成图片，多字节通道按本机字节序
+fn image_from_raw(
+    width: u32,
+    height: u32,
+    color_type: ColorType,
+    buf: Vec<u8>,
+) -> Option<DynamicImage> {
+    let u16s = |buf: &[u8]| -> Vec<u16> {
+        buf.chunks_exact(2)
+            .map(|b| u16::from_ne_bytes([b[0], b[1]]))
+            .collect()
+    };
+    let f32s = |buf: &[u8]| -> Vec<f32> {
+        buf.chunks_exact(4)
+            .map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+            .collect()
+    };
+    match color_type {
+        ColorType::L8 => ImageBuffer::from_raw(width, height, buf).map(DynamicImage::ImageLuma8),
+        ColorType::La8 => ImageBuffer::from_raw(width, height, buf).map(DynamicImage::ImageLumaA8),
+        ColorType::Rgb8 => ImageBuffer::from_raw(width, height, buf).map(DynamicImage::ImageRgb8),
+        ColorType::Rgba8 => ImageBuffer::from_raw(width, height, buf).map(DynamicImage::ImageRgba8),
+        ColorType::L16 => {
+            ImageBuffer::from_raw(width, height, u16s(&buf)).map(DynamicImage::ImageLuma16)
+        }
+        ColorType::La16 => {
+            ImageBuffer::from_raw(width, height, u16s(&buf)).map(DynamicImage::ImageLumaA16)
+        }
+        ColorType::Rgb16 => {
+            ImageBuffer::from_raw(width, height, u16s(&buf)).map(DynamicImage::ImageRgb16)
+        }
+        ColorType::Rgba16 => {
+            ImageBuffer::from_raw(width, height, u16s(&buf)).map(DynamicImage::ImageRgba16)
+        }
+        ColorType::Rgb32F => {
+            ImageBuffer::from_raw(width, height, f32s(&buf)).map(DynamicImage::ImageRgb32F)
+        }
+        ColorType::Rgba32F => {
+            ImageBuffer::from_raw(width, height, f32s(&buf)).map(DynamicImage::ImageRgba32F)
+        }
+        _ => None,
+    }
+}
+
+/// 沿着段长度跳过文件头（包括 EXIF 里的内嵌缩略图），在主图扫描数据之后找不到结束标记即视为残缺
+fn jpeg_is_truncated(data: &[u8]) -> bool {
+    let mut pos = 2;
+    while pos + 4 <= data.len() {
+        if data[pos] != 0xFF {
+            // 文件头结构无法识别时不下结论
+            return false;
+        }
+        match data[pos + 1] {
+            // 段之间的填充字节
+            0xFF => pos += 1,
+            // 扫描数据中的 0xFF 都经过转义，不会误判出结束标记
+            0xDA => return !data[pos..].windows(2).any(|w| w == [0xFF, 0xD9]),
+            _ => pos += 2 + u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize,
+        }
+    }
+    true
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{ImageLoadError, ImageLoader};
+    use super::{ImageLoadError, ImageLoader, jpeg_is_truncated};
     use image::{DynamicImage, RgbaImage};
     use std::{
         env, fs,
@@ -418,6 +960,49 @@ mod tests {
         let _ = fs::remove_file(path);
     }
 
+    #[test]
+    fn decode_image_thumbnail_keeps_aspect_ratio() {
+        let path = unique_temp_path("png");
+        DynamicImage::ImageRgba8(RgbaImage::new(400, 100))
+            .save(&path)
+            .expect("Test image should be written");
+
+        let (image, properties) = ImageLoader::decode_image(&path, Some((64, 64)))
+            .expect("PNG image should decode successfully");
+
+        assert_eq!(image.size, [64, 16]);
+        assert_eq!((properties.width, properties.height), (400, 100));
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn large_jpeg_thumbnail_uses_scaled_decode() {
+        let path = unique_temp_path("jpg");
+        DynamicImage::ImageRgb8(image::RgbImage::from_pixel(
+            640,
+            480,
+            image::Rgb([200, 50, 10]),
+        ))
+        .save(&path)
+        .expect("Test image should be written");
+        let data = fs::read(&path).expect("Test image should be readable");
+
+        // 缩到 80x60 以内，1/8 缩放正好得到 80x60
+        let scaled = ImageLoader::decode_jpeg_scaled(&data, (640, 480), (80, 80))
+            .expect("RGB JPEG should decode at reduced scale");
+        assert_eq!((scaled.width(), scaled.height()), (80, 60));
+        // 缩小不到一半时不走缩小解码
+        assert!(ImageLoader::decode_jpeg_scaled(&data, (640, 480), (400, 400)).is_none());
+
+        let (image, properties) = ImageLoader::decode_image(&path, Some((64, 64)))
+            .expect("JPEG thumbnail should decode successfully");
+        assert_eq!(image.size, [64, 48]);
+        assert_eq!((properties.width, properties.height), (640, 480));
+
+        let _ = fs::remove_file(path);
+    }
+
     #[test]
     fn decode_image_reports_missing_file() {
         let path = unique_temp_path("png");
@@ -426,4 +1011,36 @@ mod tests {
 
         assert!(matches!(result, Err(ImageLoadError::FileNotFound(_))));
     }
+
+    #[test]
+    fn decode_image_salvages_truncated_png() {
+        let path = unique_temp_path("png");
+        let rgba = RgbaImage::from_fn(64, 64, |x, y| {
+            image::Rgba([(x * 3) as u8, (y * 3) as u8, 200, 255])
+        });
+        DynamicImage::ImageRgba8(rgba)
+            .save(&path)
+            .expect("Test image should be written");
+        let bytes = fs::read(&path).expect("Test image should be readable");
+        fs::write(&path, &bytes[..bytes.len() * 6 / 10]).expect("Test image should be truncated");
+
+        let (image, properties) =
+            ImageLoader::decode_image(&path, None).expect("Truncated PNG should be salvaged");
+
+        assert_eq!(image.size, [64, 64]);
+        assert!(properties.truncated);
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn jpeg_truncation_checks_main_scan_only() {
+        // SOI, 一个包含结束标记的 APP1 段, SOS 以及扫描数据
+        let mut data = vec![0xFF, 0xD8, 0xFF, 0xE1, 0x00, 0x04, 0xFF, 0xD9];
+        data.extend([0xFF, 0xDA, 0x00, 0x02, 0x12, 0xFF, 0x00, 0x34]);
+        assert!(jpeg_is_truncated(&data));
+
+        data.extend([0xFF, 0xD9]);
+        assert!(!jpeg_is_truncated(&data));
+    }
 }
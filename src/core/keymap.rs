@@ -0,0 +1,250 @@
+use crate::core::hotkey_parser::{ParsedHotkey, egui_hotkey_pressed, parse_hotkey_str};
+use crate::i18n::lang::TextBundle;
+use egui::InputState;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// 快捷键生效的范围，范围重叠的两个动作不能使用同一组合键
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeyScope {
+    /// 系统级热键，任何时候都生效
+    Global,
+    /// 看图模式下的各个视图
+    Viewer,
+    Single,
+    Grid,
+    Compare,
+    Screenshot,
+}
+
+impl KeyScope {
+    pub fn overlaps(self, other: Self) -> bool {
+        use KeyScope::*;
+        match (self, other) {
+            (Global, _) | (_, Global) => true,
+            (Viewer, Single | Grid | Compare) | (Single | Grid | Compare, Viewer) => true,
+            _ => self == other,
+        }
+    }
+}
+
+/// 可以绑定快捷键的动作
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Action {
+    PrevImage,
+    NextImage,
+    ToggleCacheStats,
+    ZoomFit,
+    ZoomFill,
+    ZoomActual,
+    ZoomFitWidth,
+    ZoomFitHeight,
+    ToggleZoomLock,
+    ToggleAlpha,
+    CycleChannelView,
+    ToggleInfoOverlay,
+    ToggleRulers,
+    ToggleMeasure,
+    SaveAs,
+    SelectAll,
+    ClearSelection,
+    DeleteSelection,
+    ExitCompare,
+    ScreenshotUndo,
+    ScreenshotRedo,
+    ScreenshotConfirm,
+    ScreenshotCancel,
+}
+
+impl Action {
+    /// 设置面板中的显示顺序
+    pub const ALL: [Self; 23] = [
+        Self::PrevImage,
+        Self::NextImage,
+        Self::ToggleCacheStats,
+        Self::ZoomFit,
+        Self::ZoomFill,
+        Self::ZoomActual,
+        Self::ZoomFitWidth,
+        Self::ZoomFitHeight,
+        Self::ToggleZoomLock,
+        Self::ToggleAlpha,
+        Self::CycleChannelView,
+        Self::ToggleInfoOverlay,
+        Self::ToggleRulers,
+        Self::ToggleMeasure,
+        Self::SaveAs,
+        Self::SelectAll,
+        Self::ClearSelection,
+        Self::DeleteSelection,
+        Self::ExitCompare,
+        Self::ScreenshotUndo,
+        Self::ScreenshotRedo,
+        Self::ScreenshotConfirm,
+        Self::ScreenshotCancel,
+    ];
+
+    pub fn default_binding(self) -> &'static str {
+        match self {
+            Self::PrevImage => "ArrowLeft",
+            Self::NextImage => "ArrowRight",
+            Self::ToggleCacheStats => "F3",
+            Self::ZoomFit => "F",
+            Self::ZoomFill => "Shift+F",
+            Self::ZoomActual => "Num1",
+            Self::ZoomFitWidth => "W",
+            Self::ZoomFitHeight => "H",
+            Self::ToggleZoomLock => "L",
+            Self::ToggleAlpha => "A",
+            Self::CycleChannelView => "C",
+            Self::ToggleInfoOverlay => "I",
+            Self::ToggleRulers => "R",
+            Self::ToggleMeasure => "M",
+            Self::SaveAs => "Ctrl+S",
+            Self::SelectAll => "Ctrl+A",
+            Self::ClearSelection => "Escape",
+            Self::DeleteSelection => "Delete",
+            Self::ExitCompare => "Escape",
+            Self::ScreenshotUndo => "Ctrl+Z",
+            Self::ScreenshotRedo => "Ctrl+Y",
+            Self::ScreenshotConfirm => "Enter",
+            Self::ScreenshotCancel => "Escape",
+        }
+    }
+
+    pub fn scope(self) -> KeyScope {
+        match self {
+            Self::PrevImage | Self::NextImage | Self::ToggleCacheStats => KeyScope::Viewer,
+            Self::ZoomFit
+            | Self::ZoomFill
+            | Self::ZoomActual
+            | Self::ZoomFitWidth
+            | Self::ZoomFitHeight
+            | Self::ToggleZoomLock
+            | Self::ToggleAlpha
+            | Self::CycleChannelView
+            | Self::ToggleInfoOverlay
+            | Self::ToggleRulers
+            | Self::ToggleMeasure
+            | Self::SaveAs => KeyScope::Single,
+            Self::SelectAll | Self::ClearSelection | Self::DeleteSelection => KeyScope::Grid,
+            Self::ExitCompare => KeyScope::Compare,
+            Self::ScreenshotUndo
+            | Self::ScreenshotRedo
+            | Self::ScreenshotConfirm
+            | Self::ScreenshotCancel => KeyScope::Screenshot,
+        }
+    }
+
+    pub fn label(self, text: &TextBundle) -> &'static str {
+        let keymap = &text.keymap;
+        match self {
+            Self::PrevImage => keymap.prev_image,
+            Self::NextImage => keymap.next_image,
+            Self::ToggleCacheStats => keymap.cache_stats,
+            Self::ZoomFit => text.zoom.fit,
+            Self::ZoomFill => text.zoom.fill,
+            Self::ZoomActual => text.zoom.actual,
+            Self::ZoomFitWidth => text.zoom.fit_width,
+            Self::ZoomFitHeight => text.zoom.fit_height,
+            Self::ToggleZoomLock => keymap.zoom_lock,
+            Self::ToggleAlpha => keymap.show_alpha,
+            Self::CycleChannelView => keymap.channel_view,
+            Self::ToggleInfoOverlay => keymap.info_overlay,
+            Self::ToggleRulers => text.measure.rulers,
+            Self::ToggleMeasure => text.measure.tool,
+            Self::SaveAs => text.context_menu.save_as,
+            Self::SelectAll => keymap.select_all,
+            Self::ClearSelection => keymap.clear_selection,
+            Self::DeleteSelection => text.context_menu.delete,
+            Self::ExitCompare => keymap.exit_compare,
+            Self::ScreenshotUndo => keymap.undo,
+            Self::ScreenshotRedo => keymap.redo,
+            Self::ScreenshotConfirm => keymap.confirm,
+            Self::ScreenshotCancel => keymap.cancel,
+        }
+    }
+}
+
+/// 用户修改过的快捷键，未出现的动作使用默认值；空字符串表示取消绑定
+#[derive(Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(transparent)]
+pub struct Keymap {
+    overrides: BTreeMap<Action, String>,
+}
+
+impl Keymap {
+    pub fn binding(&self, action: Action) -> &str {
+        self.overrides
+            .get(&action)
+            .map(String::as_str)
+            .unwrap_or_else(|| action.default_binding())
+    }
+
+    /// 与默认值相同时删除记录，配置文件里只保留改动
+    pub fn set(&mut self, action: Action, binding: String) {
+        if binding == action.default_binding() {
+            self.overrides.remove(&action);
+        } else {
+            self.overrides.insert(action, binding);
+        }
+    }
+
+    pub fn pressed(&self, input: &InputState, action: Action) -> bool {
+        egui_hotkey_pressed(input, self.binding(action))
+    }
+}
+
+/// 找出范围重叠且组合键相同的绑定，返回每个冲突项及与之冲突的另一项
+pub fn find_conflicts<K: Copy + Eq + Hash>(
+    bindings: impl IntoIterator<Item = (K, KeyScope, String)>,
+) -> HashMap<K, K> {
+    let parsed: Vec<(K, KeyScope, ParsedHotkey)> = bindings
+        .into_iter()
+        .filter_map(|(key, scope, binding)| Some((key, scope, parse_hotkey_str(&binding)?)))
+        .collect();
+    let mut conflicts = HashMap::new();
+    for (i, (key_a, scope_a, hotkey_a)) in parsed.iter().enumerate() {
+        for (key_b, scope_b, hotkey_b) in &parsed[i + 1..] {
+            if hotkey_a == hotkey_b && scope_a.overlaps(*scope_b) {
+                conflicts.entry(*key_a).or_insert(*key_b);
+                conflicts.entry(*key_b).or_insert(*key_a);
+            }
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, KeyScope, find_conflicts};
+
+    #[test]
+    fn default_bindings_do_not_conflict() {
+        let conflicts = find_conflicts(
+            Action::ALL
+                .iter()
+                .map(|a| (*a, a.scope(), a.default_binding().to_string())),
+        );
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn conflicts_respect_scope_and_modifier_order() {
+        let conflicts = find_conflicts([
+            (1, KeyScope::Single, "Ctrl+Shift+K".to_string()),
+            (2, KeyScope::Viewer, "Shift+Ctrl+K".to_string()),
+            (3, KeyScope::Screenshot, "Ctrl+Shift+K".to_string()),
+            (4, KeyScope::Grid, "Escape".to_string()),
+            (5, KeyScope::Compare, "Escape".to_string()),
+            (6, KeyScope::Global, "Escape".to_string()),
+        ]);
+        assert_eq!(conflicts.get(&1), Some(&2));
+        assert_eq!(conflicts.get(&2), Some(&1));
+        assert!(!conflicts.contains_key(&3));
+        assert_eq!(conflicts.get(&4), Some(&6));
+        assert_eq!(conflicts.get(&5), Some(&6));
+        assert!(conflicts.contains_key(&6));
+    }
+}
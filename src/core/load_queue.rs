@@ -0,0 +1,186 @@
+use std::path::{Path, PathBuf};
+
+/// 加载任务的优先级，值越小越先解码
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum LoadPriority {
+    /// 正在查看的图片
+    Current,
+    /// 视口内可见的缩略图
+    Visible,
+    /// 当前图片前后相邻的图片
+    Neighbor,
+    /// 视口附近、即将滚动到的缩略图
+    Prefetch,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadJob {
+    pub path: PathBuf,
+    /// 缩略图的尺寸上限，None 表示原图
+    pub size: Option<(u32, u32)>,
+    pub priority: LoadPriority,
+    generation: u64,
+    seq: u64,
+}
+
+impl LoadJob {
+    pub fn is_thumbnail(&self) -> bool {
+        self.size.is_some()
+    }
+}
+
+/// 等待解码的任务队列。工作线程每次取出优先级最高的任务；
+/// 调用 `supersede` 后代数加一，之前排队的任务在解码前被丢弃
+#[derive(Default)]
+pub struct LoadQueue {
+    jobs: Vec<LoadJob>,
+    generation: u64,
+    next_seq: u64,
+}
+
+impl LoadQueue {
+    /// 加入任务，同一路径和尺寸的任务已在排队时只提升优先级。返回是否新增了任务
+    pub fn push(
+        &mut self,
+        path: PathBuf,
+        size: Option<(u32, u32)>,
+        priority: LoadPriority,
+    ) -> bool {
+        if let Some(job) = self
+            .jobs
+            .iter_mut()
+            .find(|job| job.path == path && job.size == size)
+        {
+            job.priority = job.priority.min(priority);
+            job.generation = self.generation;
+            return false;
+        }
+        self.jobs.push(LoadJob {
+            path,
+            size,
+            priority,
+            generation: self.generation,
+            seq: self.next_seq,
+        });
+        self.next_seq += 1;
+        true
+    }
+
+    /// 取出优先级最高的任务，同优先级先进先出；过期任务直接丢弃
+    pub fn pop(&mut self) -> Option<LoadJob> {
+        let generation = self.generation;
+        self.jobs.retain(|job| job.generation == generation);
+        let best = self
+            .jobs
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, job)| (job.priority, job.seq))
+            .map(|(i, _)| i)?;
+        Some(self.jobs.swap_remove(best))
+    }
+
+    /// 让此前排队的任务全部作废
+    pub fn supersede(&mut self) {
+        self.generation += 1;
+    }
+
+    /// 按 `priority_of` 重新评估排队中的缩略图任务，返回 None 的任务被取消。返回被取消的路径
+    pub fn reprioritize_thumbnails(
+        &mut self,
+        priority_of: impl Fn(&Path) -> Option<LoadPriority>,
+    ) -> Vec<PathBuf> {
+        let mut cancelled = Vec::new();
+        self.jobs.retain_mut(|job| {
+            if !job.is_thumbnail() {
+                return true;
+            }
+            match priority_of(&job.path) {
+                Some(priority) => {
+                    job.priority = priority;
+                    true
+                }
+                None => {
+                    cancelled.push(job.path.clone());
+                    false
+                }
+            }
+        });
+        cancelled
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.jobs
+            .iter()
+            .filter(|job| job.generation == self.generation)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LoadPriority, LoadQueue};
+    use std::path::PathBuf;
+
+    fn path(name: &str) -> PathBuf {
+        PathBuf::from(name)
+    }
+
+    #[test]
+    fn pops_by_priority_then_fifo() {
+        let mut queue = LoadQueue::default();
+        queue.push(path("a"), Some((1, 1)), LoadPriority::Prefetch);
+        queue.push(path("b"), Some((1, 1)), LoadPriority::Visible);
+        queue.push(path("c"), Some((1, 1)), LoadPriority::Visible);
+        queue.push(path("d"), None, LoadPriority::Current);
+
+        let order: Vec<_> = std::iter::from_fn(|| queue.pop())
+            .map(|job| job.path)
+            .collect();
+        assert_eq!(order, vec![path("d"), path("b"), path("c"), path("a")]);
+    }
+
+    #[test]
+    fn duplicate_push_only_raises_priority() {
+        let mut queue = LoadQueue::default();
+        assert!(queue.push(path("a"), None, LoadPriority::Neighbor));
+        assert!(!queue.push(path("a"), None, LoadPriority::Current));
+        assert!(!queue.push(path("a"), None, LoadPriority::Prefetch));
+        assert!(queue.push(path("a"), Some((8, 8)), LoadPriority::Visible));
+
+        assert_eq!(queue.len(), 2);
+        let job = queue.pop().expect("queue should not be empty");
+        assert_eq!(job.priority, LoadPriority::Current);
+        assert!(!job.is_thumbnail());
+    }
+
+    #[test]
+    fn superseded_jobs_are_dropped() {
+        let mut queue = LoadQueue::default();
+        queue.push(path("old"), None, LoadPriority::Current);
+        queue.supersede();
+        queue.push(path("new"), None, LoadPriority::Current);
+
+        assert_eq!(queue.pop().map(|job| job.path), Some(path("new")));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn reprioritize_cancels_offscreen_thumbnails_only() {
+        let mut queue = LoadQueue::default();
+        queue.push(path("visible"), Some((1, 1)), LoadPriority::Prefetch);
+        queue.push(path("gone"), Some((1, 1)), LoadPriority::Visible);
+        queue.push(path("full"), None, LoadPriority::Visible);
+
+        let cancelled = queue.reprioritize_thumbnails(|p| {
+            (p == path("visible").as_path()).then_some(LoadPriority::Visible)
+        });
+
+        assert_eq!(cancelled, vec![path("gone")]);
+        assert_eq!(queue.len(), 2);
+        assert_eq!(
+            queue.pop().map(|job| (job.path, job.priority)),
+            Some((path("visible"), LoadPriority::Visible))
+        );
+    }
+}
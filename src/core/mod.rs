@@ -1,6 +1,10 @@
+pub mod byte_cache;
 pub mod config_manager;
 pub mod hotkey_parser;
 pub mod hotkeys;
 pub mod image_loader;
+pub mod keymap;
 pub mod launch;
+pub mod load_queue;
 pub mod logging;
+pub mod resize;
@@ -0,0 +1,188 @@
+use wide::f32x4;
+
+/// 等比缩放到不超过 (max_w, max_h) 时的目标尺寸，取整方式与 image crate 的 `thumbnail` 一致
+pub fn fit_within(width: u32, height: u32, max_w: u32, max_h: u32) -> (u32, u32) {
+    let ratio = (max_w as f64 / width as f64).min(max_h as f64 / height as f64);
+    let w = ((width as f64 * ratio).round() as u32).max(1);
+    let h = ((height as f64 * ratio).round() as u32).max(1);
+    (w, h)
+}
+
+/// 按整数倍对交错排列的 8 位像素做区域平均缩小，返回像素和新的宽高。
+/// 逐行累加到 u32 缓冲区，内层循环是连续内存上的加法，编译器可以自动向量化。
+/// 不足一整块的右侧和底部余数直接舍弃，最多少于 `factor` 个像素
+pub fn box_reduce(
+    pixels: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    factor: usize,
+) -> (Vec<u8>, usize, usize) {
+    let factor = factor.max(1);
+    let out_w = width / factor;
+    let out_h = height / factor;
+    let row_len = width * channels;
+    let block_len = factor * channels;
+    let area = (factor * factor) as u32;
+
+    let mut acc = vec![0u32; out_w * block_len];
+    let mut out = Vec::with_capacity(out_w * out_h * channels);
+    for oy in 0..out_h {
+        acc.fill(0);
+        let rows = &pixels[oy * factor * row_len..(oy + 1) * factor * row_len];
+        for row in rows.chunks_exact(row_len) {
+            for (sum, &p) in acc.iter_mut().zip(row) {
+                *sum += p as u32;
+            }
+        }
+        for block in acc.chunks_exact(block_len) {
+            for c in 0..channels {
+                let sum: u32 = block[c..].iter().step_by(channels).sum();
+                out.push(((sum + area / 2) / area) as u8);
+            }
+        }
+    }
+    (out, out_w, out_h)
+}
+
+/// Catmull-Rom 卷积核，支撑范围 [-2, 2]
+fn catmull_rom(x: f32) -> f32 {
+    let x = x.abs();
+    if x < 1.0 {
+        (1.5 * x - 2.5) * x * x + 1.0
+    } else if x < 2.0 {
+        ((-0.5 * x + 2.5) * x - 4.0) * x + 2.0
+    } else {
+        0.0
+    }
+}
+
+/// 一个输出坐标用到的源像素起点和归一化后的权重
+struct Taps {
+    start: usize,
+    weights: Vec<f32>,
+}
+
+/// 缩小时按比例放宽卷积核，避免跳过源像素产生锯齿
+fn taps(src: usize, dst: usize) -> Vec<Taps> {
+    let scale = src as f32 / dst as f32;
+    let filter_scale = scale.max(1.0);
+    let support = 2.0 * filter_scale;
+    (0..dst)
+        .map(|i| {
+            let center = (i as f32 + 0.5) * scale;
+            let start = (center - support).floor().max(0.0) as usize;
+            let end = ((center + support).ceil() as usize).min(src);
+            let mut weights: Vec<f32> = (start..end)
+                .map(|j| catmull_rom((j as f32 + 0.5 - center) / filter_scale))
+                .collect();
+            let sum: f32 = weights.iter().sum();
+            if sum != 0.0 {
+                weights.iter_mut().for_each(|w| *w /= sum);
+            }
+            Taps { start, weights }
+        })
+        .collect()
+}
+
+/// 可分离的 Catmull-Rom 重采样，用于整数倍区域平均之后剩下的非整数比例缩放。
+/// 每个像素的通道（最多 4 个）装进一个 f32x4，先横向再纵向，各通道的乘加一条指令完成
+pub fn resample(
+    pixels: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    out_w: usize,
+    out_h: usize,
+) -> Vec<u8> {
+    let load = |px: &[u8]| {
+        let mut lanes = [0.0f32; 4];
+        for (lane, &p) in lanes.iter_mut().zip(px) {
+            *lane = p as f32;
+        }
+        f32x4::new(lanes)
+    };
+
+    let columns = taps(width, out_w);
+    let mut horizontal = vec![f32x4::ZERO; out_w * height];
+    for (src_row, dst_row) in pixels
+        .chunks_exact(width * channels)
+        .zip(horizontal.chunks_exact_mut(out_w))
+    {
+        for (dst, tap) in dst_row.iter_mut().zip(&columns) {
+            let src = src_row[tap.start * channels..].chunks_exact(channels);
+            *dst = tap
+                .weights
+                .iter()
+                .zip(src)
+                .fold(f32x4::ZERO, |acc, (w, px)| {
+                    load(px).mul_add(f32x4::splat(*w), acc)
+                });
+        }
+    }
+
+    let max = f32x4::splat(255.0);
+    let mut acc = vec![f32x4::ZERO; out_w];
+    let mut out = Vec::with_capacity(out_w * out_h * channels);
+    for tap in taps(height, out_h) {
+        acc.fill(f32x4::ZERO);
+        for (row, w) in horizontal[tap.start * out_w..]
+            .chunks_exact(out_w)
+            .zip(&tap.weights)
+        {
+            let w = f32x4::splat(*w);
+            for (sum, &p) in acc.iter_mut().zip(row) {
+                *sum = p.mul_add(w, *sum);
+            }
+        }
+        for sum in &acc {
+            let px = sum.round().max(f32x4::ZERO).min(max).to_array();
+            out.extend(px[..channels].iter().map(|v| *v as u8));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{box_reduce, fit_within, resample};
+
+    #[test]
+    fn fit_within_keeps_aspect_ratio() {
+        assert_eq!(fit_within(400, 100, 64, 64), (64, 16));
+        assert_eq!(fit_within(3000, 4000, 512, 256), (192, 256));
+        assert_eq!(fit_within(10000, 1, 64, 64), (64, 1));
+    }
+
+    #[test]
+    fn box_reduce_averages_blocks_and_drops_remainder() {
+        // 5x2 的 RGB 图，按 2 倍缩小得到 2x1，最右一列被舍弃
+        #[rustfmt::skip]
+        let pixels: Vec<u8> = vec![
+            0, 0, 0,   2, 4, 6,   10, 10, 10,   20, 20, 20,   255, 255, 255,
+            4, 8, 12,  6, 0, 2,   30, 30, 30,   40, 40, 40,   255, 255, 255,
+        ];
+        let (out, w, h) = box_reduce(&pixels, 5, 2, 3, 2);
+        assert_eq!((w, h), (2, 1));
+        assert_eq!(out, vec![3, 3, 5, 25, 25, 25]);
+    }
+
+    #[test]
+    fn resample_keeps_flat_color_and_averages_edges() {
+        // 纯色缩放后颜色不变
+        let flat: Vec<u8> = [10u8, 120, 240, 255].repeat(7 * 5);
+        let out = resample(&flat, 7, 5, 4, 4, 3);
+        assert_eq!(out, [10u8, 120, 240, 255].repeat(4 * 3));
+
+        // 左黑右白的 RGB 图缩小后左端仍是黑、右端仍是白，中间是过渡
+        let row: Vec<u8> = (0..6)
+            .flat_map(|x| [if x < 3 { 0 } else { 255 }; 3])
+            .collect();
+        let pixels = row.repeat(2);
+        let out = resample(&pixels, 6, 2, 3, 4, 1);
+        assert_eq!(out.len(), 4 * 3);
+        assert_eq!(out[0], 0);
+        assert_eq!(out[9], 255);
+        assert!(out[3] < out[6]);
+    }
+}
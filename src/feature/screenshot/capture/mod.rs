@@ -1,6 +1,7 @@
 mod actions;
 mod capture_impl;
 
+use crate::core::keymap::Action;
 use crate::feature::screenshot::canvas::{self, CanvasState};
 use crate::feature::screenshot::help_box;
 use crate::feature::screenshot::magnifier::handle_magnifier;
@@ -351,8 +352,9 @@ pub fn draw_screenshot_ui_inside(
         }
     }
 
-    let undo_requested = ui.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::Z));
-    let redo_requested = ui.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::Y));
+    let pressed = |action: Action| ui.input(|i| config.keymap.pressed(i, action));
+    let undo_requested = pressed(Action::ScreenshotUndo);
+    let redo_requested = pressed(Action::ScreenshotRedo);
 
     if undo_requested {
         state.undo_last();
@@ -362,7 +364,7 @@ pub fn draw_screenshot_ui_inside(
         state.redo_last();
     }
 
-    if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+    if pressed(Action::ScreenshotConfirm) {
         let can_save_to_clipboard = state.has_positive_selection();
 
         if can_save_to_clipboard && state.input.active_text_input.is_none() {
@@ -370,7 +372,7 @@ pub fn draw_screenshot_ui_inside(
         }
     }
 
-    if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+    if pressed(Action::ScreenshotCancel) {
         action = ScreenshotAction::Close;
     }
 
@@ -5,7 +5,8 @@ use eframe::egui::{Color32, Painter, Pos2, Rect, Shape, Stroke, StrokeKind, Vec2
 use image::{Rgba, RgbaImage};
 use std::sync::LazyLock;
 
-static EMBEDDED_FONT: LazyLock<Option<FontRef<'static>>> = LazyLock::new(|| {
+/// 内嵌字体，截图文字和导出图片上的文字共用
+pub static EMBEDDED_FONT: LazyLock<Option<FontRef<'static>>> = LazyLock::new(|| {
     let data = crate::ui::resources::APP_FONT;
     match FontRef::try_from_slice(data) {
         Ok(font) => Some(font),
@@ -1,3 +1,4 @@
+use crate::core::keymap::Action;
 use crate::feature::screenshot::canvas::phys_to_local;
 use crate::feature::screenshot::state::ScreenshotState;
 use crate::i18n::lang::get_i18n_text;
@@ -87,9 +88,17 @@ pub fn render_help_box(ui: &mut Ui, state: &ScreenshotState, global_offset_phys:
                                 RichText::new(text_bundle.help.exit_with_right_click)
                                     .font(font_id.clone()),
                             );
-                            ui.label(RichText::new(text_bundle.help.esc).font(font_id.clone()));
-                            ui.label(RichText::new(text_bundle.help.undo).font(font_id.clone()));
-                            ui.label(RichText::new(text_bundle.help.redo).font(font_id.clone()));
+                            let keymap_row = |action: Action, desc: &str| {
+                                RichText::new(format!(
+                                    "{} : {}",
+                                    config.keymap.binding(action),
+                                    desc
+                                ))
+                                .font(font_id.clone())
+                            };
+                            ui.label(keymap_row(Action::ScreenshotCancel, text_bundle.help.esc));
+                            ui.label(keymap_row(Action::ScreenshotUndo, text_bundle.help.undo));
+                            ui.label(keymap_row(Action::ScreenshotRedo, text_bundle.help.redo));
                             ui.label(
                                 RichText::new(format!(
                                     "{} : {}",
@@ -97,10 +106,10 @@ pub fn render_help_box(ui: &mut Ui, state: &ScreenshotState, global_offset_phys:
                                 ))
                                 .font(font_id.clone()),
                             );
-                            ui.label(
-                                RichText::new(text_bundle.help.copy_to_clipboard)
-                                    .font(font_id.clone()),
-                            );
+                            ui.label(keymap_row(
+                                Action::ScreenshotConfirm,
+                                text_bundle.help.copy_to_clipboard,
+                            ));
                             ui.add_space(6.0);
 
                             // --- 2. 图标说明 ---
@@ -0,0 +1,96 @@
+use crate::i18n::lang::TextBundle;
+use crate::model::config::{ImageBackground, ViewerConfig};
+use egui::epaint::RectShape;
+use egui::{
+    Color32, ColorImage, Context, CornerRadius, Id, Painter, Pos2, Rect, TextureFilter,
+    TextureHandle, TextureOptions, TextureWrapMode,
+};
+
+/// 棋盘格单个格子的边长
+const CHECKER_TILE: f32 = 8.0;
+const CHECKER_LIGHT: Color32 = Color32::from_gray(204);
+const CHECKER_DARK: Color32 = Color32::from_gray(153);
+
+impl ImageBackground {
+    pub fn label(self, text: &TextBundle) -> &'static str {
+        match self {
+            Self::Checkerboard => text.background.checkerboard,
+            Self::Black => text.background.black,
+            Self::White => text.background.white,
+            Self::Custom => text.background.custom,
+        }
+    }
+}
+
+/// 在图片下方绘制背景，让透明区域与界面底色区分开。`tint` 用于淡入淡出
+pub fn paint_image_background(
+    painter: &Painter,
+    rect: Rect,
+    corner_radius: impl Into<CornerRadius>,
+    config: &ViewerConfig,
+    tint: Color32,
+) {
+    let shape = match config.background {
+        ImageBackground::Checkerboard => {
+            let texture = checker_texture(painter.ctx());
+            let uv = Rect::from_min_size(Pos2::ZERO, rect.size() / (CHECKER_TILE * 2.0));
+            RectShape::filled(rect, corner_radius, tint).with_texture(texture.id(), uv)
+        }
+        ImageBackground::Black => RectShape::filled(rect, corner_radius, Color32::BLACK * tint),
+        ImageBackground::White => RectShape::filled(rect, corner_radius, Color32::WHITE * tint),
+        ImageBackground::Custom => {
+            let [r, g, b] = config.background_color;
+            RectShape::filled(rect, corner_radius, Color32::from_rgb(r, g, b) * tint)
+        }
+    };
+    painter.add(shape);
+}
+
+/// 棋盘格纹理只有 2x2 像素，靠重复平铺和最近邻采样放大成格子
+fn checker_texture(ctx: &Context) -> TextureHandle {
+    let id = Id::new("image_background_checker");
+    if let Some(texture) = ctx.data(|d| d.get_temp::<TextureHandle>(id)) {
+        return texture;
+    }
+    let image = ColorImage::new(
+        [2, 2],
+        vec![CHECKER_LIGHT, CHECKER_DARK, CHECKER_DARK, CHECKER_LIGHT],
+    );
+    let options = TextureOptions {
+        magnification: TextureFilter::Nearest,
+        minification: TextureFilter::Nearest,
+        wrap_mode: TextureWrapMode::Repeat,
+        ..TextureOptions::default()
+    };
+    let texture = ctx.load_texture("image_background_checker", image, options);
+    ctx.data_mut(|d| d.insert_temp(id, texture.clone()));
+    texture
+}
+
+/// 把透明度映射为灰度：完全透明为黑，完全不透明为白
+pub fn alpha_mask(pixels: &[Color32]) -> Vec<Color32> {
+    pixels.iter().map(|p| Color32::from_gray(p.a())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::alpha_mask;
+    use egui::Color32;
+
+    #[test]
+    fn alpha_mask_ignores_color_and_keeps_alpha() {
+        let pixels = [
+            Color32::TRANSPARENT,
+            Color32::from_rgba_unmultiplied(255, 0, 0, 128),
+            Color32::from_rgb(0, 0, 255),
+        ];
+        assert_eq!(
+            alpha_mask(&pixels),
+            vec![
+                Color32::from_gray(0),
+                Color32::from_gray(128),
+                Color32::from_gray(255)
+            ]
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use crate::i18n::lang::get_i18n_text;
+use crate::model::config::{MetadataPolicy, get_context_config};
+use crate::os::current_platform;
+use crate::ui::widgets::toast::ToastManager;
+use crate::utils::metadata::{apply_metadata_policy, exif_for_export};
+use egui::Context;
+use image::codecs::{jpeg::JpegEncoder, png::PngEncoder, webp::WebPEncoder};
+use image::{DynamicImage, ImageEncoder, ImageFormat, ImageReader};
+use rayon::prelude::*;
+use rfd::FileDialog;
+use std::fs::{self, File};
+use std::io::{BufWriter, Cursor};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, Sender, channel};
+
+/// 批量转换的目标格式
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConvertFormat {
+    Png,
+    Jpeg,
+    Webp,
+    Bmp,
+    Tiff,
+}
+
+impl ConvertFormat {
+    pub const ALL: [Self; 5] = [Self::Png, Self::Jpeg, Self::Webp, Self::Bmp, Self::Tiff];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Png => "PNG",
+            Self::Jpeg => "JPEG",
+            Self::Webp => "WebP",
+            Self::Bmp => "BMP",
+            Self::Tiff => "TIFF",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+            Self::Webp => "webp",
+            Self::Bmp => "bmp",
+            Self::Tiff => "tiff",
+        }
+    }
+
+    fn supports_exif(self) -> bool {
+        matches!(self, Self::Png | Self::Jpeg | Self::Webp)
+    }
+
+    fn image_format(self) -> ImageFormat {
+        match self {
+            Self::Png => ImageFormat::Png,
+            Self::Jpeg => ImageFormat::Jpeg,
+            Self::Webp => ImageFormat::WebP,
+            Self::Bmp => ImageFormat::Bmp,
+            Self::Tiff => ImageFormat::Tiff,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BatchOperation {
+    CopyTo,
+    MoveTo,
+    Convert(ConvertFormat),
+    Delete,
+}
+
+impl BatchOperation {
+    fn needs_destination(self) -> bool {
+        !matches!(self, Self::Delete)
+    }
+}
+
+pub struct BatchOutcome {
+    pub operation: BatchOperation,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// 已从原位置消失的文件（移动、删除）
+    pub removed: Vec<PathBuf>,
+    pub destination: Option<PathBuf>,
+}
+
+/// 批量文件操作在后台线程执行，结果通过通道回传给 UI 线程
+pub struct BatchRunner {
+    tx: Sender<BatchOutcome>,
+    rx: Receiver<BatchOutcome>,
+}
+
+impl BatchRunner {
+    pub fn new() -> Self {
+        let (tx, rx) = channel();
+        Self { tx, rx }
+    }
+
+    /// 需要目标文件夹的操作会先弹出文件夹选择框，取消则什么都不做
+    pub fn start(
+        &self,
+        ctx: &Context,
+        paths: Vec<PathBuf>,
+        operation: BatchOperation,
+        toast_manager: &ToastManager,
+    ) {
+        if paths.is_empty() {
+            return;
+        }
+        let text = get_i18n_text(ctx);
+        let processing = text.batch.processing;
+        let policy = get_context_config(ctx).metadata_policy;
+        let tx = self.tx.clone();
+        let ctx = ctx.clone();
+        let toast = toast_manager.clone();
+
+        std::thread::spawn(move || {
+            let destination = if operation.needs_destination() {
+                match FileDialog::new().pick_folder() {
+                    Some(dir) => Some(dir),
+                    None => return,
+                }
+            } else {
+                None
+            };
+
+            toast.loading(processing);
+            let outcome = execute(operation, &paths, destination, policy);
+            let _ = tx.send(outcome);
+            ctx.request_repaint();
+        });
+    }
+
+    pub fn try_recv(&self) -> Option<BatchOutcome> {
+        self.rx.try_recv().ok()
+    }
+}
+
+impl Default for BatchRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn execute(
+    operation: BatchOperation,
+    paths: &[PathBuf],
+    destination: Option<PathBuf>,
+    policy: MetadataPolicy,
+) -> BatchOutcome {
+    let results: Vec<(PathBuf, Result<bool, String>)> = paths
+        .par_iter()
+        .map(|path| {
+            let result = match (operation, destination.as_deref()) {
+                (BatchOperation::CopyTo, Some(dir)) => copy_to(path, dir, policy).map(|_| false),
+                (BatchOperation::MoveTo, Some(dir)) => move_to(path, dir),
+                (BatchOperation::Convert(format), Some(dir)) => {
+                    convert_to(path, dir, format, policy).map(|_| false)
+                }
+                (BatchOperation::Delete, _) => current_platform().move_to_trash(path).map(|_| true),
+                (_, None) => Err("missing destination".to_string()),
+            };
+            (path.clone(), result)
+        })
+        .collect();
+
+    let mut outcome = BatchOutcome {
+        operation,
+        succeeded: 0,
+        failed: 0,
+        removed: Vec::new(),
+        destination,
+    };
+    for (path, result) in results {
+        match result {
+            Ok(removed) => {
+                outcome.succeeded += 1;
+                if removed {
+                    outcome.removed.push(path);
+                }
+            }
+            Err(e) => {
+                outcome.failed += 1;
+                tracing::warn!("批量操作失败 {}: {}", path.display(), e);
+            }
+        }
+    }
+    outcome
+}
+
+/// 复制出去的文件按元数据策略处理，保留全部时直接复制
+fn copy_to(path: &Path, dir: &Path, policy: MetadataPolicy) -> Result<PathBuf, String> {
+    let file_name = path.file_name().ok_or("invalid file name")?;
+    let target = unique_destination(dir, Path::new(file_name));
+    if policy == MetadataPolicy::KeepAll {
+        fs::copy(path, &target).map_err(|e| e.to_string())?;
+    } else {
+        let data = fs::read(path).map_err(|e| e.to_string())?;
+        let data = apply_metadata_policy(&data, policy)?;
+        fs::write(&target, data).map_err(|e| e.to_string())?;
+    }
+    Ok(target)
+}
+
+/// 返回是否真正移动了文件；目标就是原文件夹时视为成功但不移动
+fn move_to(path: &Path, dir: &Path) -> Result<bool, String> {
+    if path.parent() == Some(dir) {
+        return Ok(false);
+    }
+    let file_name = path.file_name().ok_or("invalid file name")?;
+    let target = unique_destination(dir, Path::new(file_name));
+    if fs::rename(path, &target).is_err() {
+        // 跨磁盘时 rename 会失败，退化为复制后删除
+        fs::copy(path, &target).map_err(|e| e.to_string())?;
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    Ok(true)
+}
+
+fn convert_to(
+    path: &Path,
+    dir: &Path,
+    format: ConvertFormat,
+    policy: MetadataPolicy,
+) -> Result<PathBuf, String> {
+    let data = fs::read(path).map_err(|e| e.to_string())?;
+    let img = ImageReader::new(Cursor::new(&data))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?
+        .decode()
+        .map_err(|e| e.to_string())?;
+
+    // JPEG 不支持透明通道，其余格式统一转为 8 位 RGBA 以兼容各编码器
+    let img = match format {
+        ConvertFormat::Jpeg => DynamicImage::ImageRgb8(img.to_rgb8()),
+        _ => DynamicImage::ImageRgba8(img.to_rgba8()),
+    };
+
+    let stem = path.file_stem().ok_or("invalid file name")?;
+    let file_name = Path::new(stem).with_extension(format.extension());
+    let target = unique_destination(dir, &file_name);
+    match exif_for_export(&data, policy) {
+        Some(exif) if format.supports_exif() => save_with_exif(&img, &target, format, exif)?,
+        _ => img
+            .save_with_format(&target, format.image_format())
+            .map_err(|e| e.to_string())?,
+    }
+    Ok(target)
+}
+
+/// 按策略处理过的 EXIF 写入新文件，编码器与 `save_with_format` 的默认选择一致
+fn save_with_exif(
+    img: &DynamicImage,
+    target: &Path,
+    format: ConvertFormat,
+    exif: Vec<u8>,
+) -> Result<(), String> {
+    fn write(
+        img: &DynamicImage,
+        mut encoder: impl ImageEncoder,
+        exif: Vec<u8>,
+    ) -> Result<(), String> {
+        encoder.set_exif_metadata(exif).map_err(|e| e.to_string())?;
+        img.write_with_encoder(encoder).map_err(|e| e.to_string())
+    }
+    let file = BufWriter::new(File::create(target).map_err(|e| e.to_string())?);
+    match format {
+        ConvertFormat::Png => write(img, PngEncoder::new(file), exif),
+        ConvertFormat::Jpeg => write(img, JpegEncoder::new(file), exif),
+        ConvertFormat::Webp => write(img, WebPEncoder::new_lossless(file), exif),
+        ConvertFormat::Bmp | ConvertFormat::Tiff => Err("EXIF not supported".to_string()),
+    }
+}
+
+/// 目标已存在时在文件名后追加序号，避免覆盖
+pub fn unique_destination(dir: &Path, file_name: &Path) -> PathBuf {
+    let target = dir.join(file_name);
+    if !target.exists() {
+        return target;
+    }
+    let stem = file_name
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = file_name
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (1..)
+        .map(|n| dir.join(format!("{stem}_{n}{ext}")))
+        .find(|candidate| !candidate.exists())
+        .unwrap_or(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::unique_destination;
+    use std::{
+        env, fs,
+        path::Path,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    #[test]
+    fn unique_destination_appends_counter_on_conflict() {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System time should be after UNIX_EPOCH")
+            .as_nanos();
+        let dir = env::temp_dir().join(format!("cloverviewer-batch-{timestamp}"));
+        fs::create_dir_all(&dir).expect("Test directory should be created");
+
+        let first = unique_destination(&dir, Path::new("a.png"));
+        assert_eq!(first, dir.join("a.png"));
+
+        fs::write(&first, []).expect("Placeholder should be written");
+        fs::write(dir.join("a_1.png"), []).expect("Placeholder should be written");
+        assert_eq!(
+            unique_destination(&dir, Path::new("a.png")),
+            dir.join("a_2.png")
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::feature::viewer::background::alpha_mask;
+use crate::i18n::lang::TextBundle;
+use egui::ecolor::{gamma_u8_from_linear_f32, linear_f32_from_gamma_u8};
+use egui::{Align2, Color32, FontId, Rect, Ui, Vec2};
+use rayon::prelude::*;
+use std::sync::LazyLock;
+
+/// 线性值转回 sRGB 的查找表精度
+const LINEAR_STEPS: usize = 4096;
+
+/// Machado 等人 (2009) 的色觉缺陷模拟矩阵，严重程度 1.0，作用于线性 RGB
+const PROTANOPIA: [[f32; 3]; 3] = [
+    [0.152286, 1.052583, -0.204868],
+    [0.114503, 0.786281, 0.099216],
+    [-0.003882, -0.048116, 1.051998],
+];
+const DEUTERANOPIA: [[f32; 3]; 3] = [
+    [0.367322, 0.860646, -0.227968],
+    [0.280085, 0.672501, 0.047413],
+    [-0.011820, 0.042940, 0.968881],
+];
+const TRITANOPIA: [[f32; 3]; 3] = [
+    [1.255528, -0.076749, -0.178779],
+    [-0.078411, 0.930809, 0.147602],
+    [0.004733, 0.691367, 0.303900],
+];
+
+static TO_LINEAR: LazyLock<[f32; 256]> =
+    LazyLock::new(|| std::array::from_fn(|i| linear_f32_from_gamma_u8(i as u8)));
+static FROM_LINEAR: LazyLock<Vec<u8>> = LazyLock::new(|| {
+    (0..LINEAR_STEPS)
+        .map(|i| gamma_u8_from_linear_f32(i as f32 / (LINEAR_STEPS - 1) as f32))
+        .collect()
+});
+
+fn to_gamma(linear: f32) -> u8 {
+    FROM_LINEAR[(linear.clamp(0.0, 1.0) * (LINEAR_STEPS - 1) as f32).round() as usize]
+}
+
+/// 单图模式的显示方式：单个通道、亮度或色觉缺陷模拟
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ChannelView {
+    #[default]
+    Normal,
+    Red,
+    Green,
+    Blue,
+    Alpha,
+    Luminance,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl ChannelView {
+    pub const ALL: [Self; 9] = [
+        Self::Normal,
+        Self::Red,
+        Self::Green,
+        Self::Blue,
+        Self::Alpha,
+        Self::Luminance,
+        Self::Deuteranopia,
+        Self::Protanopia,
+        Self::Tritanopia,
+    ];
+
+    pub fn label(self, text: &TextBundle) -> &'static str {
+        let channel = &text.channel;
+        match self {
+            Self::Normal => channel.normal,
+            Self::Red => channel.red,
+            Self::Green => channel.green,
+            Self::Blue => channel.blue,
+            Self::Alpha => channel.alpha,
+            Self::Luminance => channel.luminance,
+            Self::Deuteranopia => channel.deuteranopia,
+            Self::Protanopia => channel.protanopia,
+            Self::Tritanopia => channel.tritanopia,
+        }
+    }
+
+    /// 快捷键循环切换的下一个模式
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|v| *v == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// 对预乘透明度的纹理像素做变换。通道和亮度显示为不透明灰度，色觉模拟保留透明度
+    pub fn transform(self, pixels: &[Color32]) -> Vec<Color32> {
+        let gray = |pick: fn([u8; 4]) -> u8| -> Vec<Color32> {
+            pixels
+                .par_iter()
+                .map(|p| Color32::from_gray(pick(p.to_srgba_unmultiplied())))
+                .collect()
+        };
+        match self {
+            Self::Normal => pixels.to_vec(),
+            Self::Red => gray(|[r, _, _, _]| r),
+            Self::Green => gray(|[_, g, _, _]| g),
+            Self::Blue => gray(|[_, _, b, _]| b),
+            Self::Alpha => alpha_mask(pixels),
+            Self::Luminance => gray(|[r, g, b, _]| {
+                let [r, g, b] = [r, g, b].map(|c| TO_LINEAR[c as usize]);
+                to_gamma(0.2126 * r + 0.7152 * g + 0.0722 * b)
+            }),
+            Self::Deuteranopia => simulate(pixels, &DEUTERANOPIA),
+            Self::Protanopia => simulate(pixels, &PROTANOPIA),
+            Self::Tritanopia => simulate(pixels, &TRITANOPIA),
+        }
+    }
+}
+
+fn simulate(pixels: &[Color32], matrix: &[[f32; 3]; 3]) -> Vec<Color32> {
+    pixels
+        .par_iter()
+        .map(|p| {
+            let [r, g, b, a] = p.to_srgba_unmultiplied();
+            let rgb = [r, g, b].map(|c| TO_LINEAR[c as usize]);
+            let [r, g, b] =
+                matrix.map(|row| to_gamma(row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]));
+            Color32::from_rgba_unmultiplied(r, g, b, a)
+        })
+        .collect()
+}
+
+/// 非正常显示时在视图顶部居中标出当前模式
+pub fn draw_channel_indicator(ui: &Ui, rect: Rect, view: ChannelView, text: &TextBundle) {
+    if view == ChannelView::Normal {
+        return;
+    }
+    let painter = ui.painter_at(rect);
+    let galley = painter.layout_no_wrap(
+        view.label(text).to_string(),
+        FontId::proportional(14.0),
+        Color32::WHITE,
+    );
+    let padding = Vec2::new(10.0, 4.0);
+    let badge = Align2::CENTER_TOP.anchor_size(
+        rect.center_top() + Vec2::new(0.0, 12.0),
+        galley.size() + padding * 2.0,
+    );
+    painter.rect_filled(
+        badge,
+        6.0,
+        Color32::from_rgba_unmultiplied(200, 120, 0, 220),
+    );
+    painter.galley(badge.min + padding, galley, Color32::WHITE);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChannelView;
+    use egui::Color32;
+
+    #[test]
+    fn channels_and_simulations_map_pixels() {
+        let pixels = [
+            Color32::from_rgb(200, 100, 50),
+            Color32::from_rgba_unmultiplied(255, 0, 0, 128),
+        ];
+        assert_eq!(
+            ChannelView::Green.transform(&pixels),
+            vec![Color32::from_gray(100), Color32::from_gray(0)]
+        );
+        assert_eq!(
+            ChannelView::Luminance.transform(&[Color32::WHITE, Color32::BLACK]),
+            vec![Color32::WHITE, Color32::BLACK]
+        );
+
+        // 灰色不受色觉模拟影响，透明度保留
+        let gray = Color32::from_gray(128);
+        assert_eq!(ChannelView::Deuteranopia.transform(&[gray]), vec![gray]);
+        let red = ChannelView::Protanopia.transform(&pixels[1..]);
+        assert_eq!(red[0].a(), 128);
+        // 红色盲看纯红偏暗且不再是红色主导
+        let [r, g, _, _] = red[0].to_srgba_unmultiplied();
+        assert!(r < 255 && g > 0);
+
+        assert_eq!(ChannelView::Tritanopia.next(), ChannelView::Normal);
+    }
+}
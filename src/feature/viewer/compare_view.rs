@@ -0,0 +1,88 @@
+use crate::feature::viewer::background::paint_image_background;
+use crate::feature::viewer::viewer_state::ViewerState;
+use crate::i18n::lang::get_i18n_text;
+use crate::model::config::get_context_config;
+use crate::model::session::ViewMode;
+use eframe::egui;
+use egui::{Align2, Color32, Context, FontId, Pos2, Rect, Spinner, Ui, Vec2};
+
+const COMPARE_GAP: f32 = 8.0;
+const COMPARE_LABEL_HEIGHT: f32 = 24.0;
+
+/// 并排对比视图，每张图在各自的格子内等比适配
+pub fn draw_compare_view(ctx: &Context, ui: &mut Ui, viewer: &mut ViewerState) {
+    let text = get_i18n_text(ctx);
+    let config = get_context_config(ctx);
+    let rect = ui.available_rect_before_wrap();
+    ui.allocate_rect(rect, egui::Sense::hover());
+
+    if viewer.compare.is_empty() {
+        viewer.view_mode = ViewMode::Grid;
+        return;
+    }
+
+    // 两张左右排列，三到四张排成两行
+    let count = viewer.compare.len();
+    let columns = if count <= 2 { count } else { 2 };
+    let rows = count.div_ceil(columns);
+    let cell_size = Vec2::new(
+        (rect.width() - COMPARE_GAP * (columns as f32 + 1.0)) / columns as f32,
+        (rect.height() - COMPARE_GAP * (rows as f32 + 1.0)) / rows as f32,
+    );
+
+    let painter = ui.painter_at(rect);
+    for (i, path) in viewer.compare.iter().enumerate() {
+        let (row, col) = (i / columns, i % columns);
+        let cell_min = rect.min
+            + Vec2::new(
+                COMPARE_GAP + col as f32 * (cell_size.x + COMPARE_GAP),
+                COMPARE_GAP + row as f32 * (cell_size.y + COMPARE_GAP),
+            );
+        let cell = Rect::from_min_size(cell_min, cell_size);
+        painter.rect_filled(cell, 6.0, Color32::from_gray(30));
+
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        painter.text(
+            Pos2::new(cell.center().x, cell.min.y + COMPARE_LABEL_HEIGHT * 0.5),
+            Align2::CENTER_CENTER,
+            name,
+            FontId::proportional(13.0),
+            Color32::LIGHT_GRAY,
+        );
+
+        let image_area = Rect::from_min_max(
+            Pos2::new(cell.min.x, cell.min.y + COMPARE_LABEL_HEIGHT),
+            cell.max,
+        )
+        .shrink(COMPARE_GAP);
+
+        if let Some(texture) = viewer.texture_cache.get(path).map(|cached| &cached.texture) {
+            let tex_size = texture.size_vec2();
+            let scale = (image_area.width() / tex_size.x)
+                .min(image_area.height() / tex_size.y)
+                .min(1.0);
+            let target = Rect::from_center_size(image_area.center(), tex_size * scale);
+            paint_image_background(&painter, target, 0, &config.viewer, Color32::WHITE);
+            painter.image(
+                texture.id(),
+                target,
+                Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
+                Color32::WHITE,
+            );
+        } else if viewer.thumbs.failed.contains(path) {
+            painter.text(
+                image_area.center(),
+                Align2::CENTER_CENTER,
+                text.viewer.error,
+                FontId::proportional(14.0),
+                Color32::RED,
+            );
+        } else {
+            let spinner_rect = Rect::from_center_size(image_area.center(), Vec2::splat(32.0));
+            ui.put(spinner_rect, Spinner::new().size(32.0));
+        }
+    }
+}
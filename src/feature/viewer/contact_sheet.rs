@@ -0,0 +1,544 @@
+use crate::core::image_loader::ImageLoader;
+use crate::core::resize;
+use crate::feature::screenshot::draw::EMBEDDED_FONT;
+use crate::i18n::lang::{TextBundle, get_i18n_text};
+use crate::model::session::{ContactSheetOptions, PageSize, SheetFormat};
+use crate::ui::widgets::modal::{ModalAction, ModalFrame};
+use crate::ui::widgets::toast::ToastManager;
+use crate::utils::image::unmultiplied_rgba;
+use crate::utils::pdf::{PdfPage, images_to_pdf};
+use crate::utils::time::format_system_time;
+use ab_glyph::{FontRef, PxScale};
+use egui::{ComboBox, Context, Slider};
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, Rgba, RgbaImage, imageops};
+use imageproc::drawing::{draw_text_mut, text_size};
+use rayon::prelude::*;
+use rfd::FileDialog;
+use std::path::{Path, PathBuf};
+
+/// 纸张尺寸按此分辨率换算为像素
+pub const PAGE_DPI: f32 = 150.0;
+pub const COLUMNS_RANGE: std::ops::RangeInclusive<u32> = 1..=12;
+pub const CELL_SIZE_RANGE: std::ops::RangeInclusive<u32> = 80..=600;
+pub const MARGIN_RANGE: std::ops::RangeInclusive<u32> = 0..=200;
+/// 单元格之间的间距
+const GAP: u32 = 16;
+/// 自动尺寸时单页的最大高度，超出后像固定纸张一样分页，避免生成超大画布和超出 JPEG 的尺寸上限
+const MAX_AUTO_HEIGHT: u32 = 8192;
+const CAPTION_SIZE: f32 = 18.0;
+const LINE_HEIGHT: u32 = 22;
+const BACKGROUND: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const PLACEHOLDER: Rgba<u8> = Rgba([225, 225, 225, 255]);
+const CAPTION_COLOR: Rgba<u8> = Rgba([60, 60, 60, 255]);
+
+impl SheetFormat {
+    pub const ALL: [Self; 3] = [Self::Png, Self::Jpeg, Self::Pdf];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Png => "PNG",
+            Self::Jpeg => "JPEG",
+            Self::Pdf => "PDF",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+            Self::Pdf => "pdf",
+        }
+    }
+}
+
+impl PageSize {
+    pub const ALL: [Self; 4] = [Self::Auto, Self::A4, Self::Letter, Self::A3];
+
+    pub fn label(self, text: &TextBundle) -> &'static str {
+        match self {
+            Self::Auto => text.contact_sheet.page_auto,
+            Self::A4 => "A4",
+            Self::Letter => "Letter",
+            Self::A3 => "A3",
+        }
+    }
+
+    /// 纵向时的宽高（毫米）
+    fn millimeters(self) -> Option<(f32, f32)> {
+        match self {
+            Self::Auto => None,
+            Self::A4 => Some((210.0, 297.0)),
+            Self::Letter => Some((215.9, 279.4)),
+            Self::A3 => Some((297.0, 420.0)),
+        }
+    }
+}
+
+impl ContactSheetOptions {
+    fn caption_lines(&self) -> u32 {
+        [
+            self.caption_name,
+            self.caption_date,
+            self.caption_dimensions,
+        ]
+        .iter()
+        .filter(|on| **on)
+        .count() as u32
+    }
+}
+
+/// 排版结果，单位均为像素
+#[derive(Debug, PartialEq, Eq)]
+pub struct SheetLayout {
+    pub page_width: u32,
+    pub page_height: u32,
+    pub columns: u32,
+    /// 缩略图区域为 `cell_size` 见方，标题在其下方
+    pub cell_size: u32,
+    pub caption_height: u32,
+    pub rows_per_page: u32,
+    pub pages: u32,
+    pub margin: u32,
+}
+
+impl SheetLayout {
+    fn row_height(&self) -> u32 {
+        self.cell_size + self.caption_height
+    }
+
+    fn per_page(&self) -> usize {
+        (self.columns * self.rows_per_page) as usize
+    }
+
+    /// 第 `index` 张图片所在的页和缩略图区域左上角
+    pub fn cell_origin(&self, index: usize) -> (usize, u32, u32) {
+        let per_page = self.per_page();
+        let page = index / per_page;
+        let slot = (index % per_page) as u32;
+        let (row, column) = (slot / self.columns, slot % self.columns);
+        let x = self.margin + column * (self.cell_size + GAP);
+        let y = self.margin + row * (self.row_height() + GAP);
+        (page, x, y)
+    }
+}
+
+/// 根据选项和图片数量计算页面尺寸、单元格大小和页数
+pub fn compute_layout(options: &ContactSheetOptions, count: usize) -> SheetLayout {
+    let columns = options.columns.max(1);
+    let margin = options.margin;
+    let caption_height = options.caption_lines() * LINE_HEIGHT;
+    let rows = (count.max(1) as u32).div_ceil(columns);
+    let span = |cells: u32, size: u32| cells * size + cells.saturating_sub(1) * GAP;
+
+    let Some((w_mm, h_mm)) = options.page.millimeters() else {
+        let cell_size = options.cell_size.max(1);
+        let row_height = cell_size + caption_height;
+        let max_rows = (MAX_AUTO_HEIGHT.saturating_sub(margin * 2) + GAP) / (row_height + GAP);
+        let rows_per_page = rows.min(max_rows.max(1));
+        return SheetLayout {
+            page_width: margin * 2 + span(columns, cell_size),
+            page_height: margin * 2 + span(rows_per_page, row_height),
+            columns,
+            cell_size,
+            caption_height,
+            rows_per_page,
+            pages: rows.div_ceil(rows_per_page),
+            margin,
+        };
+    };
+
+    let to_px = |mm: f32| (mm / 25.4 * PAGE_DPI).round() as u32;
+    let (mut page_width, mut page_height) = (to_px(w_mm), to_px(h_mm));
+    if options.landscape {
+        std::mem::swap(&mut page_width, &mut page_height);
+    }
+    // 边距过大时至少留出 1 像素的单元格，避免除零
+    let usable_width = page_width.saturating_sub(margin * 2 + (columns - 1) * GAP);
+    let cell_size = (usable_width / columns).max(1);
+    let usable_height = page_height.saturating_sub(margin * 2) + GAP;
+    let rows_per_page = (usable_height / (cell_size + caption_height + GAP)).max(1);
+    SheetLayout {
+        page_width,
+        page_height,
+        columns,
+        cell_size,
+        caption_height,
+        rows_per_page,
+        pages: rows.div_ceil(rows_per_page),
+        margin,
+    }
+}
+
+struct Cell {
+    thumbnail: Option<RgbaImage>,
+    captions: Vec<String>,
+}
+
+fn load_cell(path: &Path, options: &ContactSheetOptions, size: u32) -> Cell {
+    let decoded = ImageLoader::decode_thumbnail(&path.to_path_buf(), (size, size));
+    let (thumbnail, properties) = match decoded {
+        Ok((color_image, properties)) => {
+            let thumbnail = unmultiplied_rgba(&color_image.pixels, color_image.size).map(|img| {
+                let (fit_w, fit_h) = resize::fit_within(img.width(), img.height(), size, size);
+                if (fit_w, fit_h) == img.dimensions() {
+                    img
+                } else {
+                    imageops::thumbnail(&img, fit_w, fit_h)
+                }
+            });
+            (thumbnail, Some(properties))
+        }
+        Err(e) => {
+            tracing::warn!("索引图缩略图加载失败 {}: {:?}", path.display(), e);
+            (None, None)
+        }
+    };
+
+    let mut captions = Vec::new();
+    if options.caption_name {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        captions.push(name.into_owned());
+    }
+    if options.caption_date {
+        let date = properties
+            .as_ref()
+            .map(|p| p.date.clone())
+            .filter(|d| !d.is_empty())
+            .or_else(|| {
+                let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+                format_system_time(modified)
+            });
+        captions.push(date.unwrap_or_default());
+    }
+    if options.caption_dimensions {
+        let dimensions = properties
+            .as_ref()
+            .filter(|p| p.width > 0)
+            .map(|p| format!("{} x {}", p.width, p.height));
+        captions.push(dimensions.unwrap_or_default());
+    }
+    Cell {
+        thumbnail,
+        captions,
+    }
+}
+
+/// 超出宽度时截断并加省略号
+fn fit_caption(font: &FontRef, scale: PxScale, caption: &str, max_width: u32) -> String {
+    if text_size(scale, font, caption).0 <= max_width {
+        return caption.to_string();
+    }
+    let mut chars: Vec<char> = caption.chars().collect();
+    while !chars.is_empty() {
+        chars.pop();
+        let candidate = format!("{}…", chars.iter().collect::<String>());
+        if text_size(scale, font, &candidate).0 <= max_width {
+            return candidate;
+        }
+    }
+    String::new()
+}
+
+/// 解码一页的缩略图并排版，逐页生成，内存中只保留当前页
+fn render_page(
+    paths: &[PathBuf],
+    options: &ContactSheetOptions,
+    layout: &SheetLayout,
+) -> RgbaImage {
+    let cells: Vec<Cell> = paths
+        .par_iter()
+        .map(|path| load_cell(path, options, layout.cell_size))
+        .collect();
+
+    let mut canvas = RgbaImage::from_pixel(layout.page_width, layout.page_height, BACKGROUND);
+    let font = EMBEDDED_FONT.as_ref();
+    if font.is_none() && layout.caption_height > 0 {
+        tracing::warn!("内嵌字体不可用，索引图不绘制标题");
+    }
+    let scale = PxScale::from(CAPTION_SIZE);
+
+    for (index, cell) in cells.iter().enumerate() {
+        let (_, x, y) = layout.cell_origin(index);
+        match &cell.thumbnail {
+            Some(thumbnail) => {
+                // 不透明底色上合成，透明图片与其余单元格保持同一背景
+                let offset_x = x + (layout.cell_size - thumbnail.width()) / 2;
+                let offset_y = y + (layout.cell_size - thumbnail.height()) / 2;
+                imageops::overlay(&mut canvas, thumbnail, offset_x as i64, offset_y as i64);
+            }
+            None => {
+                let placeholder =
+                    RgbaImage::from_pixel(layout.cell_size, layout.cell_size, PLACEHOLDER);
+                imageops::overlay(&mut canvas, &placeholder, x as i64, y as i64);
+            }
+        }
+
+        let Some(font) = font else {
+            continue;
+        };
+        for (line, caption) in cell.captions.iter().enumerate() {
+            let caption = fit_caption(font, scale, caption, layout.cell_size);
+            let width = text_size(scale, font, &caption).0;
+            let text_x = x + (layout.cell_size - width.min(layout.cell_size)) / 2;
+            let text_y = y + layout.cell_size + 2 + line as u32 * LINE_HEIGHT;
+            draw_text_mut(
+                &mut canvas,
+                CAPTION_COLOR,
+                text_x as i32,
+                text_y as i32,
+                scale,
+                font,
+                &caption,
+            );
+        }
+    }
+    canvas
+}
+
+fn encode_jpeg(page: &RgbaImage) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    let rgb = DynamicImage::ImageRgba8(page.clone()).into_rgb8();
+    rgb.write_with_encoder(JpegEncoder::new_with_quality(&mut bytes, 90))
+        .map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
+
+/// 多页的图片格式输出为 `名称_1.png`、`名称_2.png`…，返回写入的第一个文件
+fn write_sheet(
+    pages: impl Iterator<Item = RgbaImage>,
+    count: u32,
+    format: SheetFormat,
+    target: &Path,
+) -> Result<PathBuf, String> {
+    if format == SheetFormat::Pdf {
+        // PDF 中只保存压缩后的 JPEG，页面像素生成后即释放
+        let pdf_pages = pages
+            .map(|page| {
+                Ok(PdfPage {
+                    width_px: page.width(),
+                    height_px: page.height(),
+                    jpeg: encode_jpeg(&page)?,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        std::fs::write(target, images_to_pdf(&pdf_pages, PAGE_DPI)).map_err(|e| e.to_string())?;
+        return Ok(target.to_path_buf());
+    }
+
+    let mut written = Vec::new();
+    for (index, page) in pages.enumerate() {
+        let path = if count == 1 {
+            target.to_path_buf()
+        } else {
+            let stem = target.file_stem().unwrap_or_default().to_string_lossy();
+            target.with_file_name(format!("{}_{}.{}", stem, index + 1, format.extension()))
+        };
+        match format {
+            SheetFormat::Jpeg => std::fs::write(&path, encode_jpeg(&page)?),
+            _ => page.save(&path).map_err(std::io::Error::other),
+        }
+        .map_err(|e| e.to_string())?;
+        written.push(path);
+    }
+    written
+        .into_iter()
+        .next()
+        .ok_or_else(|| "no pages".to_string())
+}
+
+/// 选择保存位置后在后台生成索引图
+pub fn export_contact_sheet(
+    ctx: &Context,
+    paths: Vec<PathBuf>,
+    options: ContactSheetOptions,
+    toast_manager: &ToastManager,
+) {
+    if paths.is_empty() {
+        return;
+    }
+    let text = get_i18n_text(ctx);
+    let processing = text.batch.processing;
+    let saved = text.toast.saved;
+    let save_failed = text.toast.save_failed;
+    let toast = toast_manager.clone();
+
+    std::thread::spawn(move || {
+        let extension = options.format.extension();
+        let Some(target) = FileDialog::new()
+            .add_filter(options.format.label(), &[extension])
+            .set_file_name(format!("contact_sheet.{extension}"))
+            .save_file()
+        else {
+            return;
+        };
+        toast.loading(processing);
+        let layout = compute_layout(&options, paths.len());
+        let pages = paths
+            .chunks(layout.per_page())
+            .map(|chunk| render_page(chunk, &options, &layout));
+        match write_sheet(pages, layout.pages, options.format, &target) {
+            Ok(path) => toast.success(saved.replace("{path}", &path.display().to_string())),
+            Err(e) => {
+                tracing::error!("导出索引图失败 {}: {}", target.display(), e);
+                toast.error(format!("{}: {}", save_failed, e));
+            }
+        }
+    });
+}
+
+/// 导出选项窗口，点击导出时返回 `ModalAction::Apply`
+pub fn render_contact_sheet_window(
+    ctx: &Context,
+    open: &mut bool,
+    options: &mut ContactSheetOptions,
+    count: usize,
+) -> ModalAction {
+    let text = get_i18n_text(ctx);
+    let mut result = ModalAction::None;
+    ModalFrame::show(ctx, open, text.contact_sheet.title, |ui| {
+        let mut action = ModalAction::None;
+        ui.label(
+            text.contact_sheet
+                .count
+                .replace("{count}", &count.to_string()),
+        );
+        ui.add_space(8.0);
+        egui::Grid::new("contact_sheet_options")
+            .num_columns(2)
+            .spacing([16.0, 8.0])
+            .show(ui, |ui| {
+                ui.label(text.contact_sheet.format);
+                ComboBox::from_id_salt("contact_sheet_format")
+                    .selected_text(options.format.label())
+                    .show_ui(ui, |ui| {
+                        for format in SheetFormat::ALL {
+                            ui.selectable_value(&mut options.format, format, format.label());
+                        }
+                    });
+                ui.end_row();
+
+                ui.label(text.contact_sheet.page_size);
+                ui.horizontal(|ui| {
+                    ComboBox::from_id_salt("contact_sheet_page")
+                        .selected_text(options.page.label(text))
+                        .show_ui(ui, |ui| {
+                            for page in PageSize::ALL {
+                                ui.selectable_value(&mut options.page, page, page.label(text));
+                            }
+                        });
+                    if options.page != PageSize::Auto {
+                        ui.checkbox(&mut options.landscape, text.contact_sheet.landscape);
+                    }
+                });
+                ui.end_row();
+
+                ui.label(text.contact_sheet.columns);
+                ui.add(Slider::new(&mut options.columns, COLUMNS_RANGE));
+                ui.end_row();
+
+                if options.page == PageSize::Auto {
+                    ui.label(text.contact_sheet.cell_size);
+                    ui.add(Slider::new(&mut options.cell_size, CELL_SIZE_RANGE).suffix(" px"));
+                    ui.end_row();
+                }
+
+                ui.label(text.contact_sheet.margin);
+                ui.add(Slider::new(&mut options.margin, MARGIN_RANGE).suffix(" px"));
+                ui.end_row();
+
+                ui.label(text.contact_sheet.captions);
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut options.caption_name, text.contact_sheet.caption_name);
+                    ui.checkbox(&mut options.caption_date, text.contact_sheet.caption_date);
+                    ui.checkbox(
+                        &mut options.caption_dimensions,
+                        text.contact_sheet.caption_dimensions,
+                    );
+                });
+                ui.end_row();
+            });
+
+        let layout = compute_layout(options, count);
+        ui.add_space(8.0);
+        ui.label(
+            egui::RichText::new(format!(
+                "{} x {} px × {}",
+                layout.page_width, layout.page_height, layout.pages
+            ))
+            .weak(),
+        );
+        ui.add_space(12.0);
+        ui.horizontal(|ui| {
+            if ui.button(text.contact_sheet.export).clicked() {
+                action = ModalAction::Apply;
+            }
+            if ui.button(text.contact_sheet.cancel).clicked() {
+                action = ModalAction::Close;
+            }
+        });
+        result = action;
+        action
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContactSheetOptions, GAP, LINE_HEIGHT, MAX_AUTO_HEIGHT, PageSize, compute_layout};
+
+    #[test]
+    fn layout_auto_grows_with_rows_and_paged_splits() {
+        let options = ContactSheetOptions {
+            columns: 4,
+            cell_size: 100,
+            margin: 10,
+            caption_name: true,
+            caption_date: false,
+            caption_dimensions: false,
+            ..Default::default()
+        };
+        let auto = compute_layout(&options, 9);
+        assert_eq!(auto.pages, 1);
+        assert_eq!(auto.rows_per_page, 3);
+        assert_eq!(auto.page_width, 20 + 4 * 100 + 3 * GAP);
+        assert_eq!(auto.page_height, 20 + 3 * (100 + LINE_HEIGHT) + 2 * GAP);
+        assert_eq!(
+            auto.cell_origin(5),
+            (0, 10 + 100 + GAP, 10 + 100 + LINE_HEIGHT + GAP)
+        );
+
+        let paged = compute_layout(
+            &ContactSheetOptions {
+                page: PageSize::A4,
+                ..options.clone()
+            },
+            100,
+        );
+        assert_eq!((paged.page_width, paged.page_height), (1240, 1754));
+        assert_eq!(paged.cell_size, (1240 - 20 - 3 * GAP) / 4);
+        let per_page = (paged.columns * paged.rows_per_page) as usize;
+        assert_eq!(paged.pages as usize, 100usize.div_ceil(per_page));
+        assert_eq!(paged.cell_origin(per_page), (1, 10, 10));
+
+        let landscape = compute_layout(
+            &ContactSheetOptions {
+                page: PageSize::A4,
+                landscape: true,
+                ..options
+            },
+            1,
+        );
+        assert_eq!((landscape.page_width, landscape.page_height), (1754, 1240));
+
+        // 自动尺寸的页高有上限，图片多时分页
+        let many = compute_layout(&options, 4000);
+        assert!(many.page_height <= MAX_AUTO_HEIGHT);
+        assert_eq!(
+            many.rows_per_page,
+            (MAX_AUTO_HEIGHT - 20 + GAP) / (100 + LINE_HEIGHT + GAP)
+        );
+        assert_eq!(many.pages, 1000u32.div_ceil(many.rows_per_page));
+        assert_eq!(many.cell_origin(many.per_page()), (1, 10, 10));
+    }
+}
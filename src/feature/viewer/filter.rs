@@ -0,0 +1,403 @@
+use crate::utils::time::format_system_time;
+use egui::Context;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, channel};
+
+/// 网格筛选条件，数值为 0 或字符串为空表示该项不限制
+#[derive(Clone, Default, PartialEq)]
+pub struct FilterCriteria {
+    /// 文件名子串，包含 `*` / `?` 时按通配符整体匹配
+    pub name: String,
+    /// 扩展名列表，逗号或空格分隔，如 "png, jpg"
+    pub extensions: String,
+    pub min_width: u32,
+    pub max_width: u32,
+    pub min_height: u32,
+    pub max_height: u32,
+    pub min_size_kb: u64,
+    pub max_size_kb: u64,
+    /// 修改日期范围，格式 YYYY-MM-DD，允许只写前缀（如 "2024" 或 "2024-05"）
+    pub date_from: String,
+    pub date_to: String,
+}
+
+impl FilterCriteria {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn needs_file_stat(&self) -> bool {
+        self.min_size_kb > 0
+            || self.max_size_kb > 0
+            || !self.date_from.trim().is_empty()
+            || !self.date_to.trim().is_empty()
+    }
+
+    fn needs_dimensions(&self) -> bool {
+        self.min_width > 0 || self.max_width > 0 || self.min_height > 0 || self.max_height > 0
+    }
+
+    fn extension_list(&self) -> Vec<String> {
+        self.extensions
+            .split([',', ' ', ';'])
+            .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+            .filter(|ext| !ext.is_empty())
+            .collect()
+    }
+}
+
+/// 筛选用到的文件信息缓存，避免每次调整条件都重新读盘
+#[derive(Clone, Default)]
+struct FileStat {
+    size: u64,
+    /// YYYY-MM-DD
+    date: String,
+    dims: Option<(u32, u32)>,
+    dims_probed: bool,
+}
+
+/// 后台读取状态的变化，由界面取走后更新提示
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProbeEvent {
+    Started,
+    /// 被新的条件、换文件夹或线程异常打断，没有产生结果
+    Cancelled,
+}
+
+#[derive(Default)]
+pub struct FilterState {
+    /// 正在编辑的条件
+    pub criteria: FilterCriteria,
+    /// 当前已生效的条件
+    applied: FilterCriteria,
+    pub show_advanced: bool,
+    stats: HashMap<PathBuf, FileStat>,
+    /// 后台读取文件信息的结果。发起新的读取或换文件夹时替换掉，旧线程的结果随之丢弃
+    probe: Option<Receiver<Vec<(PathBuf, FileStat)>>>,
+    /// 尚未被界面取走的读取状态变化
+    probe_event: Option<ProbeEvent>,
+}
+
+impl FilterState {
+    pub fn is_active(&self) -> bool {
+        !self.applied.is_empty()
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.criteria != self.applied
+    }
+
+    /// 切换文件夹时清空条件和缓存
+    pub fn reset(&mut self) {
+        self.criteria = FilterCriteria::default();
+        self.applied = FilterCriteria::default();
+        self.stats.clear();
+        self.cancel_probe();
+    }
+
+    pub fn clear(&mut self) {
+        self.criteria = FilterCriteria::default();
+        self.applied = FilterCriteria::default();
+    }
+
+    pub fn is_probing(&self) -> bool {
+        self.probe.is_some()
+    }
+
+    /// 取走最近一次读取状态变化，发起时显示加载提示，中止时替换掉它
+    pub fn take_probe_event(&mut self) -> Option<ProbeEvent> {
+        self.probe_event.take()
+    }
+
+    /// 丢弃进行中的读取；界面还没显示加载提示时无需再提示中止
+    fn cancel_probe(&mut self) {
+        if self.probe.take().is_some() {
+            self.probe_event = match self.probe_event {
+                Some(ProbeEvent::Started) => None,
+                _ => Some(ProbeEvent::Cancelled),
+            };
+        }
+    }
+
+    /// 收取后台读取的文件信息，收到后返回 true，调用方应重新筛选
+    pub fn poll(&mut self) -> bool {
+        let Some(rx) = &self.probe else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(probed) => {
+                self.stats.extend(probed);
+                self.probe = None;
+                true
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => false,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.cancel_probe();
+                false
+            }
+        }
+    }
+
+    /// 以当前编辑的条件对完整列表进行筛选，返回保持原顺序的子集
+    pub fn apply(&mut self, all: &[PathBuf], ctx: &Context) -> Option<Vec<PathBuf>> {
+        self.applied = self.criteria.clone();
+        self.reapply(all, ctx)
+    }
+
+    /// 以已生效的条件重新筛选，用于文件夹内容变化后。
+    /// 缺少文件信息时在后台读取并返回 None，读完后由 `poll` 通知调用方再次筛选
+    pub fn reapply(&mut self, all: &[PathBuf], ctx: &Context) -> Option<Vec<PathBuf>> {
+        self.cancel_probe();
+        if self.applied.is_empty() {
+            return Some(all.to_vec());
+        }
+
+        let criteria = &self.applied;
+        let extensions = criteria.extension_list();
+        let name_pattern = criteria.name.trim().to_lowercase();
+
+        // 先用廉价的文件名条件过滤，再对剩余文件读取元数据
+        let candidates: Vec<&PathBuf> = all
+            .iter()
+            .filter(|path| matches_extension(path, &extensions))
+            .filter(|path| {
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_lowercase())
+                    .unwrap_or_default();
+                matches_name(&name_pattern, &name)
+            })
+            .collect();
+
+        let need_stat = criteria.needs_file_stat();
+        let need_dims = criteria.needs_dimensions();
+        if need_stat || need_dims {
+            let missing = missing_stats(&self.stats, &candidates, need_dims);
+            if !missing.is_empty() {
+                self.probe = Some(probe_stats(ctx, missing, need_dims));
+                self.probe_event = Some(ProbeEvent::Started);
+                return None;
+            }
+        }
+
+        let list = candidates
+            .into_iter()
+            .filter(|path| {
+                let Some(stat) = self.stats.get(*path) else {
+                    return !need_stat && !need_dims;
+                };
+                matches_stat(criteria, stat)
+            })
+            .cloned()
+            .collect();
+        Some(list)
+    }
+}
+
+/// 缓存中还没有（或缺少宽高）的文件
+fn missing_stats(
+    stats: &HashMap<PathBuf, FileStat>,
+    paths: &[&PathBuf],
+    need_dims: bool,
+) -> Vec<PathBuf> {
+    paths
+        .iter()
+        .filter(|path| {
+            stats
+                .get(**path)
+                .is_none_or(|stat| need_dims && !stat.dims_probed)
+        })
+        .map(|path| (*path).clone())
+        .collect()
+}
+
+/// 在后台线程并行读取文件信息，大文件夹首次筛选时不阻塞界面
+fn probe_stats(
+    ctx: &Context,
+    missing: Vec<PathBuf>,
+    need_dims: bool,
+) -> Receiver<Vec<(PathBuf, FileStat)>> {
+    let (tx, rx) = channel();
+    let ctx = ctx.clone();
+    std::thread::spawn(move || {
+        let probed: Vec<(PathBuf, FileStat)> = missing
+            .into_par_iter()
+            .map(|path| {
+                let stat = read_file_stat(&path, need_dims);
+                (path, stat)
+            })
+            .collect();
+        if tx.send(probed).is_ok() {
+            ctx.request_repaint();
+        }
+    });
+    rx
+}
+
+fn read_file_stat(path: &Path, need_dims: bool) -> FileStat {
+    let mut stat = FileStat::default();
+    if let Ok(metadata) = std::fs::metadata(path) {
+        stat.size = metadata.len();
+        stat.date = metadata
+            .modified()
+            .ok()
+            .and_then(format_system_time)
+            .map(|date| date.chars().take(10).collect())
+            .unwrap_or_default();
+    }
+    if need_dims {
+        // 只解析文件头，不做完整解码
+        stat.dims = image::image_dimensions(path).ok();
+        stat.dims_probed = true;
+    }
+    stat
+}
+
+fn matches_extension(path: &Path, extensions: &[String]) -> bool {
+    if extensions.is_empty() {
+        return true;
+    }
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.contains(&ext.to_lowercase()))
+}
+
+fn matches_stat(criteria: &FilterCriteria, stat: &FileStat) -> bool {
+    let size_kb = stat.size / 1024;
+    if !in_range(size_kb, criteria.min_size_kb, criteria.max_size_kb) {
+        return false;
+    }
+    if !date_in_range(&stat.date, &criteria.date_from, &criteria.date_to) {
+        return false;
+    }
+    if criteria.needs_dimensions() {
+        let Some((w, h)) = stat.dims else {
+            return false;
+        };
+        return in_range(w, criteria.min_width, criteria.max_width)
+            && in_range(h, criteria.min_height, criteria.max_height);
+    }
+    true
+}
+
+/// 闭区间判断，0 表示该端不限制
+fn in_range<T: PartialOrd + Default>(value: T, min: T, max: T) -> bool {
+    let unbounded = T::default();
+    (min == unbounded || value >= min) && (max == unbounded || value <= max)
+}
+
+/// 日期按前缀比较，`to` 写 "2024" 时包含整个 2024 年
+fn date_in_range(date: &str, from: &str, to: &str) -> bool {
+    let from = from.trim();
+    let to = to.trim();
+    if from.is_empty() && to.is_empty() {
+        return true;
+    }
+    if date.is_empty() {
+        return false;
+    }
+    let prefix = |bound: &str| &date[..bound.len().min(date.len())];
+    (from.is_empty() || prefix(from) >= from) && (to.is_empty() || prefix(to) <= to)
+}
+
+/// 文件名匹配：包含通配符时整体匹配，否则按子串匹配。调用方负责统一大小写
+pub fn matches_name(pattern: &str, name: &str) -> bool {
+    if pattern.is_empty() {
+        return true;
+    }
+    if pattern.contains(['*', '?']) {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let name: Vec<char> = name.chars().collect();
+        wildcard_match(&pattern, &name)
+    } else {
+        name.contains(pattern)
+    }
+}
+
+fn wildcard_match(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // 最近一次 `*` 的位置及其匹配到的文本位置，用于回溯
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FilterState, ProbeEvent, date_in_range, in_range, matches_name};
+    use egui::Context;
+    use std::path::PathBuf;
+
+    #[test]
+    fn name_matching_supports_substring_and_wildcards() {
+        assert!(matches_name("", "photo.jpg"));
+        assert!(matches_name("oto", "photo.jpg"));
+        assert!(matches_name("*.jpg", "photo.jpg"));
+        assert!(matches_name("img_??.png", "img_01.png"));
+        assert!(matches_name("a*b*c", "axxbyyc"));
+        assert!(!matches_name("*.png", "photo.jpg"));
+        assert!(!matches_name("img_?.png", "img_01.png"));
+    }
+
+    #[test]
+    fn ranges_treat_zero_as_unbounded() {
+        assert!(in_range(500u32, 0, 0));
+        assert!(in_range(500u32, 100, 0));
+        assert!(!in_range(50u32, 100, 0));
+        assert!(!in_range(5000u32, 0, 1000));
+    }
+
+    #[test]
+    fn date_range_compares_by_prefix() {
+        assert!(date_in_range("2024-05-10", "2024", "2024"));
+        assert!(date_in_range("2024-05-10", "2024-05-01", ""));
+        assert!(!date_in_range("2024-05-10", "2024-06", ""));
+        assert!(!date_in_range("2024-05-10", "", "2023-12-31"));
+        assert!(!date_in_range("", "2024", ""));
+    }
+
+    #[test]
+    fn clearing_filter_mid_probe_ends_loading_state() {
+        let ctx = Context::default();
+        let all = vec![
+            PathBuf::from("missing_a.png"),
+            PathBuf::from("missing_b.png"),
+        ];
+        let mut filter = FilterState::default();
+
+        filter.criteria.min_size_kb = 1;
+        assert_eq!(filter.apply(&all, &ctx), None);
+        assert!(filter.is_probing());
+        assert_eq!(filter.take_probe_event(), Some(ProbeEvent::Started));
+
+        filter.clear();
+        assert_eq!(filter.apply(&all, &ctx), Some(all.clone()));
+        assert!(!filter.is_probing());
+        assert_eq!(filter.take_probe_event(), Some(ProbeEvent::Cancelled));
+        assert!(!filter.poll());
+
+        // 加载提示还没显示就中止时不再提示
+        filter.criteria.min_size_kb = 1;
+        assert_eq!(filter.apply(&all, &ctx), None);
+        filter.reset();
+        assert_eq!(filter.take_probe_event(), None);
+    }
+}
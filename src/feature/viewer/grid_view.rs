@@ -1,55 +1,126 @@
-use crate::feature::viewer::viewer_state::{ViewMode, ViewerState};
-use crate::i18n::lang::get_i18n_text;
+use crate::core::byte_cache::ByteLruCache;
+use crate::core::load_queue::LoadPriority;
+use crate::feature::viewer::background::paint_image_background;
+use crate::feature::viewer::justified::{JustifiedRow, clamp_aspect, justify_rows};
+use crate::feature::viewer::selection::Selection;
+use crate::feature::viewer::viewer_state::{THUMB_SIZE, ThumbInfo, ViewerState};
+use crate::i18n::lang::{TextBundle, get_i18n_text};
+use crate::model::config::{GRID_ROW_HEIGHT_RANGE, GridConfig, ViewerConfig, get_context_config};
+use crate::model::mode::PopupMode;
+use crate::model::session::{SortOrder, ViewMode};
 use eframe::egui;
 use egui::{
-    Align2, Color32, Context, FontId, Frame, Pos2, Rect, ScrollArea, Sense, Stroke, Ui, Vec2,
+    Align, Align2, Color32, ComboBox, Context, CornerRadius, DragValue, FontId, Key, Layout, Pos2,
+    Rect, Response, RichText, ScrollArea, Sense, Slider, Stroke, StrokeKind, TextEdit, TextFormat,
+    Ui, Vec2,
+    scroll_area::ScrollSource,
+    text::{LayoutJob, TextWrapping},
 };
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-const GRID_ITEM_SIZE: Vec2 = Vec2::new(150.0, 150.0);
 const GRID_FRAME_MARGIN: f32 = 4.0;
 const GRID_BORDER_WIDTH: f32 = 2.0;
 const GRID_SPACING: f32 = 16.0;
 const GRID_PRELOAD_MARGIN: f32 = 500.0;
-const GRID_THUMB_SIZE: (u32, u32) = (200, 200);
+const GRID_CAPTION_HEIGHT: f32 = 36.0;
+const GRID_ACCENT: Color32 = Color32::from_rgb(0, 120, 215);
 
-struct GridLayout {
-    columns: usize,
-    left_padding: f32,
+#[derive(Default)]
+struct ToolbarResponse {
+    apply_filter: bool,
+    grid_changed: bool,
+    sort: Option<SortOrder>,
 }
 
 #[derive(Default)]
 struct GridInteraction {
     clicked_index: Option<usize>,
     double_clicked_index: Option<usize>,
+    /// 右键点击的项及菜单位置
+    context_index: Option<(usize, Pos2)>,
+    /// 每一项在屏幕上的位置，用于框选命中
+    item_rects: Vec<(usize, Rect)>,
+    /// 空白区域的响应，负责框选和点击取消选择
+    background: Option<Response>,
 }
 
 struct GridRenderContext<'a> {
     ctx: &'a Context,
     loading_text: &'a str,
     current_index: usize,
+    selection: &'a Selection,
+    show_captions: bool,
+    viewer_config: &'a ViewerConfig,
     preload_rect: Rect,
-    thumb_cache: &'a mut lru::LruCache<std::path::PathBuf, egui::TextureHandle>,
-    failed_thumbs: &'a std::collections::HashSet<std::path::PathBuf>,
-    loading_thumbs: &'a mut std::collections::HashSet<std::path::PathBuf>,
+    thumb_cache: &'a mut ByteLruCache<PathBuf, egui::TextureHandle>,
+    thumb_info: &'a HashMap<PathBuf, ThumbInfo>,
+    failed_thumbs: &'a std::collections::HashSet<PathBuf>,
+    loading_thumbs: &'a mut std::collections::HashSet<PathBuf>,
     loader: &'a mut crate::core::image_loader::ImageLoader,
+    /// 本帧预加载范围内的缩略图及其优先级，范围外排队中的任务会被取消
+    wanted: &'a mut HashMap<PathBuf, LoadPriority>,
     interaction: &'a mut GridInteraction,
 }
 
-pub fn draw_grid_view(ctx: &Context, ui: &mut Ui, viewer: &mut ViewerState) {
+pub fn draw_grid_view(ctx: &Context, ui: &mut Ui, viewer: &mut ViewerState, popup: &mut PopupMode) {
     let text = get_i18n_text(ctx);
 
-    if viewer.list.is_empty() {
+    if viewer.all_images.is_empty() {
         ui.centered_and_justified(|ui| {
             ui.label(text.viewer.no_images);
         });
         return;
     }
 
-    let layout = calculate_grid_layout(ui.available_width());
+    let config = get_context_config(ctx);
+    let mut grid = viewer
+        .pending_grid_config
+        .clone()
+        .unwrap_or_else(|| config.grid.clone());
+    if let Some(row_height) = viewer.folder_row_height {
+        grid.row_height = row_height;
+    }
+
+    let toolbar = draw_toolbar(ui, viewer, &mut grid, text);
+    if let Some(order) = toolbar.sort {
+        viewer.set_sort(ctx.clone(), order);
+    }
+    if toolbar.apply_filter {
+        viewer.apply_filter(ctx.clone());
+    }
+
+    // ctrl + 滚轮或触控板捏合调整缩略图大小
+    let zoom = ui.input(|i| i.zoom_delta());
+    let grid_changed = if zoom != 1.0 && ui.ui_contains_pointer() {
+        grid.row_height = (grid.row_height * zoom)
+            .clamp(*GRID_ROW_HEIGHT_RANGE.start(), *GRID_ROW_HEIGHT_RANGE.end());
+        true
+    } else {
+        toolbar.grid_changed
+    };
+    if grid_changed {
+        // 行高同时记到当前文件夹，全局设置作为新文件夹的默认值
+        if viewer.folder.is_some() {
+            viewer.folder_row_height = Some(grid.row_height);
+        }
+        viewer.pending_grid_config = Some(grid.clone());
+    }
+
+    if viewer.list.is_empty() {
+        ui.centered_and_justified(|ui| {
+            ui.label(text.filter.no_match);
+        });
+        return;
+    }
+
     let mut interaction = GridInteraction::default();
+    let mut wanted = HashMap::new();
 
     let list = &viewer.list;
     let current_index = viewer.index;
+    let selection = &viewer.selection;
+    let thumb_info = &viewer.thumbs.info;
     let thumb_cache = &mut viewer.thumbs.cache;
     let loading_thumbs = &mut viewer.thumbs.loading;
     let failed_thumbs = &viewer.thumbs.failed;
@@ -61,127 +132,455 @@ pub fn draw_grid_view(ctx: &Context, ui: &mut Ui, viewer: &mut ViewerState) {
         ctx,
         loading_text: text.grid.loading,
         current_index,
+        selection,
+        show_captions: grid.show_captions,
+        viewer_config: &config.viewer,
         preload_rect,
         thumb_cache,
+        thumb_info,
         failed_thumbs,
         loading_thumbs,
         loader,
+        wanted: &mut wanted,
         interaction: &mut interaction,
     };
 
+    // 拖拽用于框选，不再拖动滚动
     ScrollArea::vertical()
         .auto_shrink([false; 2])
+        .scroll_source(ScrollSource {
+            drag: false,
+            ..ScrollSource::ALL
+        })
         .show(ui, |ui| {
-            render_grid_rows(ui, list, &layout, &mut render_context);
+            // 先分配背景，缩略图后分配会优先接收点击
+            let background = ui.interact(
+                ui.clip_rect(),
+                ui.id().with("grid_background"),
+                Sense::click_and_drag(),
+            );
+            render_grid_rows(ui, list, grid.row_height, &mut render_context);
+            render_context.interaction.background = Some(background);
         });
 
-    apply_grid_interaction(ctx, viewer, interaction);
+    for path in viewer
+        .loader
+        .reprioritize_thumbnails(|path| wanted.get(path).copied())
+    {
+        viewer.thumbs.loading.remove(&path);
+    }
+
+    apply_grid_interaction(ctx, ui, viewer, popup, interaction);
 }
 
-fn calculate_grid_layout(available_width: f32) -> GridLayout {
-    let cell_total_width = GRID_ITEM_SIZE.x + (GRID_FRAME_MARGIN * 2.0) + (GRID_BORDER_WIDTH * 2.0);
-    let mut columns =
-        ((available_width + GRID_SPACING) / (cell_total_width + GRID_SPACING)).floor() as usize;
-    columns = columns.max(1);
+/// 绘制筛选栏和显示选项
+fn draw_toolbar(
+    ui: &mut Ui,
+    viewer: &mut ViewerState,
+    grid: &mut GridConfig,
+    text: &TextBundle,
+) -> ToolbarResponse {
+    let mut grid_changed = false;
+    let mut sort = viewer.sort;
+    let mut apply = false;
+    let mut clear = false;
+    let matched = viewer.list.len();
+    let total = viewer.all_images.len();
+    let selected = viewer.selection.len();
+    let is_active = viewer.filter.is_active();
+    let filter = &mut viewer.filter;
+
+    ui.add_space(8.0);
+    ui.horizontal(|ui| {
+        ui.add_space(GRID_SPACING);
+        let name = ui.add(
+            TextEdit::singleline(&mut filter.criteria.name)
+                .hint_text(text.filter.name_hint)
+                .desired_width(220.0),
+        );
+        let extensions = ui.add(
+            TextEdit::singleline(&mut filter.criteria.extensions)
+                .hint_text(text.filter.extension_hint)
+                .desired_width(140.0),
+        );
+        // 文件名与扩展名无需读盘，输入即生效
+        apply |= name.changed() || extensions.changed();
+
+        ui.toggle_value(&mut filter.show_advanced, text.filter.more);
+
+        if (is_active || !filter.criteria.is_empty()) && ui.button(text.filter.clear).clicked() {
+            clear = true;
+        }
 
-    let content_width =
-        (columns as f32 * cell_total_width) + ((columns as f32 - 1.0) * GRID_SPACING);
-    let left_padding = ((available_width - content_width) / 2.0).max(0.0);
+        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            ui.add_space(GRID_SPACING);
+            grid_changed |= ui
+                .toggle_value(&mut grid.show_captions, text.grid.captions)
+                .changed();
+            grid_changed |= ui
+                .add(Slider::new(&mut grid.row_height, GRID_ROW_HEIGHT_RANGE).show_value(false))
+                .on_hover_text(text.grid.thumb_size)
+                .changed();
+            ComboBox::from_id_salt("grid_sort")
+                .selected_text(sort.label(text))
+                .show_ui(ui, |ui| {
+                    for order in SortOrder::ALL {
+                        ui.selectable_value(&mut sort, order, order.label(text));
+                    }
+                })
+                .response
+                .on_hover_text(text.grid.sort);
+            ui.add_space(12.0);
+            ui.label(format!("{matched} / {total}"));
+            if selected > 0 {
+                ui.label(
+                    RichText::new(
+                        text.batch
+                            .selected
+                            .replace("{count}", &selected.to_string()),
+                    )
+                    .color(GRID_ACCENT),
+                );
+            }
+        });
+    });
 
-    GridLayout {
-        columns,
-        left_padding,
+    if filter.show_advanced {
+        ui.horizontal(|ui| {
+            let criteria = &mut filter.criteria;
+            ui.add_space(GRID_SPACING);
+            range_inputs(
+                ui,
+                text.filter.width,
+                &mut criteria.min_width,
+                &mut criteria.max_width,
+            );
+            ui.add_space(12.0);
+            range_inputs(
+                ui,
+                text.filter.height,
+                &mut criteria.min_height,
+                &mut criteria.max_height,
+            );
+            ui.add_space(12.0);
+            range_inputs(
+                ui,
+                text.filter.size_kb,
+                &mut criteria.min_size_kb,
+                &mut criteria.max_size_kb,
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.add_space(GRID_SPACING);
+            ui.label(format!("{}:", text.filter.date));
+            let criteria = &mut filter.criteria;
+            for date in [&mut criteria.date_from, &mut criteria.date_to] {
+                let response = ui.add(
+                    TextEdit::singleline(date)
+                        .hint_text(text.filter.date_hint)
+                        .desired_width(90.0),
+                );
+                apply |= response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            }
+            let dirty = filter.is_dirty();
+            if ui
+                .add_enabled(dirty, egui::Button::new(text.filter.apply))
+                .clicked()
+            {
+                apply = true;
+            }
+            if filter.is_probing() {
+                ui.spinner();
+            }
+        });
+    }
+
+    if clear {
+        filter.clear();
+        apply = true;
+    }
+
+    ToolbarResponse {
+        apply_filter: apply,
+        grid_changed,
+        sort: (sort != viewer.sort).then_some(sort),
     }
 }
 
+fn range_inputs<Num: egui::emath::Numeric>(ui: &mut Ui, label: &str, min: &mut Num, max: &mut Num) {
+    ui.label(format!("{label}:"));
+    ui.add(DragValue::new(min).speed(10));
+    ui.label("–");
+    ui.add(DragValue::new(max).speed(10));
+}
+
 fn render_grid_rows(
     ui: &mut Ui,
-    list: &[std::path::PathBuf],
-    layout: &GridLayout,
+    list: &[PathBuf],
+    row_height: f32,
     render: &mut GridRenderContext<'_>,
 ) {
-    ui.add_space(GRID_SPACING);
+    let aspects: Vec<f32> = list
+        .iter()
+        .map(|path| clamp_aspect(render.thumb_info.get(path).map_or(1.0, |info| info.aspect)))
+        .collect();
+    let width = ui.available_width() - GRID_SPACING * 2.0;
+    let rows = justify_rows(&aspects, width, row_height, GRID_SPACING);
 
-    for (row_idx, row_items) in list.chunks(layout.columns).enumerate() {
-        ui.horizontal(|ui| {
-            ui.add_space(layout.left_padding);
-            ui.spacing_mut().item_spacing.x = GRID_SPACING;
-
-            for (col_idx, path) in row_items.iter().enumerate() {
-                let global_index = row_idx * layout.columns + col_idx;
-                let is_selected = global_index == render.current_index;
-                let (stroke_color, bg_color) = if is_selected {
-                    (Color32::from_rgb(0, 120, 215), Color32::from_gray(45))
-                } else {
-                    (Color32::from_gray(60), Color32::from_gray(30))
-                };
-
-                let frame = Frame::default()
-                    .fill(bg_color)
-                    .stroke(Stroke::new(GRID_BORDER_WIDTH, stroke_color))
-                    .inner_margin(GRID_FRAME_MARGIN)
-                    .corner_radius(6.0);
-
-                frame.show(ui, |ui| {
-                    let (rect, response) = ui.allocate_exact_size(GRID_ITEM_SIZE, Sense::click());
-
-                    if render.preload_rect.intersects(rect)
-                        && !render.thumb_cache.contains(path)
-                        && !render.failed_thumbs.contains(path)
-                        && !render.loading_thumbs.contains(path)
-                    {
-                        render.loading_thumbs.insert(path.clone());
-                        render.loader.load_async(
-                            render.ctx.clone(),
-                            path.clone(),
-                            false,
-                            Some(GRID_THUMB_SIZE),
-                        );
-                    }
+    let caption_height = if render.show_captions {
+        GRID_CAPTION_HEIGHT
+    } else {
+        0.0
+    };
+    let total_height = rows
+        .iter()
+        .map(|row| row.height + caption_height + GRID_SPACING)
+        .sum::<f32>()
+        + GRID_SPACING;
+    let (content_rect, _) = ui.allocate_exact_size(
+        Vec2::new(ui.available_width(), total_height),
+        Sense::hover(),
+    );
 
-                    if ui.is_rect_visible(rect) {
-                        if let Some(texture) = render.thumb_cache.get(path) {
-                            let tex_size = texture.size_vec2();
-                            let scale = (rect.width() / tex_size.x).min(rect.height() / tex_size.y);
-                            let target_size = tex_size * scale;
-                            let target_rect = Rect::from_center_size(rect.center(), target_size);
-
-                            ui.painter().image(
-                                texture.id(),
-                                target_rect,
-                                Rect::from_min_max(Pos2::new(0.0, 0.0), Pos2::new(1.0, 1.0)),
-                                Color32::WHITE,
-                            );
-                        } else {
-                            ui.painter().text(
-                                rect.center(),
-                                Align2::CENTER_CENTER,
-                                render.loading_text,
-                                FontId::proportional(14.0),
-                                Color32::GRAY,
-                            );
-                        }
-                    }
+    // 只为可见行创建交互和绘制，大文件夹下也能保持流畅
+    let mut y = content_rect.min.y + GRID_SPACING;
+    for row in &rows {
+        let row_rect = Rect::from_min_size(
+            Pos2::new(content_rect.min.x, y),
+            Vec2::new(content_rect.width(), row.height + caption_height),
+        );
+        if render.preload_rect.intersects(row_rect) {
+            render_grid_row(ui, list, &aspects, row, row_rect, render);
+        }
+        y += row_rect.height() + GRID_SPACING;
+    }
+}
 
-                    if response.double_clicked() {
-                        render.interaction.double_clicked_index = Some(global_index);
-                    } else if response.clicked() {
-                        render.interaction.clicked_index = Some(global_index);
-                    }
-                });
-            }
-        });
+fn render_grid_row(
+    ui: &mut Ui,
+    list: &[PathBuf],
+    aspects: &[f32],
+    row: &JustifiedRow,
+    row_rect: Rect,
+    render: &mut GridRenderContext<'_>,
+) {
+    let mut x = row_rect.min.x + GRID_SPACING;
+    for index in row.range.clone() {
+        let path = &list[index];
+        let width = aspects[index] * row.height;
+        let item_rect = Rect::from_min_size(
+            Pos2::new(x, row_rect.min.y),
+            Vec2::new(width, row_rect.height()),
+        );
+        x += width + GRID_SPACING;
+        render.interaction.item_rects.push((index, item_rect));
 
-        ui.add_space(GRID_SPACING);
+        let visible = ui.is_rect_visible(item_rect);
+        let priority = if visible {
+            LoadPriority::Visible
+        } else {
+            LoadPriority::Prefetch
+        };
+        render.wanted.insert(path.clone(), priority);
+        if !render.thumb_cache.contains(path)
+            && !render.failed_thumbs.contains(path)
+            && render.loading_thumbs.insert(path.clone())
+        {
+            render
+                .loader
+                .load_thumbnail(render.ctx, path.clone(), THUMB_SIZE, priority);
+        }
+
+        if !visible {
+            continue;
+        }
+
+        let response = ui.interact(
+            item_rect,
+            ui.id().with(("grid_item", index)),
+            Sense::click(),
+        );
+        let thumb_rect = Rect::from_min_size(item_rect.min, Vec2::new(width, row.height));
+        paint_grid_item(ui, path, index, item_rect, thumb_rect, render);
+
+        if response.double_clicked() {
+            render.interaction.double_clicked_index = Some(index);
+        } else if response.clicked() {
+            render.interaction.clicked_index = Some(index);
+        } else if response.secondary_clicked()
+            && let Some(pos) = response.interact_pointer_pos()
+        {
+            render.interaction.context_index = Some((index, pos));
+        }
     }
 }
 
-fn apply_grid_interaction(ctx: &Context, viewer: &mut ViewerState, interaction: GridInteraction) {
+fn paint_grid_item(
+    ui: &Ui,
+    path: &PathBuf,
+    index: usize,
+    item_rect: Rect,
+    thumb_rect: Rect,
+    render: &mut GridRenderContext<'_>,
+) {
+    let is_selected = render.selection.contains(path);
+    let is_current = index == render.current_index;
+    let (stroke_color, bg_color) = if is_selected {
+        (GRID_ACCENT, Color32::from_rgb(30, 45, 62))
+    } else if is_current {
+        (Color32::from_gray(130), Color32::from_gray(45))
+    } else {
+        (Color32::from_gray(60), Color32::from_gray(30))
+    };
+
+    let painter = ui.painter();
+    let frame_rect = item_rect.expand(GRID_FRAME_MARGIN);
+    painter.rect_filled(frame_rect, 6.0, bg_color);
+    painter.rect_stroke(
+        frame_rect,
+        6.0,
+        Stroke::new(GRID_BORDER_WIDTH, stroke_color),
+        StrokeKind::Outside,
+    );
+
+    if let Some(texture) = render.thumb_cache.get(path) {
+        paint_image_background(painter, thumb_rect, 0, render.viewer_config, Color32::WHITE);
+        painter.image(
+            texture.id(),
+            thumb_rect,
+            Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
+            Color32::WHITE,
+        );
+    } else {
+        painter.text(
+            thumb_rect.center(),
+            Align2::CENTER_CENTER,
+            render.loading_text,
+            FontId::proportional(14.0),
+            Color32::GRAY,
+        );
+    }
+
+    if render.show_captions {
+        let caption_rect =
+            Rect::from_min_max(Pos2::new(item_rect.min.x, thumb_rect.max.y), item_rect.max);
+        paint_caption(ui, path, render.thumb_info.get(path), caption_rect);
+    }
+}
+
+/// 标题：第一行文件名，第二行尺寸和格式角标
+fn paint_caption(ui: &Ui, path: &Path, info: Option<&ThumbInfo>, rect: Rect) {
+    let painter = ui.painter_at(rect);
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut job = LayoutJob::single_section(
+        name,
+        TextFormat::simple(FontId::proportional(12.0), Color32::LIGHT_GRAY),
+    );
+    job.wrap = TextWrapping::truncate_at_width(rect.width());
+    let galley = painter.layout_job(job);
+    painter.galley(rect.min + Vec2::new(0.0, 3.0), galley, Color32::LIGHT_GRAY);
+
+    let second_line_y = rect.min.y + 26.0;
+    if let Some(info) = info.filter(|info| info.width > 0) {
+        painter.text(
+            Pos2::new(rect.min.x, second_line_y),
+            Align2::LEFT_CENTER,
+            format!("{} × {}", info.width, info.height),
+            FontId::proportional(11.0),
+            Color32::GRAY,
+        );
+    }
+
+    let format = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_uppercase())
+        .unwrap_or_default();
+    if !format.is_empty() {
+        let galley = painter.layout_no_wrap(format, FontId::monospace(10.0), Color32::WHITE);
+        let badge = Rect::from_min_size(
+            Pos2::new(
+                rect.max.x - galley.size().x - 8.0,
+                second_line_y - galley.size().y * 0.5 - 2.0,
+            ),
+            galley.size() + Vec2::new(8.0, 4.0),
+        );
+        painter.rect_filled(badge, CornerRadius::same(3), Color32::from_gray(70));
+        painter.galley(badge.min + Vec2::new(4.0, 2.0), galley, Color32::WHITE);
+    }
+}
+
+fn apply_grid_interaction(
+    ctx: &Context,
+    ui: &Ui,
+    viewer: &mut ViewerState,
+    popup: &mut PopupMode,
+    interaction: GridInteraction,
+) {
+    let modifiers = ctx.input(|i| i.modifiers);
+
     if let Some(index) = interaction.double_clicked_index {
+        viewer.selection.select_only(&viewer.list, index);
         viewer.set_index(index);
         viewer.view_mode = ViewMode::Single;
         viewer.load_current(ctx.clone());
     } else if let Some(index) = interaction.clicked_index {
+        if modifiers.shift {
+            viewer
+                .selection
+                .select_range(&viewer.list, index, modifiers.command);
+        } else if modifiers.command {
+            viewer.selection.toggle(&viewer.list, index);
+        } else {
+            viewer.selection.select_only(&viewer.list, index);
+        }
         viewer.set_index(index);
+    } else if let Some((index, pos)) = interaction.context_index {
+        // 右键未选中的项时改为只选中它，与文件管理器一致
+        let on_selection = viewer
+            .list
+            .get(index)
+            .is_some_and(|path| viewer.selection.contains(path));
+        if !on_selection {
+            viewer.selection.select_only(&viewer.list, index);
+            viewer.set_index(index);
+        }
+        *popup = PopupMode::ContextMenu(pos);
+    }
+
+    let Some(background) = interaction.background else {
+        return;
+    };
+
+    if background.clicked() && !modifiers.command && !modifiers.shift {
+        viewer.selection.clear();
+    }
+
+    if background.drag_started() {
+        viewer
+            .selection
+            .begin_band(modifiers.command || modifiers.shift);
+    }
+    if viewer.selection.is_banding() {
+        let origin = ctx.input(|i| i.pointer.press_origin());
+        let current = background.interact_pointer_pos();
+        if let (Some(origin), Some(current)) = (origin, current) {
+            let band = Rect::from_two_pos(origin, current);
+            let hits = interaction
+                .item_rects
+                .iter()
+                .filter(|(_, rect)| rect.intersects(band))
+                .map(|(index, _)| *index);
+            viewer.selection.update_band(&viewer.list, hits);
+
+            let painter = ui.painter_at(ui.clip_rect());
+            painter.rect_filled(band, 0.0, GRID_ACCENT.gamma_multiply(0.15));
+            painter.rect_stroke(band, 0.0, Stroke::new(1.0, GRID_ACCENT), StrokeKind::Inside);
+        }
+    }
+    if background.drag_stopped() {
+        viewer.selection.end_band();
     }
 }
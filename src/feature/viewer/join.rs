@@ -0,0 +1,389 @@
+use crate::core::image_loader::{ImageLoader, MAX_IMAGE_SIDE};
+use crate::core::resize;
+use crate::i18n::lang::{TextBundle, get_i18n_text};
+use crate::model::session::{JoinAlign, JoinDirection, JoinOptions, JoinScale};
+use crate::ui::widgets::modal::{ModalAction, ModalFrame};
+use crate::ui::widgets::toast::ToastManager;
+use crate::utils::image::unmultiplied_rgba;
+use egui::{ColorImage, ComboBox, Context, Slider};
+use image::{Rgba, RgbaImage, imageops};
+use rayon::prelude::*;
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, Sender, channel};
+
+pub const SPACING_RANGE: std::ops::RangeInclusive<u32> = 0..=200;
+pub const COLUMNS_RANGE: std::ops::RangeInclusive<u32> = 1..=12;
+
+impl JoinDirection {
+    pub const ALL: [Self; 3] = [Self::Horizontal, Self::Vertical, Self::Grid];
+
+    pub fn label(self, text: &TextBundle) -> &'static str {
+        match self {
+            Self::Horizontal => text.join.horizontal,
+            Self::Vertical => text.join.vertical,
+            Self::Grid => text.join.grid,
+        }
+    }
+}
+
+impl JoinAlign {
+    pub const ALL: [Self; 3] = [Self::Start, Self::Center, Self::End];
+
+    pub fn label(self, text: &TextBundle) -> &'static str {
+        match self {
+            Self::Start => text.join.align_start,
+            Self::Center => text.join.align_center,
+            Self::End => text.join.align_end,
+        }
+    }
+
+    fn offset(self, free: u32) -> u32 {
+        match self {
+            Self::Start => 0,
+            Self::Center => free / 2,
+            Self::End => free,
+        }
+    }
+}
+
+impl JoinScale {
+    pub const ALL: [Self; 3] = [Self::None, Self::Smallest, Self::Largest];
+
+    pub fn label(self, text: &TextBundle) -> &'static str {
+        match self {
+            Self::None => text.join.scale_none,
+            Self::Smallest => text.join.scale_smallest,
+            Self::Largest => text.join.scale_largest,
+        }
+    }
+}
+
+/// 单张图片在画布上的位置和缩放后的尺寸
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Placement {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 计算画布尺寸和每张图片的位置
+pub fn compute_placements(
+    sizes: &[(u32, u32)],
+    options: &JoinOptions,
+) -> ((u32, u32), Vec<Placement>) {
+    let vertical = options.direction == JoinDirection::Vertical;
+    // 纵向统一宽度，其余统一高度
+    let side = |&(w, h): &(u32, u32)| if vertical { w } else { h };
+    let target = match options.scale {
+        JoinScale::None => None,
+        JoinScale::Smallest => sizes.iter().map(side).filter(|s| *s > 0).min(),
+        JoinScale::Largest => sizes.iter().map(side).max(),
+    };
+    let scaled: Vec<(u32, u32)> = sizes
+        .iter()
+        .map(|&(w, h)| match target {
+            Some(t) if vertical && w > 0 => (t, (h as u64 * t as u64 / w as u64).max(1) as u32),
+            Some(t) if !vertical && h > 0 => ((w as u64 * t as u64 / h as u64).max(1) as u32, t),
+            _ => (w, h),
+        })
+        .collect();
+
+    let columns = match options.direction {
+        JoinDirection::Horizontal => scaled.len().max(1),
+        JoinDirection::Vertical => 1,
+        JoinDirection::Grid => options.columns.max(1) as usize,
+    };
+    let rows = scaled.len().div_ceil(columns).max(1);
+    let mut column_widths = vec![0u32; columns];
+    let mut row_heights = vec![0u32; rows];
+    for (index, &(w, h)) in scaled.iter().enumerate() {
+        let (row, column) = (index / columns, index % columns);
+        column_widths[column] = column_widths[column].max(w);
+        row_heights[row] = row_heights[row].max(h);
+    }
+
+    let starts = |lengths: &[u32]| -> Vec<u32> {
+        lengths
+            .iter()
+            .scan(0, |next, len| {
+                let start = *next;
+                *next += len + options.spacing;
+                Some(start)
+            })
+            .collect()
+    };
+    let column_starts = starts(&column_widths);
+    let row_starts = starts(&row_heights);
+    let total = |lengths: &[u32]| {
+        lengths.iter().sum::<u32>() + lengths.len().saturating_sub(1) as u32 * options.spacing
+    };
+    let canvas = (total(&column_widths), total(&row_heights));
+
+    let placements = scaled
+        .iter()
+        .enumerate()
+        .map(|(index, &(width, height))| {
+            let (row, column) = (index / columns, index % columns);
+            Placement {
+                x: column_starts[column] + options.align.offset(column_widths[column] - width),
+                y: row_starts[row] + options.align.offset(row_heights[row] - height),
+                width,
+                height,
+            }
+        })
+        .collect();
+    (canvas, placements)
+}
+
+/// 按选项把图片拼到一张画布上，画布过大时整体缩小到纹理上限以内
+pub fn join_images(images: &[RgbaImage], options: &JoinOptions) -> Option<RgbaImage> {
+    let sizes: Vec<(u32, u32)> = images.iter().map(|img| img.dimensions()).collect();
+    let ((width, height), placements) = compute_placements(&sizes, options);
+    if width == 0 || height == 0 {
+        return None;
+    }
+    // 拼接结果以纹理显示，也可能再次被打开，不能超过加载器接受的边长
+    let (fit_w, fit_h) = if width.max(height) > MAX_IMAGE_SIDE {
+        resize::fit_within(width, height, MAX_IMAGE_SIDE, MAX_IMAGE_SIDE)
+    } else {
+        (width, height)
+    };
+    let ratio = fit_w as f64 / width as f64;
+    let shrink = |v: u32| (v as f64 * ratio).round() as u32;
+
+    let mut canvas = RgbaImage::from_pixel(fit_w, fit_h, Rgba(options.background));
+    for (image, placement) in images.iter().zip(placements) {
+        let (w, h) = (
+            shrink(placement.width).max(1),
+            shrink(placement.height).max(1),
+        );
+        let resized;
+        let source = if (w, h) == image.dimensions() {
+            image
+        } else {
+            resized = imageops::resize(image, w, h, imageops::FilterType::Lanczos3);
+            &resized
+        };
+        imageops::overlay(
+            &mut canvas,
+            source,
+            shrink(placement.x) as i64,
+            shrink(placement.y) as i64,
+        );
+    }
+    Some(canvas)
+}
+
+/// 拼接在后台线程解码和合成，结果通过通道回传给 UI 线程
+pub struct JoinRunner {
+    tx: Sender<Result<ColorImage, String>>,
+    rx: Receiver<Result<ColorImage, String>>,
+}
+
+impl JoinRunner {
+    pub fn new() -> Self {
+        let (tx, rx) = channel();
+        Self { tx, rx }
+    }
+
+    pub fn start(
+        &self,
+        ctx: &Context,
+        paths: Vec<PathBuf>,
+        options: JoinOptions,
+        toast_manager: &ToastManager,
+    ) {
+        if paths.len() < 2 {
+            return;
+        }
+        toast_manager.loading(get_i18n_text(ctx).batch.processing);
+        let tx = self.tx.clone();
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            let decoded: Result<Vec<RgbaImage>, String> = paths
+                .par_iter()
+                .map(|path| {
+                    let (color_image, _) = ImageLoader::decode_image(path, None)
+                        .map_err(|e| format!("{}: {:?}", path.display(), e))?;
+                    unmultiplied_rgba(&color_image.pixels, color_image.size)
+                        .ok_or_else(|| "invalid image size".to_string())
+                })
+                .collect();
+            let result = decoded.and_then(|images| {
+                let joined =
+                    join_images(&images, &options).ok_or_else(|| "empty image".to_string())?;
+                Ok(ColorImage::from_rgba_unmultiplied(
+                    [joined.width() as usize, joined.height() as usize],
+                    joined.as_raw(),
+                ))
+            });
+            let _ = tx.send(result);
+            ctx.request_repaint();
+        });
+    }
+
+    pub fn try_recv(&self) -> Option<Result<ColorImage, String>> {
+        self.rx.try_recv().ok()
+    }
+}
+
+impl Default for JoinRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 拼接选项窗口，点击拼接时返回 `ModalAction::Apply`
+pub fn render_join_window(
+    ctx: &Context,
+    open: &mut bool,
+    options: &mut JoinOptions,
+    count: usize,
+) -> ModalAction {
+    let text = get_i18n_text(ctx);
+    let mut result = ModalAction::None;
+    ModalFrame::show(ctx, open, text.join.title, |ui| {
+        let mut action = ModalAction::None;
+        ui.label(text.join.count.replace("{count}", &count.to_string()));
+        ui.add_space(8.0);
+        egui::Grid::new("join_options")
+            .num_columns(2)
+            .spacing([16.0, 8.0])
+            .show(ui, |ui| {
+                ui.label(text.join.direction);
+                ui.horizontal(|ui| {
+                    for direction in JoinDirection::ALL {
+                        ui.selectable_value(
+                            &mut options.direction,
+                            direction,
+                            direction.label(text),
+                        );
+                    }
+                });
+                ui.end_row();
+
+                if options.direction == JoinDirection::Grid {
+                    ui.label(text.join.columns);
+                    ui.add(Slider::new(&mut options.columns, COLUMNS_RANGE));
+                    ui.end_row();
+                }
+
+                ui.label(text.join.spacing);
+                ui.add(Slider::new(&mut options.spacing, SPACING_RANGE).suffix(" px"));
+                ui.end_row();
+
+                ui.label(text.join.align);
+                ComboBox::from_id_salt("join_align")
+                    .selected_text(options.align.label(text))
+                    .show_ui(ui, |ui| {
+                        for align in JoinAlign::ALL {
+                            ui.selectable_value(&mut options.align, align, align.label(text));
+                        }
+                    });
+                ui.end_row();
+
+                ui.label(text.join.scale);
+                ComboBox::from_id_salt("join_scale")
+                    .selected_text(options.scale.label(text))
+                    .show_ui(ui, |ui| {
+                        for scale in JoinScale::ALL {
+                            ui.selectable_value(&mut options.scale, scale, scale.label(text));
+                        }
+                    });
+                ui.end_row();
+
+                ui.label(text.join.background);
+                ui.color_edit_button_srgba_unmultiplied(&mut options.background);
+                ui.end_row();
+            });
+
+        ui.add_space(12.0);
+        ui.horizontal(|ui| {
+            if ui.button(text.join.join).clicked() {
+                action = ModalAction::Apply;
+            }
+            if ui.button(text.join.cancel).clicked() {
+                action = ModalAction::Close;
+            }
+        });
+        result = action;
+        action
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JoinAlign, JoinDirection, JoinOptions, JoinScale, Placement, compute_placements};
+
+    #[test]
+    fn placements_scale_align_and_wrap() {
+        let sizes = [(200, 100), (50, 50), (100, 200)];
+        let horizontal = JoinOptions {
+            spacing: 10,
+            scale: JoinScale::Smallest,
+            ..Default::default()
+        };
+        let (canvas, placed) = compute_placements(&sizes, &horizontal);
+        assert_eq!(canvas, (100 + 10 + 50 + 10 + 25, 50));
+        assert_eq!(
+            placed[1],
+            Placement {
+                x: 110,
+                y: 0,
+                width: 50,
+                height: 50
+            }
+        );
+        assert_eq!(placed[2].x, 170);
+
+        let vertical = JoinOptions {
+            direction: JoinDirection::Vertical,
+            scale: JoinScale::None,
+            align: JoinAlign::End,
+            ..Default::default()
+        };
+        let (canvas, placed) = compute_placements(&sizes, &vertical);
+        assert_eq!(canvas, (200, 350));
+        assert_eq!(
+            placed[1],
+            Placement {
+                x: 150,
+                y: 100,
+                width: 50,
+                height: 50
+            }
+        );
+
+        let grid = JoinOptions {
+            direction: JoinDirection::Grid,
+            columns: 2,
+            spacing: 4,
+            scale: JoinScale::None,
+            align: JoinAlign::Center,
+            ..Default::default()
+        };
+        let (canvas, placed) = compute_placements(&sizes, &grid);
+        assert_eq!(canvas, (200 + 4 + 50, 100 + 4 + 200));
+        assert_eq!(
+            placed[1],
+            Placement {
+                x: 204,
+                y: 25,
+                width: 50,
+                height: 50
+            }
+        );
+        assert_eq!(
+            placed[2],
+            Placement {
+                x: 50,
+                y: 104,
+                width: 100,
+                height: 200
+            }
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use std::ops::Range;
+
+/// 极端比例的图片按此范围截断，避免一张全景图独占整行或竖条过细
+const MIN_ASPECT: f32 = 0.25;
+const MAX_ASPECT: f32 = 5.0;
+/// 行高最多放大到目标行高的倍数，超过时该行不再强行铺满
+const MAX_ROW_SCALE: f32 = 1.5;
+
+/// 两端对齐布局中的一行
+#[derive(Debug, Clone, PartialEq)]
+pub struct JustifiedRow {
+    pub range: Range<usize>,
+    pub height: f32,
+}
+
+pub fn clamp_aspect(aspect: f32) -> f32 {
+    if aspect.is_finite() && aspect > 0.0 {
+        aspect.clamp(MIN_ASPECT, MAX_ASPECT)
+    } else {
+        1.0
+    }
+}
+
+/// 按目标行高把图片分行，每行缩放到恰好铺满 `width`（最后一行保持目标行高）。
+/// 行尾放不下时，比较放入与不放入两种结果，选行高更接近目标的一种
+pub fn justify_rows(
+    aspects: &[f32],
+    width: f32,
+    target_height: f32,
+    spacing: f32,
+) -> Vec<JustifiedRow> {
+    let mut rows = Vec::new();
+    let width = width.max(1.0);
+    let max_height = target_height * MAX_ROW_SCALE;
+    let fit_height =
+        |sum: f32, count: usize| ((width - spacing * (count as f32 - 1.0)) / sum).min(max_height);
+
+    let mut start = 0;
+    let mut sum = 0.0;
+    let mut i = 0;
+    while i < aspects.len() {
+        let aspect = clamp_aspect(aspects[i]);
+        let count = i - start + 1;
+        let natural = (sum + aspect) * target_height + spacing * (count as f32 - 1.0);
+
+        if natural < width {
+            sum += aspect;
+            i += 1;
+            continue;
+        }
+
+        let with_item = fit_height(sum + aspect, count);
+        let without_item = (count > 1).then(|| fit_height(sum, count - 1));
+        match without_item {
+            Some(height) if (height - target_height).abs() < (with_item - target_height).abs() => {
+                rows.push(JustifiedRow {
+                    range: start..i,
+                    height,
+                });
+                start = i;
+                sum = 0.0;
+            }
+            _ => {
+                rows.push(JustifiedRow {
+                    range: start..i + 1,
+                    height: with_item,
+                });
+                i += 1;
+                start = i;
+                sum = 0.0;
+            }
+        }
+    }
+
+    if start < aspects.len() {
+        rows.push(JustifiedRow {
+            range: start..aspects.len(),
+            height: target_height,
+        });
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::justify_rows;
+
+    fn row_width(aspects: &[f32], row: &super::JustifiedRow, spacing: f32) -> f32 {
+        let items: f32 = aspects[row.range.clone()]
+            .iter()
+            .map(|a| a * row.height)
+            .sum();
+        items + spacing * (row.range.len() as f32 - 1.0)
+    }
+
+    #[test]
+    fn full_rows_fit_width_and_last_row_keeps_target_height() {
+        let aspects = [1.5, 0.75, 1.0, 1.5, 1.33, 0.66, 1.0];
+        let rows = justify_rows(&aspects, 600.0, 150.0, 10.0);
+
+        let (last, full) = rows.split_last().expect("rows should not be empty");
+        for row in full {
+            assert!(row_width(&aspects, row, 10.0) <= 600.01);
+            assert!(row.height <= 150.0 * 1.5);
+        }
+        assert_eq!(last.height, 150.0);
+        assert_eq!(last.range.end, aspects.len());
+    }
+
+    #[test]
+    fn rows_cover_every_item_in_order() {
+        let aspects = [1.0; 17];
+        let rows = justify_rows(&aspects, 500.0, 120.0, 8.0);
+        let mut next = 0;
+        for row in &rows {
+            assert_eq!(row.range.start, next);
+            assert!(!row.range.is_empty());
+            next = row.range.end;
+        }
+        assert_eq!(next, aspects.len());
+    }
+
+    #[test]
+    fn wide_panorama_takes_its_own_row() {
+        let aspects = [1.0, 5.0, 1.0];
+        let rows = justify_rows(&aspects, 400.0, 150.0, 0.0);
+        assert!(rows.iter().any(|row| row.range == (1..2)));
+    }
+}
@@ -0,0 +1,418 @@
+use crate::i18n::lang::TextBundle;
+use egui::epaint::TextShape;
+use egui::{
+    Align2, Color32, CursorIcon, FontId, Id, Painter, Pos2, Rect, Sense, Shape, Stroke, Ui, Vec2,
+    pos2, vec2,
+};
+use std::f32::consts::FRAC_PI_2;
+
+/// 标尺宽度
+const RULER_SIZE: f32 = 20.0;
+/// 刻度数字之间的最小屏幕距离
+const MIN_LABEL_SPACING: f32 = 60.0;
+/// 指针离参考线多近时可以拖动
+const GUIDE_GRAB: f32 = 4.0;
+
+const RULER_FILL: Color32 = Color32::from_rgba_unmultiplied_const(30, 30, 30, 230);
+const GUIDE_COLOR: Color32 = Color32::from_rgb(0, 200, 255);
+const MEASURE_COLOR: Color32 = Color32::from_rgb(255, 80, 160);
+
+/// 图片像素与屏幕坐标的换算：图片左上角的屏幕位置和缩放比例
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ImageTransform {
+    pub origin: Pos2,
+    pub zoom: f32,
+}
+
+impl ImageTransform {
+    pub fn to_image(self, screen: Pos2) -> Pos2 {
+        ((screen - self.origin) / self.zoom).to_pos2()
+    }
+
+    pub fn to_screen(self, image: Pos2) -> Pos2 {
+        self.origin + image.to_vec2() * self.zoom
+    }
+
+    /// 屏幕位置吸附到最近的像素边界，返回图片坐标
+    pub fn snap(self, screen: Pos2) -> Pos2 {
+        let image = self.to_image(screen);
+        pos2(image.x.round(), image.y.round())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GuideAxis {
+    Horizontal,
+    Vertical,
+}
+
+/// 参考线，位置为图片像素坐标
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Guide {
+    pub axis: GuideAxis,
+    pub position: f32,
+}
+
+impl Guide {
+    fn screen_rect(self, transform: ImageTransform, view: Rect) -> Rect {
+        let at = transform.to_screen(pos2(self.position, self.position));
+        match self.axis {
+            GuideAxis::Vertical => Rect::from_x_y_ranges(
+                at.x - GUIDE_GRAB..=at.x + GUIDE_GRAB,
+                view.min.y + RULER_SIZE..=view.max.y,
+            ),
+            GuideAxis::Horizontal => Rect::from_x_y_ranges(
+                view.min.x + RULER_SIZE..=view.max.x,
+                at.y - GUIDE_GRAB..=at.y + GUIDE_GRAB,
+            ),
+        }
+    }
+}
+
+/// 两点之间的测量结果，单位为图片像素
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Measurement {
+    pub width: f32,
+    pub height: f32,
+    pub distance: f32,
+    /// 相对水平向右的角度，逆时针为正
+    pub angle: f32,
+}
+
+impl Measurement {
+    pub fn between(start: Pos2, end: Pos2) -> Self {
+        let delta = end - start;
+        Self {
+            width: delta.x.abs(),
+            height: delta.y.abs(),
+            distance: delta.length(),
+            // 屏幕 y 轴向下，取反后与数学上的角度方向一致
+            angle: (-delta.y).atan2(delta.x).to_degrees(),
+        }
+    }
+}
+
+/// 标尺上标注数字的间隔（图片像素），取 1、2、5 的十进倍数
+pub fn ruler_step(zoom: f32) -> u32 {
+    (0..10)
+        .flat_map(|exp| [1, 2, 5].map(|m| m * 10u32.pow(exp)))
+        .find(|step| *step as f32 * zoom >= MIN_LABEL_SPACING)
+        .unwrap_or(u32::MAX)
+}
+
+/// 两个数字之间的小刻度间隔
+fn minor_step(step: u32) -> u32 {
+    if step.is_multiple_of(5) {
+        step / 5
+    } else if step.is_multiple_of(2) {
+        step / 2
+    } else {
+        step
+    }
+}
+
+/// 单图模式的标尺、参考线和测量工具
+#[derive(Default)]
+pub struct MeasureState {
+    pub rulers: bool,
+    pub tool: bool,
+    pub guides: Vec<Guide>,
+    /// 测量的起点和终点（图片坐标），终点为 None 时等待第二次点击
+    pub points: Option<(Pos2, Option<Pos2>)>,
+    /// 本帧图片的坐标换算，没有正常显示图片时为 None
+    pub transform: Option<ImageTransform>,
+    /// 正在拖动的参考线
+    dragging: Option<usize>,
+}
+
+/// 在单图视图上绘制并处理标尺、参考线和测量。需在图片绘制之后调用，以便优先响应拖动
+pub fn draw_measure_overlay(ui: &mut Ui, view: Rect, state: &mut MeasureState, text: &TextBundle) {
+    let Some(transform) = state.transform else {
+        return;
+    };
+    let painter = ui.painter_at(view);
+    let pointer = ui
+        .input(|i| i.pointer.hover_pos())
+        .filter(|pos| view.contains(*pos));
+
+    if state.tool {
+        handle_measure_tool(ui, view, transform, state);
+        let end = state
+            .points
+            .and_then(|(_, end)| end.or_else(|| pointer.map(|p| transform.snap(p))));
+        if let Some(((start, _), end)) = state.points.zip(end) {
+            draw_measurement(&painter, view, transform, start, end, text);
+        }
+        if pointer.is_some() {
+            ui.set_cursor_icon(CursorIcon::Crosshair);
+        }
+    }
+
+    if !state.rulers {
+        return;
+    }
+    handle_guides(ui, view, transform, state, pointer);
+
+    let stroke = Stroke::new(1.0, GUIDE_COLOR);
+    for guide in &state.guides {
+        let at = transform.to_screen(pos2(guide.position, guide.position));
+        match guide.axis {
+            GuideAxis::Vertical => painter.vline(at.x, view.y_range(), stroke),
+            GuideAxis::Horizontal => painter.hline(view.x_range(), at.y, stroke),
+        };
+    }
+    draw_rulers(&painter, view, transform, pointer);
+}
+
+/// 拖动或先后点击两点进行测量，起点和终点吸附到像素边界
+fn handle_measure_tool(ui: &Ui, view: Rect, transform: ImageTransform, state: &mut MeasureState) {
+    let response = ui.interact(view, Id::new("measure_tool"), Sense::click_and_drag());
+    if response.drag_started()
+        && let Some(origin) = ui.input(|i| i.pointer.press_origin())
+    {
+        state.points = Some((transform.snap(origin), None));
+    }
+    if response.dragged()
+        && let Some((start, _)) = state.points
+        && let Some(pos) = response.interact_pointer_pos()
+    {
+        state.points = Some((start, Some(transform.snap(pos))));
+    }
+    if response.clicked()
+        && let Some(pos) = response.interact_pointer_pos()
+    {
+        let point = transform.snap(pos);
+        state.points = match state.points {
+            Some((start, None)) => Some((start, Some(point))),
+            _ => Some((point, None)),
+        };
+    }
+}
+
+/// 从标尺拖出参考线，拖动已有参考线可移动，拖回标尺或视图外则删除
+fn handle_guides(
+    ui: &Ui,
+    view: Rect,
+    transform: ImageTransform,
+    state: &mut MeasureState,
+    pointer: Option<Pos2>,
+) {
+    let top = Rect::from_min_max(view.min, pos2(view.max.x, view.min.y + RULER_SIZE));
+    let left = Rect::from_min_max(view.min, pos2(view.min.x + RULER_SIZE, view.max.y));
+    let rulers = [(top, GuideAxis::Horizontal), (left, GuideAxis::Vertical)];
+    for (rect, axis) in rulers {
+        let response = ui.interact(
+            rect,
+            Id::new("measure_ruler").with(axis as u8),
+            Sense::drag(),
+        );
+        if response.drag_started() {
+            state.guides.push(Guide {
+                axis,
+                position: 0.0,
+            });
+            state.dragging = Some(state.guides.len() - 1);
+        }
+    }
+    for (index, guide) in state.guides.iter().enumerate() {
+        let response = ui.interact(
+            guide.screen_rect(transform, view),
+            Id::new("measure_guide").with(index),
+            Sense::drag(),
+        );
+        if response.hovered() || response.dragged() {
+            ui.set_cursor_icon(match guide.axis {
+                GuideAxis::Vertical => CursorIcon::ResizeHorizontal,
+                GuideAxis::Horizontal => CursorIcon::ResizeVertical,
+            });
+        }
+        if response.drag_started() {
+            state.dragging = Some(index);
+        }
+    }
+
+    let Some(index) = state.dragging else {
+        return;
+    };
+    if ui.input(|i| i.pointer.primary_down()) {
+        if let Some(pos) = ui.input(|i| i.pointer.interact_pos())
+            && let Some(guide) = state.guides.get_mut(index)
+        {
+            let snapped = transform.snap(pos);
+            guide.position = match guide.axis {
+                GuideAxis::Vertical => snapped.x,
+                GuideAxis::Horizontal => snapped.y,
+            };
+        }
+    } else {
+        state.dragging = None;
+        let removed = pointer.is_none_or(|pos| top.contains(pos) || left.contains(pos));
+        if removed && index < state.guides.len() {
+            state.guides.remove(index);
+        }
+    }
+}
+
+fn draw_measurement(
+    painter: &Painter,
+    view: Rect,
+    transform: ImageTransform,
+    start: Pos2,
+    end: Pos2,
+    text: &TextBundle,
+) {
+    let a = transform.to_screen(start);
+    let b = transform.to_screen(end);
+    let bounds = Rect::from_two_pos(a, b);
+    painter.extend(Shape::dashed_line(
+        &[
+            bounds.left_top(),
+            bounds.right_top(),
+            bounds.right_bottom(),
+            bounds.left_bottom(),
+            bounds.left_top(),
+        ],
+        Stroke::new(1.0, MEASURE_COLOR.gamma_multiply(0.6)),
+        4.0,
+        4.0,
+    ));
+    painter.line_segment([a, b], Stroke::new(1.5, MEASURE_COLOR));
+    for point in [a, b] {
+        painter.circle_filled(point, 3.0, MEASURE_COLOR);
+    }
+
+    let measure = Measurement::between(start, end);
+    let label = format!(
+        "{} {:.1} px\n{} {:.1}°\n{} {} × {} px",
+        text.measure.distance,
+        measure.distance,
+        text.measure.angle,
+        measure.angle,
+        text.measure.size,
+        measure.width,
+        measure.height
+    );
+    let galley = painter.layout_no_wrap(label, FontId::monospace(12.0), Color32::WHITE);
+    let padding = vec2(6.0, 4.0);
+    let size = galley.size() + padding * 2.0;
+    // 标签放在终点右下方，超出视图时翻到另一侧
+    let mut min = b + vec2(12.0, 12.0);
+    if min.x + size.x > view.max.x {
+        min.x = b.x - 12.0 - size.x;
+    }
+    if min.y + size.y > view.max.y {
+        min.y = b.y - 12.0 - size.y;
+    }
+    let badge = Rect::from_min_size(min, size);
+    painter.rect_filled(badge, 4.0, Color32::from_black_alpha(200));
+    painter.galley(badge.min + padding, galley, Color32::WHITE);
+}
+
+/// 沿视图上边和左边绘制以图片像素为单位的标尺，并标出指针位置
+fn draw_rulers(painter: &Painter, view: Rect, transform: ImageTransform, pointer: Option<Pos2>) {
+    let top = Rect::from_min_max(view.min, pos2(view.max.x, view.min.y + RULER_SIZE));
+    let left = Rect::from_min_max(view.min, pos2(view.min.x + RULER_SIZE, view.max.y));
+    painter.rect_filled(top, 0.0, RULER_FILL);
+    painter.rect_filled(left, 0.0, RULER_FILL);
+
+    let step = ruler_step(transform.zoom) as i64;
+    let minor = minor_step(step as u32) as i64;
+    let tick = Stroke::new(1.0, Color32::GRAY);
+    let font = FontId::proportional(10.0);
+    let first = transform.to_image(view.min + Vec2::splat(RULER_SIZE));
+    let last = transform.to_image(view.max);
+
+    let start = (first.x as i64).div_euclid(minor) * minor;
+    for value in (start..=last.x.ceil() as i64).step_by(minor as usize) {
+        let x = transform.to_screen(pos2(value as f32, 0.0)).x;
+        if x < top.min.x + RULER_SIZE {
+            continue;
+        }
+        let major = value % step == 0;
+        let length = if major {
+            RULER_SIZE * 0.6
+        } else {
+            RULER_SIZE * 0.25
+        };
+        painter.vline(x, top.max.y - length..=top.max.y, tick);
+        if major {
+            painter.text(
+                pos2(x + 2.0, top.min.y + 1.0),
+                Align2::LEFT_TOP,
+                value.to_string(),
+                font.clone(),
+                Color32::LIGHT_GRAY,
+            );
+        }
+    }
+
+    let start = (first.y as i64).div_euclid(minor) * minor;
+    for value in (start..=last.y.ceil() as i64).step_by(minor as usize) {
+        let y = transform.to_screen(pos2(0.0, value as f32)).y;
+        if y < left.min.y + RULER_SIZE {
+            continue;
+        }
+        let major = value % step == 0;
+        let length = if major {
+            RULER_SIZE * 0.6
+        } else {
+            RULER_SIZE * 0.25
+        };
+        painter.hline(left.max.x - length..=left.max.x, y, tick);
+        if major {
+            // 左侧标尺的数字逆时针旋转 90°，从刻度处向上书写
+            let galley =
+                painter.layout_no_wrap(value.to_string(), font.clone(), Color32::LIGHT_GRAY);
+            painter.add(
+                TextShape::new(pos2(left.min.x + 1.0, y - 2.0), galley, Color32::LIGHT_GRAY)
+                    .with_angle(-FRAC_PI_2),
+            );
+        }
+    }
+
+    if let Some(pos) = pointer {
+        let marker = Stroke::new(1.0, MEASURE_COLOR);
+        if pos.x > top.min.x + RULER_SIZE {
+            painter.vline(pos.x, top.y_range(), marker);
+        }
+        if pos.y > left.min.y + RULER_SIZE {
+            painter.hline(left.x_range(), pos.y, marker);
+        }
+    }
+    painter.rect_filled(
+        Rect::from_min_size(view.min, Vec2::splat(RULER_SIZE)),
+        0.0,
+        RULER_FILL,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImageTransform, Measurement, ruler_step};
+    use egui::pos2;
+
+    #[test]
+    fn transform_snap_and_measure() {
+        let transform = ImageTransform {
+            origin: pos2(100.0, 50.0),
+            zoom: 4.0,
+        };
+        assert_eq!(transform.to_image(pos2(140.0, 90.0)), pos2(10.0, 10.0));
+        assert_eq!(transform.to_screen(pos2(10.0, 10.0)), pos2(140.0, 90.0));
+        // 3.4 和 9.6 像素分别吸附到 3 和 10
+        assert_eq!(transform.snap(pos2(113.6, 88.4)), pos2(3.0, 10.0));
+
+        let measure = Measurement::between(pos2(0.0, 40.0), pos2(30.0, 0.0));
+        assert_eq!((measure.width, measure.height), (30.0, 40.0));
+        assert_eq!(measure.distance, 50.0);
+        assert!((measure.angle - 53.13).abs() < 0.01);
+        assert_eq!(
+            Measurement::between(pos2(0.0, 0.0), pos2(0.0, 5.0)).angle,
+            -90.0
+        );
+
+        assert_eq!(ruler_step(100.0), 1);
+        assert_eq!(ruler_step(1.0), 100);
+        assert_eq!(ruler_step(0.25), 500);
+    }
+}
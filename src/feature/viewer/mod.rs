@@ -1,39 +1,77 @@
+use crate::core::byte_cache::CacheStats;
 use crate::feature::screenshot::ocr::ocr_panel;
 use crate::ui::widgets::context_menu::{
-    ContextMenuAction, handle_context_menu_action, render_context_menu,
+    ContextMenuAction, ContextMenuTarget, handle_context_menu_action, render_context_menu,
 };
 use crate::ui::widgets::menu::{MenuAction, draw_menu};
 use crate::{
-    core::hotkeys::HotkeyAction,
+    core::{hotkeys::HotkeyAction, keymap::Action},
     feature::Feature,
-    feature::viewer::viewer_state::{ViewMode, ViewerState},
+    feature::viewer::viewer_state::ViewerState,
     i18n::lang::get_i18n_text,
     model::{
-        config::Config,
+        config::{
+            ClickAction, Config, GridConfig, ImageBackground, ViewerConfig, get_context_config,
+        },
         image_meta::SUPPORTED_IMAGE_EXTENSIONS,
         mode::{AppMode, PanelMode, PopupMode},
+        session::{ViewMode, ZoomMode},
         state::CommonState,
     },
     ui::widgets::{
         about::render_about_window,
         icons::{IconType, draw_icon_button},
         loading::global_loading,
-        modal::ModalAction,
+        modal::{ModalAction, ModalFrame},
         settings::render_settings_window,
+        toast::ToastManager,
     },
+    utils::clipboard::{ClipboardContent, read_clipboard},
 };
+use batch::{BatchOperation, BatchOutcome};
+use channels::ChannelView;
+use compare_view::draw_compare_view;
+use contact_sheet::{export_contact_sheet, render_contact_sheet_window};
 use eframe::egui;
-use egui::{CentralPanel, Color32, Context, Frame, Panel, Ui, Vec2, ViewportCommand};
+use egui::{CentralPanel, Color32, Context, Frame, Panel, RichText, Ui, Vec2, ViewportCommand};
+use filter::ProbeEvent;
 use grid_view::draw_grid_view;
+use join::render_join_window;
+use mouse::{read_mouse_input, wheel_navigation};
+use open_with::{open_with, pressed_external_app};
 use rfd::FileDialog;
+use sequence::{
+    PreviewAction, SequenceDialogAction, draw_sequence_preview, export_sequence,
+    render_sequence_window,
+};
 use single_view::draw_single_view;
+use unsaved::save_unsaved_as;
 
 pub mod arrows;
+pub mod background;
+pub mod batch;
+pub mod channels;
+pub mod compare_view;
+pub mod contact_sheet;
+pub mod filter;
 pub mod grid_view;
+pub mod join;
+pub mod justified;
+pub mod measure;
+pub mod mouse;
+pub mod open_with;
+pub mod overlay;
 pub mod preview;
 pub mod properties_panel;
+pub mod selection;
+pub mod sequence;
 pub mod single_view;
+pub mod sort;
+pub mod unsaved;
 pub mod viewer_state;
+pub mod zoom;
+
+const MB: f64 = 1024.0 * 1024.0;
 
 /// ViewerFeature - 图片查看器功能模块
 pub struct ViewerFeature {
@@ -46,6 +84,10 @@ pub struct ViewerFeature {
     pending_config: Option<Config>,
     /// 待处理的模式切换（从菜单请求）
     pending_mode_switch: Option<AppMode>,
+    /// 本次 Ctrl+V 已经由文本粘贴事件处理过
+    paste_handled: bool,
+    /// 滚轮翻页尚未满一格的滚动量
+    wheel_navigate: f32,
 }
 
 impl ViewerFeature {
@@ -57,6 +99,8 @@ impl ViewerFeature {
             pending_config_action: None,
             pending_config: None,
             pending_mode_switch: None,
+            paste_handled: false,
+            wheel_navigate: 0.0,
         }
     }
 }
@@ -100,7 +144,7 @@ impl ViewerFeature {
         }
 
         //处理看图模式下的输入事件
-        self.handle_input(ctx);
+        self.handle_input(ctx, &common.toast_manager);
 
         // 更新属性面板动画进度
         let panel_is_open = matches!(self.panel, PanelMode::Properties);
@@ -115,11 +159,77 @@ impl ViewerFeature {
             ctx.request_repaint();
         }
 
+        // 设置中切换了放大采样方式或缓存预算
+        let config = get_context_config(ctx);
+        self.state
+            .sync_texture_options(config.viewer.texture_options());
+        self.state.set_cache_budget(&config.cache);
+
         // 处理图片加载结果
         if self.state.process_load_results(ctx) {
             ctx.request_repaint();
         }
 
+        // 筛选所需的文件信息在后台读取，读完后更新列表
+        if let Some(count) = self.state.poll_filter(ctx) {
+            let message = get_i18n_text(ctx)
+                .filter
+                .matched
+                .replace("{count}", &count.to_string());
+            common.toast_manager.success(message);
+        }
+        // 加载提示不会自动消失，读取中止时要换成结束提示
+        match self.state.filter.take_probe_event() {
+            Some(ProbeEvent::Started) => {
+                common
+                    .toast_manager
+                    .loading(get_i18n_text(ctx).filter.probing);
+            }
+            Some(ProbeEvent::Cancelled) => {
+                common
+                    .toast_manager
+                    .success(get_i18n_text(ctx).filter.probe_cancelled);
+            }
+            None => {}
+        }
+
+        // 处理批量操作结果
+        if let Some(outcome) = self.state.batch.try_recv() {
+            self.finish_batch(ctx, outcome, &common.toast_manager);
+        }
+
+        // 处理拼接结果
+        if let Some(result) = self.state.join.try_recv() {
+            match result {
+                Ok(image) => {
+                    self.popup = PopupMode::None;
+                    self.state.open_unsaved(ctx, image);
+                    common.toast_manager.success(get_i18n_text(ctx).join.done);
+                }
+                Err(e) => {
+                    tracing::error!("拼接图片失败: {}", e);
+                    let text = get_i18n_text(ctx);
+                    common
+                        .toast_manager
+                        .error(format!("{}: {}", text.join.failed, e));
+                }
+            }
+        }
+
+        // 动图预览帧解码完成
+        if let Some(result) = self.state.sequence.poll(ctx) {
+            let text = get_i18n_text(ctx);
+            match result {
+                Ok(()) => common.toast_manager.success(text.sequence.preview_ready),
+                Err(e) => {
+                    tracing::error!("动图预览失败: {}", e);
+                    common
+                        .toast_manager
+                        .error(format!("{}: {}", text.sequence.preview_failed, e));
+                }
+            }
+        }
+
         // 处理新路径
         if let Ok(path) = common.path_receiver.try_recv() {
             self.state.open_new_context(ctx.clone(), path);
@@ -131,16 +241,137 @@ impl ViewerFeature {
         }
     }
 
+    fn finish_batch(&mut self, ctx: &Context, outcome: BatchOutcome, toast_manager: &ToastManager) {
+        let text = get_i18n_text(ctx);
+        if outcome.failed == 0 {
+            toast_manager.success(
+                text.batch
+                    .done
+                    .replace("{count}", &outcome.succeeded.to_string()),
+            );
+        } else {
+            toast_manager.error(
+                text.batch
+                    .partial
+                    .replace("{ok}", &outcome.succeeded.to_string())
+                    .replace("{failed}", &outcome.failed.to_string()),
+            );
+        }
+
+        // 文件被移走、删除，或者复制、转换到了当前文件夹时需要刷新列表
+        let touches_folder = outcome.destination.is_some()
+            && outcome.destination == self.state.folder
+            && outcome.operation != BatchOperation::MoveTo;
+        if !outcome.removed.is_empty() || touches_folder {
+            self.state.refresh_folder(ctx.clone(), &outcome.removed);
+        }
+    }
+
     /// 处理 Viewer 特有的输入事件
-    pub fn handle_input(&mut self, ctx: &Context) {
-        use egui::Key;
+    pub fn handle_input(&mut self, ctx: &Context, toast_manager: &ToastManager) {
+        use egui::{Event, Key};
+
+        // 输入框获得焦点时按键用于编辑文字
+        let typing = ctx.text_edit_focused();
+        let keys_free = !typing && self.popup == PopupMode::None;
+
+        let config = get_context_config(ctx);
+        let pressed = |action: Action| ctx.input(|i| config.keymap.pressed(i, action));
 
         // 图片导航
-        if ctx.input(|i| i.key_pressed(Key::ArrowLeft)) {
-            self.state.prev_image(ctx.clone());
+        if !typing && self.state.view_mode != ViewMode::Compare {
+            if pressed(Action::PrevImage) {
+                self.state.prev_image(ctx.clone());
+            }
+            if pressed(Action::NextImage) {
+                self.state.next_image(ctx.clone());
+            }
         }
-        if ctx.input(|i| i.key_pressed(Key::ArrowRight)) {
-            self.state.next_image(ctx.clone());
+
+        // 网格多选
+        if keys_free && self.state.view_mode == ViewMode::Grid {
+            if pressed(Action::SelectAll) {
+                self.state.selection.select_all(&self.state.list);
+            }
+            if pressed(Action::ClearSelection) {
+                self.state.selection.clear();
+            }
+            if pressed(Action::DeleteSelection) && !self.state.selection.is_empty() {
+                self.popup = PopupMode::ConfirmDelete(self.state.selected_paths());
+            }
+        }
+
+        // 外部程序快捷键
+        if keys_free
+            && self.state.view_mode != ViewMode::Compare
+            && let Some(index) = pressed_external_app(ctx)
+        {
+            open_with(ctx, &self.state, index, toast_manager);
+        }
+
+        // 缩放方式
+        if keys_free && self.state.view_mode == ViewMode::Single {
+            if let Some(mode) = ZoomMode::ALL
+                .into_iter()
+                .find(|mode| pressed(mode.action()))
+            {
+                self.state.set_zoom_mode(ctx, mode);
+            }
+            if pressed(Action::ToggleZoomLock) {
+                self.state.zoom_locked = !self.state.zoom_locked;
+            }
+            if pressed(Action::ToggleAlpha) {
+                self.state.channel_view = if self.state.channel_view == ChannelView::Alpha {
+                    ChannelView::Normal
+                } else {
+                    ChannelView::Alpha
+                };
+            }
+            if pressed(Action::CycleChannelView) {
+                self.state.channel_view = self.state.channel_view.next();
+            }
+            if pressed(Action::ToggleInfoOverlay) {
+                self.state.session.show_info_overlay = !self.state.session.show_info_overlay;
+            }
+            if pressed(Action::ToggleRulers) {
+                self.state.measure.rulers = !self.state.measure.rulers;
+            }
+            if pressed(Action::ToggleMeasure) {
+                self.state.measure.tool = !self.state.measure.tool;
+            }
+        }
+
+        // 粘贴：剪贴板里只有图片或文件时 egui 不产生 Paste 事件，只能从 V 键抬起识别
+        if keys_free && self.state.view_mode != ViewMode::Compare {
+            let (pasted_text, v_released) = ctx.input(|i| {
+                let pasted = i.events.iter().any(|e| matches!(e, Event::Paste(_)));
+                let released = i.events.iter().any(|e| {
+                    matches!(e, Event::Key { key: Key::V, pressed: false, modifiers, .. }
+                        if modifiers.command)
+                });
+                (pasted, released)
+            });
+            if pasted_text || (v_released && !self.paste_handled) {
+                self.paste_from_clipboard(ctx, toast_manager);
+            }
+            if pasted_text {
+                self.paste_handled = true;
+            }
+            if v_released {
+                self.paste_handled = false;
+            }
+        }
+
+        if keys_free && self.state.current.unsaved && pressed(Action::SaveAs) {
+            save_unsaved_as(ctx, &self.state, toast_manager);
+        }
+
+        if keys_free && pressed(Action::ToggleCacheStats) {
+            self.state.show_cache_stats = !self.state.show_cache_stats;
+        }
+
+        if keys_free && self.state.view_mode == ViewMode::Compare && pressed(Action::ExitCompare) {
+            self.state.view_mode = ViewMode::Grid;
         }
 
         // 拖放文件
@@ -149,18 +380,87 @@ impl ViewerFeature {
             self.state.handle_dropped_file(ctx.clone(), path);
         }
 
-        // 缩放（属性面板区域禁止）
+        // 滚轮、鼠标按键和触控板手势（属性面板、菜单、弹窗和信息浮层上禁止）
         let pointer_over_panel = matches!(self.panel, PanelMode::Properties)
             && ctx.input(|i| {
                 i.pointer
                     .hover_pos()
                     .is_some_and(|pos| pos.x > i.viewport_rect().max.x - 300.0)
             });
-        if !pointer_over_panel {
-            let scroll_delta = ctx.input(|i| i.smooth_scroll_delta.y);
-            let pointer_pos = ctx.input(|i| i.pointer.hover_pos());
-            let viewport = ctx.content_rect();
-            self.state.update_zoom(scroll_delta, pointer_pos, viewport);
+        if !pointer_over_panel
+            && self.state.view_hovered
+            && self.popup == PopupMode::None
+            && self.state.view_mode == ViewMode::Single
+        {
+            self.handle_mouse(ctx, &config);
+        }
+    }
+
+    fn handle_mouse(&mut self, ctx: &Context, config: &Config) {
+        let input = ctx.input(|i| read_mouse_input(i, &config.mouse));
+        let pointer_pos = ctx.input(|i| i.pointer.hover_pos());
+        let viewport = ctx.content_rect();
+
+        self.state.update_zoom(
+            input.zoom_delta * config.zoom_sensitivity,
+            pointer_pos,
+            viewport,
+        );
+        if input.zoom_factor != 1.0 {
+            self.state
+                .zoom_to(self.state.zoom * input.zoom_factor, pointer_pos, viewport);
+        }
+        self.state.viewport_offset += input.pan;
+        match wheel_navigation(&mut self.wheel_navigate, input.navigate) {
+            -1 => self.state.prev_image(ctx.clone()),
+            1 => self.state.next_image(ctx.clone()),
+            _ => {}
+        }
+
+        // 双击翻页箭头或用测量工具连续点击时不触发双击动作
+        let in_arrow_zone = pointer_pos
+            .is_some_and(|pos| pos.x < viewport.min.x + 100.0 || pos.x > viewport.max.x - 100.0);
+        let double_click = input
+            .double_click
+            .filter(|_| !in_arrow_zone && !self.state.measure.tool);
+        for action in input.clicks.into_iter().chain(double_click) {
+            self.run_click_action(ctx, action);
+        }
+    }
+
+    fn run_click_action(&mut self, ctx: &Context, action: ClickAction) {
+        match action {
+            ClickAction::None => {}
+            ClickAction::ToggleFit => {
+                let mode = if (self.state.zoom - 1.0).abs() < 0.001 {
+                    ZoomMode::Fit
+                } else {
+                    ZoomMode::Actual
+                };
+                self.state.set_zoom_mode(ctx, mode);
+            }
+            ClickAction::Fit => self.state.set_zoom_mode(ctx, ZoomMode::Fit),
+            ClickAction::Actual => self.state.set_zoom_mode(ctx, ZoomMode::Actual),
+            ClickAction::Fullscreen => {
+                let fullscreen = ctx.input(|i| i.viewport().fullscreen.unwrap_or(false));
+                ctx.send_viewport_cmd(ViewportCommand::Fullscreen(!fullscreen));
+            }
+            ClickAction::PrevImage => self.state.prev_image(ctx.clone()),
+            ClickAction::NextImage => self.state.next_image(ctx.clone()),
+        }
+    }
+
+    fn paste_from_clipboard(&mut self, ctx: &Context, toast_manager: &ToastManager) {
+        let opened = match read_clipboard() {
+            Some(ClipboardContent::Image(image)) => {
+                self.state.open_unsaved(ctx, image);
+                true
+            }
+            Some(ClipboardContent::Paths(paths)) => self.state.open_paths(ctx, paths),
+            None => false,
+        };
+        if !opened {
+            toast_manager.error(get_i18n_text(ctx).toast.paste_empty);
         }
     }
 
@@ -169,7 +469,8 @@ impl ViewerFeature {
         let ctx = ui.ctx().clone();
 
         // 1. 顶部面板
-        let (open_file, open_folder, menu_action) = draw_menu(ui, &mut self.popup);
+        let (open_file, open_folder, menu_action) =
+            draw_menu(ui, &mut self.popup, &self.state.session);
 
         if open_file {
             let sender = common.path_sender.clone();
@@ -201,6 +502,16 @@ impl ViewerFeature {
             MenuAction::ShowScreenshot => {
                 self.pending_mode_switch = Some(AppMode::Screenshot);
             }
+            MenuAction::OpenRecent(path) => {
+                if path.exists() {
+                    self.state.open_new_context(ctx.clone(), path);
+                } else {
+                    common
+                        .toast_manager
+                        .error(get_i18n_text(&ctx).toast.recent_missing);
+                }
+            }
+            MenuAction::ClearRecent => self.state.clear_recent(),
             MenuAction::None => {}
         }
 
@@ -208,18 +519,37 @@ impl ViewerFeature {
         self.draw_bottom_panel(ui);
         ocr_panel::show_inside(ui, &mut common.ocr_state);
         if matches!(self.panel, PanelMode::Properties) {
-            properties_panel::draw_properties_panel_inside(ui, &mut self.panel, &self.state);
+            properties_panel::draw_properties_panel_inside(
+                ui,
+                &mut self.panel,
+                &mut self.state,
+                &common.toast_manager,
+            );
         }
 
         let background_frame = Frame::NONE.fill(Color32::from_rgb(25, 25, 25));
         CentralPanel::default()
             .frame(background_frame)
             .show_inside(ui, |ui| match self.state.view_mode {
+                // 动图预览占用中央区域，结束后回到原来的视图
+                _ if let Some(preview) = &self.state.sequence.preview => {
+                    let options = &self.state.session.sequence;
+                    match draw_sequence_preview(ui, preview, options) {
+                        Some(PreviewAction::Export) => {
+                            self.popup = PopupMode::Sequence(preview.paths.clone());
+                        }
+                        Some(PreviewAction::Close) => self.state.sequence.preview = None,
+                        None => {}
+                    }
+                }
                 ViewMode::Single => {
                     draw_single_view(&ctx, ui, &mut self.state, &mut self.popup);
                 }
                 ViewMode::Grid => {
-                    draw_grid_view(&ctx, ui, &mut self.state);
+                    draw_grid_view(&ctx, ui, &mut self.state, &mut self.popup);
+                }
+                ViewMode::Compare => {
+                    draw_compare_view(&ctx, ui, &mut self.state);
                 }
             });
 
@@ -227,15 +557,16 @@ impl ViewerFeature {
         common.toast_system.update(&ctx);
 
         // 4. 处理 overlays (about, settings, context_menu)
-        let context_menu_action = self.draw_overlays(&ctx);
+        let context_menu_action = self.draw_overlays(&ctx, &common.toast_manager);
 
         // 处理右键菜单操作
         if let Some(action) = context_menu_action {
             handle_context_menu_action(
                 &ctx,
                 action,
-                &self.state,
+                &mut self.state,
                 &mut self.panel,
+                &mut self.popup,
                 &common.toast_manager,
             );
         }
@@ -285,11 +616,77 @@ impl ViewerFeature {
                             }
                         },
                     );
+
+                    if self.state.view_mode == ViewMode::Single {
+                        ui.add_space(12.0);
+                        self.draw_zoom_menu(ui);
+                        ui.add_space(8.0);
+                        self.draw_background_menu(ui);
+                    }
                 });
             });
     }
 
-    fn draw_overlays(&mut self, ctx: &Context) -> Option<ContextMenuAction> {
+    /// 当前缩放比例，点击展开缩放方式、标尺和测量工具菜单
+    fn draw_zoom_menu(&mut self, ui: &mut Ui) {
+        let ctx = ui.ctx().clone();
+        let text = get_i18n_text(&ctx);
+        ui.menu_button(format!("{:.0}%", self.state.zoom * 100.0), |ui| {
+            let config = get_context_config(&ctx);
+            for mode in ZoomMode::ALL {
+                let button =
+                    egui::Button::selectable(self.state.zoom_mode == mode, mode.label(text))
+                        .shortcut_text(config.keymap.binding(mode.action()));
+                if ui.add(button).clicked() {
+                    self.state.set_zoom_mode(&ctx, mode);
+                    ui.close();
+                }
+            }
+            ui.separator();
+            ui.checkbox(&mut self.state.zoom_locked, text.zoom.lock);
+            ui.separator();
+            let measure = &mut self.state.measure;
+            ui.checkbox(&mut measure.rulers, text.measure.rulers);
+            ui.checkbox(&mut measure.tool, text.measure.tool);
+            if ui
+                .add_enabled(
+                    !measure.guides.is_empty(),
+                    egui::Button::new(text.measure.clear_guides),
+                )
+                .clicked()
+            {
+                measure.guides.clear();
+                ui.close();
+            }
+        });
+    }
+
+    /// 透明区域背景和通道视图
+    fn draw_background_menu(&mut self, ui: &mut Ui) {
+        let ctx = ui.ctx().clone();
+        let text = get_i18n_text(&ctx);
+        let current = get_context_config(&ctx).viewer.clone();
+        let mut viewer = current.clone();
+        ui.menu_button(text.background.title, |ui| {
+            for background in ImageBackground::ALL {
+                ui.radio_value(&mut viewer.background, background, background.label(text));
+            }
+            ui.separator();
+            ui.label(RichText::new(text.channel.title).weak());
+            for view in ChannelView::ALL {
+                ui.radio_value(&mut self.state.channel_view, view, view.label(text));
+            }
+        });
+        if viewer != current {
+            self.state.pending_viewer_config = Some(viewer);
+        }
+    }
+
+    fn draw_overlays(
+        &mut self,
+        ctx: &Context,
+        toast_manager: &ToastManager,
+    ) -> Option<ContextMenuAction> {
         let mut context_menu_action = None;
         let text = get_i18n_text(ctx);
 
@@ -337,7 +734,12 @@ impl ViewerFeature {
             }
             PopupMode::ContextMenu(pos) => {
                 let mut pos_opt = Some(*pos);
-                let action = render_context_menu(ctx, &mut pos_opt);
+                let target = match self.state.view_mode {
+                    ViewMode::Grid => ContextMenuTarget::Selection(self.state.selection.len()),
+                    _ if self.state.current.unsaved => ContextMenuTarget::Unsaved,
+                    _ => ContextMenuTarget::Current,
+                };
+                let action = render_context_menu(ctx, &mut pos_opt, target);
 
                 if let Some(action) = action {
                     context_menu_action = Some(action);
@@ -347,9 +749,95 @@ impl ViewerFeature {
                     self.popup = PopupMode::None;
                 }
             }
+            PopupMode::ConfirmDelete(paths) => {
+                let mut open = true;
+                let mut confirmed = false;
+                let message = text
+                    .batch
+                    .delete_confirm
+                    .replace("{count}", &paths.len().to_string());
+                ModalFrame::show(ctx, &mut open, text.batch.delete_title, |ui| {
+                    let mut action = ModalAction::None;
+                    ui.vertical_centered(|ui| {
+                        ui.add_space(8.0);
+                        ui.label(message);
+                        ui.add_space(16.0);
+                        ui.horizontal(|ui| {
+                            if ui.button(text.batch.confirm_delete).clicked() {
+                                confirmed = true;
+                                action = ModalAction::Apply;
+                            }
+                            if ui.button(text.batch.cancel).clicked() {
+                                action = ModalAction::Close;
+                            }
+                        });
+                    });
+                    action
+                });
+
+                if confirmed {
+                    let paths = std::mem::take(paths);
+                    self.state
+                        .batch
+                        .start(ctx, paths, BatchOperation::Delete, toast_manager);
+                }
+                if !open {
+                    self.popup = PopupMode::None;
+                }
+            }
+            PopupMode::ContactSheet(paths) => {
+                let mut open = true;
+                let options = &mut self.state.session.contact_sheet;
+                let action = render_contact_sheet_window(ctx, &mut open, options, paths.len());
+                if action == ModalAction::Apply {
+                    let paths = std::mem::take(paths);
+                    export_contact_sheet(ctx, paths, options.clone(), toast_manager);
+                }
+                if !open {
+                    self.popup = PopupMode::None;
+                }
+            }
+            PopupMode::Join(paths) => {
+                let mut open = true;
+                let options = &mut self.state.session.join;
+                let action = render_join_window(ctx, &mut open, options, paths.len());
+                if action == ModalAction::Apply {
+                    let paths = std::mem::take(paths);
+                    self.state
+                        .join
+                        .start(ctx, paths, options.clone(), toast_manager);
+                }
+                if !open {
+                    self.popup = PopupMode::None;
+                }
+            }
+            PopupMode::Sequence(paths) => {
+                let mut open = true;
+                let options = &mut self.state.session.sequence;
+                match render_sequence_window(ctx, &mut open, options, paths.len()) {
+                    SequenceDialogAction::Preview => {
+                        let paths = std::mem::take(paths);
+                        self.state
+                            .sequence
+                            .start_preview(ctx, paths, options.scale, toast_manager);
+                    }
+                    SequenceDialogAction::Export => {
+                        let paths = std::mem::take(paths);
+                        export_sequence(ctx, paths, options.clone(), toast_manager);
+                    }
+                    SequenceDialogAction::None => {}
+                }
+                if !open {
+                    self.popup = PopupMode::None;
+                }
+            }
             PopupMode::None => {}
         }
 
+        if self.state.show_cache_stats {
+            self.draw_cache_stats(ctx);
+        }
+
         // 加载提示
         if self.state.current.texture.is_none() && self.state.loader.is_loading {
             global_loading(ctx, text.loading.parsing.to_string());
@@ -358,6 +846,31 @@ impl ViewerFeature {
         context_menu_action
     }
 
+    /// 调试用的缓存读数，F3 切换
+    fn draw_cache_stats(&self, ctx: &Context) {
+        let text = get_i18n_text(ctx);
+        let line = |name: &str, stats: CacheStats| {
+            let summary = text
+                .cache
+                .summary
+                .replace("{count}", &stats.entries.to_string())
+                .replace("{used}", &format!("{:.1}", stats.bytes as f64 / MB))
+                .replace("{budget}", &format!("{:.0}", stats.budget as f64 / MB))
+                .replace("{rate}", &format!("{:.0}", stats.hit_rate() * 100.0));
+            format!("{name}: {summary}")
+        };
+
+        egui::Area::new(egui::Id::new("cache_stats"))
+            .anchor(egui::Align2::LEFT_TOP, [8.0, 36.0])
+            .interactable(false)
+            .show(ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.monospace(line(text.cache.images, self.state.texture_cache.stats()));
+                    ui.monospace(line(text.cache.thumbs, self.state.thumbs.cache.stats()));
+                });
+            });
+    }
+
     /// 获取待处理的配置动作
     pub fn get_pending_config_action(&self) -> Option<ModalAction> {
         self.pending_config_action
@@ -369,6 +882,16 @@ impl ViewerFeature {
         self.pending_config.take()
     }
 
+    /// 取出网格中调整过的显示设置，由 App 写回配置
+    pub fn take_pending_grid_config(&mut self) -> Option<GridConfig> {
+        self.state.pending_grid_config.take()
+    }
+
+    /// 取出底栏中调整过的看图设置，由 App 写回配置
+    pub fn take_pending_viewer_config(&mut self) -> Option<ViewerConfig> {
+        self.state.pending_viewer_config.take()
+    }
+
     /// 获取待处理的模式切换并清除状态
     pub fn take_pending_mode_switch(&mut self) -> Option<AppMode> {
         self.pending_mode_switch.take()
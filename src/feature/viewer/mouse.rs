@@ -0,0 +1,207 @@
+use crate::i18n::lang::TextBundle;
+use crate::model::config::{ClickAction, MouseConfig, WheelAction};
+use egui::{Event, InputState, MouseWheelUnit, PointerButton, Vec2};
+
+/// 一行滚动折算的点数，与 egui 默认的行滚动速度一致
+const LINE_POINTS: f32 = 40.0;
+/// 滚轮翻页时累计滚过多少点切换一张
+const NAVIGATE_STEP: f32 = LINE_POINTS;
+
+impl WheelAction {
+    pub fn label(self, text: &TextBundle) -> &'static str {
+        match self {
+            Self::Zoom => text.mouse.zoom,
+            Self::Navigate => text.mouse.navigate,
+            Self::Pan => text.mouse.pan,
+            Self::None => text.mouse.none,
+        }
+    }
+}
+
+impl ClickAction {
+    pub fn label(self, text: &TextBundle) -> &'static str {
+        match self {
+            Self::None => text.mouse.none,
+            Self::ToggleFit => text.mouse.toggle_fit,
+            Self::Fit => text.zoom.fit,
+            Self::Actual => text.zoom.actual,
+            Self::Fullscreen => text.mouse.fullscreen,
+            Self::PrevImage => text.keymap.prev_image,
+            Self::NextImage => text.keymap.next_image,
+        }
+    }
+}
+
+/// 一帧内的鼠标和触控板输入按配置折算成的操作
+#[derive(Debug, PartialEq)]
+pub struct MouseInput {
+    /// 加法缩放量，单位为滚动的点数
+    pub zoom_delta: f32,
+    /// 捏合手势的缩放倍数
+    pub zoom_factor: f32,
+    pub pan: Vec2,
+    /// 用于翻页的滚动量，正值为向上滚
+    pub navigate: f32,
+    pub clicks: Vec<ClickAction>,
+    /// 双击单独返回，调用方需要排除翻页箭头区域
+    pub double_click: Option<ClickAction>,
+}
+
+impl Default for MouseInput {
+    fn default() -> Self {
+        Self {
+            zoom_delta: 0.0,
+            zoom_factor: 1.0,
+            pan: Vec2::ZERO,
+            navigate: 0.0,
+            clicks: Vec::new(),
+            double_click: None,
+        }
+    }
+}
+
+pub fn read_mouse_input(input: &InputState, config: &MouseConfig) -> MouseInput {
+    let mut out = read_wheel_events(&input.events, config, input.viewport_rect().height());
+    let buttons = [
+        (PointerButton::Middle, config.middle_click),
+        (PointerButton::Extra1, config.back_button),
+        (PointerButton::Extra2, config.forward_button),
+    ];
+    out.clicks = buttons
+        .into_iter()
+        .filter(|(button, action)| {
+            *action != ClickAction::None && input.pointer.button_clicked(*button)
+        })
+        .map(|(_, action)| action)
+        .collect();
+    if config.double_click != ClickAction::None
+        && input.pointer.button_double_clicked(PointerButton::Primary)
+    {
+        out.double_click = Some(config.double_click);
+    }
+    out
+}
+
+/// 直接读取原始滚轮事件，按修饰键区分；egui 汇总后的滚动量已经把 Ctrl + 滚轮换成了缩放
+fn read_wheel_events(events: &[Event], config: &MouseConfig, page_height: f32) -> MouseInput {
+    let mut out = MouseInput::default();
+    for event in events {
+        match event {
+            Event::MouseWheel {
+                unit,
+                delta,
+                modifiers,
+                ..
+            } => {
+                let delta = match unit {
+                    MouseWheelUnit::Point => *delta,
+                    MouseWheelUnit::Line => *delta * LINE_POINTS,
+                    MouseWheelUnit::Page => *delta * page_height,
+                };
+                // 触控板双指滑动以像素为单位上报，普通滚轮按行上报
+                if config.trackpad_pan && *unit == MouseWheelUnit::Point && modifiers.is_none() {
+                    out.pan += delta;
+                    continue;
+                }
+                let action = if modifiers.ctrl || modifiers.command {
+                    config.ctrl_wheel
+                } else if modifiers.shift {
+                    config.shift_wheel
+                } else {
+                    config.wheel
+                };
+                // 部分系统按住 Shift 时直接上报水平滚动
+                let amount = if delta.y != 0.0 { delta.y } else { delta.x };
+                match action {
+                    WheelAction::Zoom => out.zoom_delta += amount,
+                    WheelAction::Navigate => out.navigate += amount,
+                    WheelAction::Pan if modifiers.shift => out.pan.x += amount,
+                    WheelAction::Pan => out.pan += delta,
+                    WheelAction::None => {}
+                }
+            }
+            Event::Zoom(factor) if config.pinch_zoom => out.zoom_factor *= factor,
+            _ => {}
+        }
+    }
+    out
+}
+
+/// 累计滚动量，满一格时返回翻页方向：1 为下一张，-1 为上一张。每帧最多翻一张
+pub fn wheel_navigation(accum: &mut f32, amount: f32) -> i32 {
+    if amount == 0.0 {
+        return 0;
+    }
+    // 反向滚动时丢弃之前的累计
+    if *accum * amount < 0.0 {
+        *accum = 0.0;
+    }
+    *accum += amount;
+    if accum.abs() < NAVIGATE_STEP {
+        return 0;
+    }
+    let direction = if *accum > 0.0 { -1 } else { 1 };
+    *accum = 0.0;
+    direction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LINE_POINTS, read_wheel_events, wheel_navigation};
+    use crate::model::config::{MouseConfig, WheelAction};
+    use egui::{Event, Modifiers, MouseWheelUnit, TouchPhase, Vec2};
+
+    fn wheel(unit: MouseWheelUnit, delta: Vec2, modifiers: Modifiers) -> Event {
+        Event::MouseWheel {
+            unit,
+            delta,
+            modifiers,
+            phase: TouchPhase::Move,
+        }
+    }
+
+    #[test]
+    fn wheel_events_follow_modifier_bindings() {
+        let config = MouseConfig {
+            wheel: WheelAction::Navigate,
+            ctrl_wheel: WheelAction::Zoom,
+            shift_wheel: WheelAction::Pan,
+            trackpad_pan: true,
+            ..MouseConfig::default()
+        };
+        let line = MouseWheelUnit::Line;
+        let events = [
+            wheel(line, Vec2::new(0.0, 1.0), Modifiers::NONE),
+            wheel(line, Vec2::new(0.0, 2.0), Modifiers::CTRL),
+            wheel(line, Vec2::new(0.0, -1.0), Modifiers::SHIFT),
+            wheel(MouseWheelUnit::Point, Vec2::new(3.0, 4.0), Modifiers::NONE),
+            Event::Zoom(1.5),
+        ];
+        let out = read_wheel_events(&events, &config, 800.0);
+        assert_eq!(out.navigate, LINE_POINTS);
+        assert_eq!(out.zoom_delta, 2.0 * LINE_POINTS);
+        assert_eq!(out.pan, Vec2::new(3.0 - LINE_POINTS, 4.0));
+        assert_eq!(out.zoom_factor, 1.5);
+
+        // 默认不把按点上报的滚动当作触控板平移，高精度滚轮照常缩放
+        let point = [wheel(
+            MouseWheelUnit::Point,
+            Vec2::new(0.0, 5.0),
+            Modifiers::NONE,
+        )];
+        let out = read_wheel_events(&point, &MouseConfig::default(), 800.0);
+        assert_eq!(out.zoom_delta, 5.0);
+        assert_eq!(out.pan, Vec2::ZERO);
+    }
+
+    #[test]
+    fn wheel_navigation_steps_once_per_notch_and_resets_on_reverse() {
+        let mut accum = 0.0;
+        assert_eq!(wheel_navigation(&mut accum, 10.0), 0);
+        assert_eq!(wheel_navigation(&mut accum, -10.0), 0);
+        assert_eq!(accum, -10.0);
+        assert_eq!(wheel_navigation(&mut accum, -LINE_POINTS), 1);
+        assert_eq!(accum, 0.0);
+        assert_eq!(wheel_navigation(&mut accum, LINE_POINTS * 3.0), -1);
+    }
+}
@@ -0,0 +1,58 @@
+use crate::core::hotkey_parser::egui_hotkey_pressed;
+use crate::feature::viewer::viewer_state::ViewerState;
+use crate::i18n::lang::get_i18n_text;
+use crate::model::config::get_context_config;
+use crate::model::session::ViewMode;
+use crate::os::current_platform;
+use crate::ui::widgets::toast::ToastManager;
+use crate::utils::external::launch_external;
+use egui::Context;
+use std::path::PathBuf;
+
+/// 外部程序和文件管理器的作用对象：网格中为选中项，否则为当前图片
+fn target_paths(viewer: &ViewerState) -> Vec<PathBuf> {
+    if viewer.current.unsaved {
+        return Vec::new();
+    }
+    if viewer.view_mode == ViewMode::Grid && !viewer.selection.is_empty() {
+        return viewer.selected_paths();
+    }
+    viewer.current().into_iter().collect()
+}
+
+/// 用配置中的第 `index` 个程序逐个打开目标图片
+pub fn open_with(ctx: &Context, viewer: &ViewerState, index: usize, toast_manager: &ToastManager) {
+    let config = get_context_config(ctx);
+    let Some(app) = config.external_apps.get(index) else {
+        return;
+    };
+    for path in target_paths(viewer) {
+        if let Err(e) = launch_external(app, &path) {
+            tracing::error!("外部程序 {} 启动失败: {}", app.name, e);
+            toast_manager.error(format!("{}: {}", get_i18n_text(ctx).toast.launch_failed, e));
+            return;
+        }
+    }
+}
+
+/// 在文件管理器中显示第一个目标图片
+pub fn reveal_in_file_manager(ctx: &Context, viewer: &ViewerState, toast_manager: &ToastManager) {
+    let Some(path) = target_paths(viewer).into_iter().next() else {
+        return;
+    };
+    if let Err(e) = current_platform().reveal_in_file_manager(&path) {
+        tracing::error!("无法在文件管理器中显示 {}: {}", path.display(), e);
+        toast_manager.error(format!("{}: {}", get_i18n_text(ctx).toast.launch_failed, e));
+    }
+}
+
+/// 返回本帧按下的外部程序快捷键对应的序号
+pub fn pressed_external_app(ctx: &Context) -> Option<usize> {
+    let config = get_context_config(ctx);
+    ctx.input(|i| {
+        config
+            .external_apps
+            .iter()
+            .position(|app| egui_hotkey_pressed(i, &app.hotkey))
+    })
+}
@@ -0,0 +1,150 @@
+use crate::feature::viewer::viewer_state::ViewerState;
+use crate::i18n::lang::TextBundle;
+use crate::model::config::{OverlayCorner, ViewerConfig};
+use crate::model::image_meta::ImageProperties;
+use egui::{Color32, FontId, Rect, Ui, Vec2, pos2};
+
+/// 模板中可用的占位符，设置界面中作为提示列出
+pub const PLACEHOLDERS: &[&str] = &[
+    "name", "index", "total", "width", "height", "zoom", "size", "exposure", "aperture", "iso",
+    "focal", "camera", "date",
+];
+
+const OVERLAY_MARGIN: f32 = 12.0;
+const OVERLAY_PADDING: f32 = 8.0;
+/// 半透明深色底，保证在任何图片上都能看清白字
+const OVERLAY_BACKGROUND: Color32 = Color32::from_rgba_unmultiplied_const(0, 0, 0, 160);
+
+impl OverlayCorner {
+    pub fn label(self, text: &TextBundle) -> &'static str {
+        match self {
+            Self::TopLeft => text.overlay.top_left,
+            Self::TopRight => text.overlay.top_right,
+            Self::BottomLeft => text.overlay.bottom_left,
+            Self::BottomRight => text.overlay.bottom_right,
+        }
+    }
+}
+
+/// 替换模板中的 `{key}`。`value` 对未知占位符返回 None，原样保留；
+/// 一行中的占位符都没有值时（例如图片没有 EXIF）整行省略
+pub fn fill_template(template: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    template
+        .lines()
+        .filter_map(|line| fill_line(line, &value))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn fill_line(line: &str, value: &impl Fn(&str) -> Option<String>) -> Option<String> {
+    let mut out = String::new();
+    let mut rest = line;
+    let mut placeholders = 0;
+    let mut filled = 0;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            out.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+        let key = &after[..end];
+        match value(key) {
+            Some(v) => {
+                placeholders += 1;
+                if !v.is_empty() {
+                    filled += 1;
+                }
+                out.push_str(&v);
+            }
+            None => out.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    (placeholders == 0 || filled > 0).then(|| out.trim_end().to_string())
+}
+
+fn format_file_size(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    let bytes = bytes as f64;
+    if bytes < KB * KB {
+        format!("{:.0} KB", bytes / KB)
+    } else {
+        format!("{:.1} MB", bytes / (KB * KB))
+    }
+}
+
+fn overlay_value(key: &str, viewer: &ViewerState, props: &ImageProperties) -> Option<String> {
+    let value = match key {
+        "name" => props.name.clone(),
+        "index" => (viewer.index + 1).to_string(),
+        "total" => viewer.list.len().to_string(),
+        "width" => props.width.to_string(),
+        "height" => props.height.to_string(),
+        "zoom" => format!("{:.0}%", viewer.zoom * 100.0),
+        "size" if props.size > 0 => format_file_size(props.size),
+        "size" => String::new(),
+        "exposure" => props.exposure_time.clone(),
+        "aperture" => props.f_number.clone(),
+        "iso" if !props.iso.is_empty() => format!("ISO {}", props.iso),
+        "iso" => String::new(),
+        "focal" => props.focal_length.clone(),
+        "camera" => props.camera.clone(),
+        "date" => props.date.clone(),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// 在视图角落按模板绘制当前图片的信息，返回浮层所占区域
+pub fn draw_info_overlay(
+    ui: &Ui,
+    rect: Rect,
+    viewer: &ViewerState,
+    config: &ViewerConfig,
+) -> Option<Rect> {
+    let props = viewer.current.properties.as_ref()?;
+    let content = fill_template(&config.overlay_template, |key| {
+        overlay_value(key, viewer, props)
+    });
+    if content.trim().is_empty() {
+        return None;
+    }
+
+    let painter = ui.painter_at(rect);
+    let galley = painter.layout_no_wrap(content, FontId::proportional(13.0), Color32::WHITE);
+    let size = galley.size() + Vec2::splat(OVERLAY_PADDING * 2.0);
+    let inner = rect.shrink(OVERLAY_MARGIN);
+    let min = match config.overlay_corner {
+        OverlayCorner::TopLeft => inner.left_top(),
+        OverlayCorner::TopRight => pos2(inner.right() - size.x, inner.top()),
+        OverlayCorner::BottomLeft => pos2(inner.left(), inner.bottom() - size.y),
+        OverlayCorner::BottomRight => inner.right_bottom() - size,
+    };
+    let background = Rect::from_min_size(min, size);
+    painter.rect_filled(background, 6.0, OVERLAY_BACKGROUND);
+    painter.galley(
+        background.min + Vec2::splat(OVERLAY_PADDING),
+        galley,
+        Color32::WHITE,
+    );
+    Some(background)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fill_template;
+
+    #[test]
+    fn fill_template_substitutes_and_drops_empty_lines() {
+        let value = |key: &str| match key {
+            "name" => Some("a.jpg".to_string()),
+            "iso" | "exposure" => Some(String::new()),
+            _ => None,
+        };
+        let out = fill_template("{name} {unknown}\nISO {iso} {exposure}\nfixed {", value);
+        assert_eq!(out, "a.jpg {unknown}\nfixed {");
+    }
+}
@@ -1,4 +1,7 @@
-use super::viewer_state::ViewerState;
+use super::background::paint_image_background;
+use super::viewer_state::{THUMB_SIZE, ViewerState};
+use crate::core::load_queue::LoadPriority;
+use crate::model::config::get_context_config;
 use egui::{
     Align2, Area, Color32, Context, CornerRadius, CursorIcon, FontId, Frame, Id, Image, Rect,
     Sense, Shadow, Spinner, Stroke, StrokeKind, TextureHandle, Ui, UiBuilder, Vec2,
@@ -288,7 +291,7 @@ fn queue_thumbnail_loads(
             viewer.thumbs.loading.insert(path.clone());
             viewer
                 .loader
-                .load_async(ctx.clone(), path, false, Some((160, 120)));
+                .load_thumbnail(ctx, path, THUMB_SIZE, LoadPriority::Visible);
         }
     }
 }
@@ -307,7 +310,18 @@ fn render_preview_item_custom(ui: &mut Ui, rect: Rect, state: ThumbnailState, al
                     .add(shadow.as_shape(rect, CornerRadius::same(6)));
             }
 
+            let config = get_context_config(ui.ctx());
+            paint_image_background(
+                ui.painter(),
+                rect,
+                6,
+                &config.viewer,
+                Color32::WHITE.linear_multiply(alpha),
+            );
+
+            // 缩略图保留原始比例，这里居中裁剪铺满卡片
             let image = Image::from_texture(tex)
+                .uv(cover_uv(tex.size_vec2(), rect.size()))
                 .fit_to_exact_size(rect.size())
                 .corner_radius(6.0)
                 .tint(Color32::WHITE.linear_multiply(alpha));
@@ -332,6 +346,17 @@ fn render_preview_item_custom(ui: &mut Ui, rect: Rect, state: ThumbnailState, al
     }
 }
 
+fn cover_uv(tex_size: Vec2, target: Vec2) -> Rect {
+    let tex_aspect = tex_size.x / tex_size.y.max(1.0);
+    let target_aspect = target.x / target.y.max(1.0);
+    let size = if tex_aspect > target_aspect {
+        Vec2::new(target_aspect / tex_aspect, 1.0)
+    } else {
+        Vec2::new(1.0, tex_aspect / target_aspect)
+    };
+    Rect::from_center_size(egui::pos2(0.5, 0.5), size)
+}
+
 fn paint_error_state(ui: &mut Ui, rect: Rect) {
     ui.painter()
         .rect_filled(rect, CornerRadius::same(4), Color32::from_rgb(60, 20, 20));
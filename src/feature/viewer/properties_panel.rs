@@ -3,9 +3,17 @@ use crate::i18n::lang::get_i18n_text;
 use crate::model::image_meta::ImageProperties;
 use crate::model::mode::PanelMode;
 use crate::ui::widgets::icons::{IconType, draw_icon_button};
-use egui::{Align, CursorIcon, Grid, Layout, Panel, RichText, Sense, Ui};
-
-pub fn draw_properties_panel_inside(ui: &mut Ui, panel: &mut PanelMode, viewer: &ViewerState) {
+use crate::ui::widgets::toast::ToastManager;
+use crate::utils::palette::{PaletteFormat, Swatch};
+use egui::{Align, Context, CursorIcon, Grid, Layout, Panel, RichText, Sense, Ui};
+use rfd::FileDialog;
+
+pub fn draw_properties_panel_inside(
+    ui: &mut Ui,
+    panel: &mut PanelMode,
+    viewer: &mut ViewerState,
+    toast_manager: &ToastManager,
+) {
     let mut is_open = matches!(panel, PanelMode::Properties);
     if !is_open {
         return;
@@ -32,8 +40,11 @@ pub fn draw_properties_panel_inside(ui: &mut Ui, panel: &mut PanelMode, viewer:
             });
             ui.separator();
 
+            let palette = viewer.palette();
             if let Some(props) = &viewer.current.properties {
                 render_properties_content(ui, props);
+                ui.add_space(10.0);
+                render_palette(ui, &palette, &props.name, toast_manager);
             } else {
                 ui.label(text.properties.no_image);
             }
@@ -74,6 +85,21 @@ fn render_properties_content(ui: &mut Ui, properties: &ImageProperties) {
             ui.end_row();
         });
 
+    if properties.has_location {
+        ui.add_space(6.0);
+        ui.colored_label(ui.visuals().warn_fg_color, text.properties.has_location);
+    }
+
+    if let Some(format) = &properties.extension_mismatch {
+        ui.add_space(6.0);
+        ui.colored_label(
+            ui.visuals().warn_fg_color,
+            text.properties
+                .extension_mismatch
+                .replace("{format}", format),
+        );
+    }
+
     ui.add_space(10.0);
 
     // 侧边栏路径
@@ -113,3 +139,111 @@ fn render_properties_content(ui: &mut Ui, properties: &ImageProperties) {
         }
     });
 }
+
+/// 主色色板，点击色块或数值复制，可导出为调色板文件
+fn render_palette(
+    ui: &mut Ui,
+    swatches: &[Swatch],
+    image_name: &str,
+    toast_manager: &ToastManager,
+) {
+    let text = get_i18n_text(ui);
+
+    ui.horizontal(|ui| {
+        ui.label(format!("{}:", text.palette.title));
+        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            ui.add_enabled_ui(!swatches.is_empty(), |ui| {
+                ui.menu_button(text.palette.export, |ui| {
+                    for format in PaletteFormat::ALL {
+                        if ui.button(format.label()).clicked() {
+                            export_palette(
+                                ui.ctx(),
+                                swatches.to_vec(),
+                                format,
+                                image_name,
+                                toast_manager,
+                            );
+                            ui.close();
+                        }
+                    }
+                });
+            });
+        });
+    });
+    if swatches.is_empty() {
+        ui.label(RichText::new(text.palette.empty).weak());
+        return;
+    }
+
+    let mut copied = None;
+    Grid::new("palette_grid")
+        .num_columns(4)
+        .spacing([10.0, 4.0])
+        .show(ui, |ui| {
+            for swatch in swatches {
+                let hex = swatch.hex();
+                let [r, g, b] = swatch.rgb;
+                let rgb = format!("{r}, {g}, {b}");
+
+                let (rect, response) =
+                    ui.allocate_exact_size(egui::vec2(36.0, 18.0), Sense::click());
+                ui.painter().rect_filled(rect, 3.0, swatch.color());
+                if response.on_hover_text(text.palette.copy_hint).clicked() {
+                    copied = Some(hex.clone());
+                }
+                for value in [hex, rgb] {
+                    let label =
+                        egui::Label::new(RichText::new(&value).monospace()).sense(Sense::click());
+                    if ui
+                        .add(label)
+                        .on_hover_text(text.palette.copy_hint)
+                        .clicked()
+                    {
+                        copied = Some(value);
+                    }
+                }
+                ui.label(RichText::new(format!("{:.1}%", swatch.share * 100.0)).weak());
+                ui.end_row();
+            }
+        });
+
+    if let Some(value) = copied {
+        ui.copy_text(value);
+        toast_manager.success(text.toast.copied);
+    }
+}
+
+fn export_palette(
+    ctx: &Context,
+    swatches: Vec<Swatch>,
+    format: PaletteFormat,
+    image_name: &str,
+    toast_manager: &ToastManager,
+) {
+    let text = get_i18n_text(ctx);
+    let saved = text.toast.saved;
+    let save_failed = text.toast.save_failed;
+    let toast = toast_manager.clone();
+    let name = std::path::Path::new(image_name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| image_name.to_string());
+
+    std::thread::spawn(move || {
+        let extension = format.extension();
+        let Some(target) = FileDialog::new()
+            .add_filter(format.label(), &[extension])
+            .set_file_name(format!("{name}.{extension}"))
+            .save_file()
+        else {
+            return;
+        };
+        match std::fs::write(&target, format.encode(&swatches, &name)) {
+            Ok(()) => toast.success(saved.replace("{path}", &target.display().to_string())),
+            Err(e) => {
+                tracing::error!("导出调色板失败 {}: {}", target.display(), e);
+                toast.error(format!("{}: {}", save_failed, e));
+            }
+        }
+    });
+}
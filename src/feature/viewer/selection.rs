@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// 网格多选状态，以路径记录选中项，筛选或删除后索引变化也不会选错
+#[derive(Default)]
+pub struct Selection {
+    items: HashSet<PathBuf>,
+    /// shift 范围选择的起点（列表索引）
+    anchor: Option<usize>,
+    /// 框选开始时已有的选中项，按住 ctrl 框选时在此基础上追加
+    band_base: Option<HashSet<PathBuf>>,
+}
+
+impl Selection {
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.items.contains(path)
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.anchor = None;
+        self.band_base = None;
+    }
+
+    /// 普通单击：只选中这一项
+    pub fn select_only(&mut self, list: &[PathBuf], index: usize) {
+        self.items.clear();
+        if let Some(path) = list.get(index) {
+            self.items.insert(path.clone());
+            self.anchor = Some(index);
+        }
+    }
+
+    /// ctrl + 单击：切换这一项
+    pub fn toggle(&mut self, list: &[PathBuf], index: usize) {
+        if let Some(path) = list.get(index) {
+            if !self.items.remove(path) {
+                self.items.insert(path.clone());
+            }
+            self.anchor = Some(index);
+        }
+    }
+
+    /// shift + 单击：选中锚点到 index 的连续范围，`additive` 为真时保留原有选中项
+    pub fn select_range(&mut self, list: &[PathBuf], index: usize, additive: bool) {
+        let anchor = self
+            .anchor
+            .unwrap_or(index)
+            .min(list.len().saturating_sub(1));
+        let (start, end) = if anchor <= index {
+            (anchor, index)
+        } else {
+            (index, anchor)
+        };
+        if !additive {
+            self.items.clear();
+        }
+        if let Some(range) = list.get(start..=end) {
+            self.items.extend(range.iter().cloned());
+        }
+        self.anchor = Some(anchor);
+    }
+
+    pub fn select_all(&mut self, list: &[PathBuf]) {
+        self.items = list.iter().cloned().collect();
+    }
+
+    pub fn begin_band(&mut self, additive: bool) {
+        let base = if additive {
+            self.items.clone()
+        } else {
+            HashSet::new()
+        };
+        self.band_base = Some(base);
+    }
+
+    /// 框选过程中每帧调用，用框内的项替换上一帧的结果
+    pub fn update_band(&mut self, list: &[PathBuf], hit: impl IntoIterator<Item = usize>) {
+        let Some(base) = &self.band_base else {
+            return;
+        };
+        self.items = base.clone();
+        let mut last = None;
+        for index in hit {
+            if let Some(path) = list.get(index) {
+                self.items.insert(path.clone());
+                last = Some(index);
+            }
+        }
+        if last.is_some() {
+            self.anchor = last;
+        }
+    }
+
+    pub fn end_band(&mut self) {
+        self.band_base = None;
+    }
+
+    pub fn is_banding(&self) -> bool {
+        self.band_base.is_some()
+    }
+
+    /// 列表变化后丢弃已不存在的项
+    pub fn retain_in(&mut self, list: &[PathBuf]) {
+        let present: HashSet<&PathBuf> = list.iter().collect();
+        self.items.retain(|path| present.contains(path));
+        if self.anchor.is_some_and(|anchor| anchor >= list.len()) {
+            self.anchor = None;
+        }
+    }
+
+    /// 按列表顺序返回选中的路径
+    pub fn paths_in_order(&self, list: &[PathBuf]) -> Vec<PathBuf> {
+        list.iter()
+            .filter(|path| self.items.contains(*path))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Selection;
+    use std::path::PathBuf;
+
+    fn list(n: usize) -> Vec<PathBuf> {
+        (0..n).map(|i| PathBuf::from(format!("{i}.png"))).collect()
+    }
+
+    #[test]
+    fn shift_range_extends_from_anchor_in_both_directions() {
+        let list = list(6);
+        let mut selection = Selection::default();
+        selection.select_only(&list, 3);
+        selection.select_range(&list, 1, false);
+        assert_eq!(selection.paths_in_order(&list), list[1..=3].to_vec());
+
+        selection.select_range(&list, 5, false);
+        assert_eq!(selection.paths_in_order(&list), list[3..=5].to_vec());
+    }
+
+    #[test]
+    fn ctrl_toggle_and_additive_range_keep_existing_items() {
+        let list = list(6);
+        let mut selection = Selection::default();
+        selection.select_only(&list, 0);
+        selection.toggle(&list, 4);
+        selection.select_range(&list, 5, true);
+        assert_eq!(
+            selection.paths_in_order(&list),
+            vec![list[0].clone(), list[4].clone(), list[5].clone()]
+        );
+
+        selection.toggle(&list, 0);
+        assert_eq!(selection.len(), 2);
+    }
+
+    #[test]
+    fn band_replaces_previous_frame_but_keeps_base() {
+        let list = list(6);
+        let mut selection = Selection::default();
+        selection.select_only(&list, 5);
+        selection.begin_band(true);
+        selection.update_band(&list, [0, 1, 2]);
+        selection.update_band(&list, [1]);
+        selection.end_band();
+        assert_eq!(
+            selection.paths_in_order(&list),
+            vec![list[1].clone(), list[5].clone()]
+        );
+    }
+
+    #[test]
+    fn retain_in_drops_missing_paths() {
+        let all = list(4);
+        let mut selection = Selection::default();
+        selection.select_all(&all);
+        selection.retain_in(&all[..2]);
+        assert_eq!(selection.paths_in_order(&all), all[..2].to_vec());
+    }
+}
@@ -0,0 +1,404 @@
+use crate::core::image_loader::ImageLoader;
+use crate::core::resize;
+use crate::i18n::lang::get_i18n_text;
+use crate::model::session::{SequenceFormat, SequenceOptions};
+use crate::ui::widgets::modal::{ModalAction, ModalFrame};
+use crate::ui::widgets::toast::ToastManager;
+use crate::utils::animation::{encode_animated_webp, encode_gif};
+use crate::utils::image::unmultiplied_rgba;
+use egui::{
+    Align2, Area, Color32, ColorImage, Context, Frame, Id, Key, Order, Rect, RichText, Slider,
+    TextureHandle, TextureOptions, Ui, Vec2,
+};
+use image::{RgbaImage, imageops};
+use rayon::prelude::*;
+use rfd::FileDialog;
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::time::Duration;
+
+pub const DELAY_RANGE: std::ops::RangeInclusive<u32> = 10..=2000;
+pub const PLAYS_RANGE: std::ops::RangeInclusive<u32> = 0..=100;
+pub const SCALE_RANGE: std::ops::RangeInclusive<u32> = 10..=100;
+pub const QUALITY_RANGE: std::ops::RangeInclusive<u32> = 1..=30;
+/// 预览帧的最大边长，只影响播放预览，不影响导出
+const PREVIEW_MAX_SIDE: u32 = 1024;
+/// 导出时每批并行解码的帧数
+const DECODE_BATCH: usize = 8;
+
+impl SequenceFormat {
+    pub const ALL: [Self; 2] = [Self::Gif, Self::Webp];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Gif => "GIF",
+            Self::Webp => "WebP",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Gif => "gif",
+            Self::Webp => "webp",
+        }
+    }
+}
+
+impl SequenceOptions {
+    /// GIF 编码器的速度参数与质量相反
+    fn gif_speed(&self) -> i32 {
+        31 - self.quality.clamp(1, 30) as i32
+    }
+}
+
+/// 动图所有帧共用第一帧缩放后的尺寸
+pub fn frame_size(first: (u32, u32), scale: u32, max_side: Option<u32>) -> (u32, u32) {
+    let scale = scale.clamp(1, 100) as u64;
+    let scaled = |v: u32| ((v as u64 * scale / 100).max(1)) as u32;
+    let (w, h) = (scaled(first.0), scaled(first.1));
+    match max_side {
+        Some(max) if w.max(h) > max => resize::fit_within(w, h, max, max),
+        _ => (w, h),
+    }
+}
+
+/// 缩放到帧尺寸以内并居中，尺寸不同的帧四周留透明
+fn fit_frame(image: RgbaImage, size: (u32, u32)) -> RgbaImage {
+    if image.dimensions() == size {
+        return image;
+    }
+    let (w, h) = resize::fit_within(image.width(), image.height(), size.0, size.1);
+    let scaled = imageops::resize(&image, w, h, imageops::FilterType::Triangle);
+    if (w, h) == size {
+        return scaled;
+    }
+    let mut canvas = RgbaImage::new(size.0, size.1);
+    let x = (size.0 - w) / 2;
+    let y = (size.1 - h) / 2;
+    imageops::overlay(&mut canvas, &scaled, x as i64, y as i64);
+    canvas
+}
+
+fn decode_rgba(path: &PathBuf, size: Option<(u32, u32)>) -> Result<RgbaImage, String> {
+    let (color_image, _) = ImageLoader::decode_image(path, size)
+        .map_err(|e| format!("{}: {:?}", path.display(), e))?;
+    unmultiplied_rgba(&color_image.pixels, color_image.size)
+        .ok_or_else(|| "invalid image size".to_string())
+}
+
+/// 按顺序惰性解码帧。第一帧决定尺寸，其余帧直接按该尺寸解码缩略图；
+/// 每次并行解码 `DECODE_BATCH` 帧，取走一批才解码下一批，导出长序列时内存中只有一批原始帧
+fn frame_stream(
+    paths: &[PathBuf],
+    scale: u32,
+    max_side: Option<u32>,
+) -> Result<impl Iterator<Item = Result<RgbaImage, String>> + '_, String> {
+    let first_path = paths.first().ok_or("no frames")?;
+    let first = decode_rgba(first_path, None)?;
+    let size = frame_size(first.dimensions(), scale, max_side);
+    let rest = paths[1..].chunks(DECODE_BATCH).flat_map(move |batch| {
+        batch
+            .par_iter()
+            .map(|path| decode_rgba(path, Some(size)).map(|frame| fit_frame(frame, size)))
+            .collect::<Vec<_>>()
+    });
+    Ok(std::iter::once(Ok(fit_frame(first, size))).chain(rest))
+}
+
+/// 一次解码全部帧，只用于尺寸受 `PREVIEW_MAX_SIDE` 限制的预览
+fn decode_frames(
+    paths: &[PathBuf],
+    scale: u32,
+    max_side: Option<u32>,
+) -> Result<Vec<RgbaImage>, String> {
+    frame_stream(paths, scale, max_side)?.collect()
+}
+
+fn encode(
+    frames: impl Iterator<Item = Result<RgbaImage, String>>,
+    options: &SequenceOptions,
+) -> Result<Vec<u8>, String> {
+    match options.format {
+        SequenceFormat::Gif => {
+            encode_gif(frames, options.delay_ms, options.plays, options.gif_speed())
+        }
+        SequenceFormat::Webp => encode_animated_webp(frames, options.delay_ms, options.plays),
+    }
+}
+
+/// 选择保存位置后在后台解码并编码动图
+pub fn export_sequence(
+    ctx: &Context,
+    paths: Vec<PathBuf>,
+    options: SequenceOptions,
+    toast_manager: &ToastManager,
+) {
+    if paths.len() < 2 {
+        return;
+    }
+    let text = get_i18n_text(ctx);
+    let processing = text.batch.processing;
+    let saved = text.toast.saved;
+    let save_failed = text.toast.save_failed;
+    let toast = toast_manager.clone();
+
+    std::thread::spawn(move || {
+        let extension = options.format.extension();
+        let Some(target) = FileDialog::new()
+            .add_filter(options.format.label(), &[extension])
+            .set_file_name(format!("animation.{extension}"))
+            .save_file()
+        else {
+            return;
+        };
+        toast.loading(processing);
+        let result = frame_stream(&paths, options.scale, None)
+            .and_then(|frames| encode(frames, &options))
+            .and_then(|bytes| std::fs::write(&target, bytes).map_err(|e| e.to_string()));
+        match result {
+            Ok(()) => toast.success(saved.replace("{path}", &target.display().to_string())),
+            Err(e) => {
+                tracing::error!("导出动图失败 {}: {}", target.display(), e);
+                toast.error(format!("{}: {}", save_failed, e));
+            }
+        }
+    });
+}
+
+/// 正在播放的序列预览
+pub struct SequencePreview {
+    pub paths: Vec<PathBuf>,
+    frames: Vec<TextureHandle>,
+    /// 开始播放的时间，按 `ctx` 的输入时间计算当前帧
+    started: f64,
+}
+
+type PreviewResult = Result<(Vec<PathBuf>, Vec<ColorImage>), String>;
+
+/// 预览帧在后台线程解码，结果通过通道回传给 UI 线程创建纹理
+pub struct SequenceState {
+    tx: Sender<PreviewResult>,
+    rx: Receiver<PreviewResult>,
+    pub preview: Option<SequencePreview>,
+}
+
+impl SequenceState {
+    pub fn new() -> Self {
+        let (tx, rx) = channel();
+        Self {
+            tx,
+            rx,
+            preview: None,
+        }
+    }
+
+    pub fn start_preview(
+        &self,
+        ctx: &Context,
+        paths: Vec<PathBuf>,
+        scale: u32,
+        toast_manager: &ToastManager,
+    ) {
+        if paths.len() < 2 {
+            return;
+        }
+        toast_manager.loading(get_i18n_text(ctx).batch.processing);
+        let tx = self.tx.clone();
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            let result = decode_frames(&paths, scale, Some(PREVIEW_MAX_SIDE)).map(|frames| {
+                let images = frames
+                    .iter()
+                    .map(|frame| {
+                        ColorImage::from_rgba_unmultiplied(
+                            [frame.width() as usize, frame.height() as usize],
+                            frame.as_raw(),
+                        )
+                    })
+                    .collect();
+                (paths, images)
+            });
+            let _ = tx.send(result);
+            ctx.request_repaint();
+        });
+    }
+
+    /// 取回解码好的预览帧并开始播放，返回解码结果供调用方提示
+    pub fn poll(&mut self, ctx: &Context) -> Option<Result<(), String>> {
+        let result = self.rx.try_recv().ok()?;
+        Some(result.map(|(paths, images)| {
+            let frames = images
+                .into_iter()
+                .enumerate()
+                .map(|(index, image)| {
+                    ctx.load_texture(
+                        format!("sequence_preview_{index}"),
+                        image,
+                        TextureOptions::LINEAR,
+                    )
+                })
+                .collect();
+            self.preview = Some(SequencePreview {
+                paths,
+                frames,
+                started: ctx.input(|i| i.time),
+            });
+        }))
+    }
+}
+
+impl Default for SequenceState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 预览播放条上的操作
+pub enum PreviewAction {
+    Export,
+    Close,
+}
+
+/// 在中央区域循环播放预览帧，帧间隔随选项实时变化
+pub fn draw_sequence_preview(
+    ui: &mut Ui,
+    preview: &SequencePreview,
+    options: &SequenceOptions,
+) -> Option<PreviewAction> {
+    let ctx = ui.ctx().clone();
+    let text = get_i18n_text(&ctx);
+    let rect = ui.available_rect_before_wrap();
+    let count = preview.frames.len().max(1);
+    let delay = options.delay_ms.max(1) as f64 / 1000.0;
+    let elapsed = ctx.input(|i| i.time) - preview.started;
+    let index = (elapsed / delay) as usize % count;
+
+    if let Some(texture) = preview.frames.get(index) {
+        let size = texture.size_vec2();
+        let fit = (rect.width() / size.x).min(rect.height() / size.y).min(1.0);
+        let image_rect = Rect::from_center_size(rect.center(), size * fit);
+        ui.painter().image(
+            texture.id(),
+            image_rect,
+            Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            Color32::WHITE,
+        );
+    }
+    ctx.request_repaint_after(Duration::from_millis(options.delay_ms.max(1) as u64));
+
+    let mut action = None;
+    if ctx.input(|i| i.key_pressed(Key::Escape)) {
+        action = Some(PreviewAction::Close);
+    }
+    Area::new(Id::new("sequence_preview_bar"))
+        .order(Order::Foreground)
+        .anchor(Align2::CENTER_BOTTOM, Vec2::new(0.0, -56.0))
+        .show(&ctx, |ui| {
+            Frame::popup(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(format!("{} / {}", index + 1, count)).monospace());
+                    ui.separator();
+                    if ui.button(text.sequence.export).clicked() {
+                        action = Some(PreviewAction::Export);
+                    }
+                    if ui.button(text.sequence.stop_preview).clicked() {
+                        action = Some(PreviewAction::Close);
+                    }
+                });
+            });
+        });
+    action
+}
+
+/// 动图选项窗口的操作
+#[derive(PartialEq)]
+pub enum SequenceDialogAction {
+    None,
+    Preview,
+    Export,
+}
+
+/// 动图导出选项窗口
+pub fn render_sequence_window(
+    ctx: &Context,
+    open: &mut bool,
+    options: &mut SequenceOptions,
+    count: usize,
+) -> SequenceDialogAction {
+    let text = get_i18n_text(ctx);
+    let mut result = SequenceDialogAction::None;
+    ModalFrame::show(ctx, open, text.sequence.title, |ui| {
+        ui.label(text.sequence.count.replace("{count}", &count.to_string()));
+        ui.add_space(8.0);
+        egui::Grid::new("sequence_options")
+            .num_columns(2)
+            .spacing([16.0, 8.0])
+            .show(ui, |ui| {
+                ui.label(text.sequence.format);
+                ui.horizontal(|ui| {
+                    for format in SequenceFormat::ALL {
+                        ui.selectable_value(&mut options.format, format, format.label());
+                    }
+                });
+                ui.end_row();
+
+                ui.label(text.sequence.delay);
+                ui.add(Slider::new(&mut options.delay_ms, DELAY_RANGE).suffix(" ms"));
+                ui.end_row();
+
+                ui.label(text.sequence.plays);
+                ui.add(Slider::new(&mut options.plays, PLAYS_RANGE))
+                    .on_hover_text(text.sequence.plays_hint);
+                ui.end_row();
+
+                ui.label(text.sequence.scale);
+                ui.add(Slider::new(&mut options.scale, SCALE_RANGE).suffix("%"));
+                ui.end_row();
+
+                ui.label(text.sequence.quality);
+                ui.add_enabled(
+                    options.format == SequenceFormat::Gif,
+                    Slider::new(&mut options.quality, QUALITY_RANGE),
+                );
+                ui.end_row();
+            });
+
+        ui.add_space(12.0);
+        let mut action = ModalAction::None;
+        ui.horizontal(|ui| {
+            if ui.button(text.sequence.preview).clicked() {
+                result = SequenceDialogAction::Preview;
+                action = ModalAction::Apply;
+            }
+            if ui.button(text.sequence.export).clicked() {
+                result = SequenceDialogAction::Export;
+                action = ModalAction::Apply;
+            }
+            if ui.button(text.sequence.cancel).clicked() {
+                action = ModalAction::Close;
+            }
+        });
+        action
+    });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fit_frame, frame_size};
+    use image::{Rgba, RgbaImage};
+
+    #[test]
+    fn frames_share_first_frame_size() {
+        assert_eq!(frame_size((1920, 1080), 50, None), (960, 540));
+        assert_eq!(frame_size((4000, 2000), 100, Some(1024)), (1024, 512));
+        assert_eq!(frame_size((3, 3), 10, None), (1, 1));
+
+        let tall = RgbaImage::from_pixel(10, 40, Rgba([255, 0, 0, 255]));
+        let fitted = fit_frame(tall, (20, 20));
+        assert_eq!(fitted.dimensions(), (20, 20));
+        assert_eq!(fitted.get_pixel(0, 10)[3], 0);
+        assert_eq!(fitted.get_pixel(10, 10), &Rgba([255, 0, 0, 255]));
+    }
+}
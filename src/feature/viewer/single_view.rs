@@ -1,10 +1,19 @@
 use eframe::egui;
-use egui::{Color32, Context, CursorIcon, Rect, RichText, Spinner, TextureHandle, Ui, UiBuilder};
+use egui::{
+    Color32, Context, CursorIcon, FontId, Id, Painter, Rect, RichText, Sense, Spinner, Stroke,
+    TextureHandle, Ui, UiBuilder,
+};
 
+use crate::core::keymap::Action;
 use crate::feature::viewer::arrows::{Nav, draw_arrows};
+use crate::feature::viewer::background::paint_image_background;
+use crate::feature::viewer::channels::draw_channel_indicator;
+use crate::feature::viewer::measure::{ImageTransform, draw_measure_overlay};
+use crate::feature::viewer::overlay::draw_info_overlay;
 use crate::feature::viewer::preview::show_preview_window;
 use crate::feature::viewer::viewer_state::{TransitionPhase, ViewerState};
 use crate::i18n::lang::get_i18n_text;
+use crate::model::config::get_context_config;
 use crate::model::mode::PopupMode;
 
 pub fn draw_single_view(
@@ -17,9 +26,13 @@ pub fn draw_single_view(
     let text = get_i18n_text(ctx);
 
     let current_texture = viewer.current.texture.clone();
+    let display_texture = viewer
+        .channel_texture(ctx)
+        .or_else(|| current_texture.clone());
     let is_transitioning = viewer.transition.phase != TransitionPhase::None;
 
-    let is_draggable = render_image_viewer(ui, rect, current_texture.as_ref(), viewer);
+    viewer.measure.transform = None;
+    let is_draggable = render_image_viewer(ui, rect, display_texture.as_ref(), viewer);
 
     // 设置光标：仅当指针在中央区域、图片可拖拽、且不在箭头区域时显示 Move
     let pointer_pos = ui.input(|i| i.pointer.hover_pos());
@@ -34,6 +47,9 @@ pub fn draw_single_view(
     } else {
         ui.set_cursor_icon(CursorIcon::Default);
     }
+    if !has_popup {
+        draw_measure_overlay(ui, rect, &mut viewer.measure, text);
+    }
 
     if !is_transitioning
         && ui.input(|i| i.pointer.secondary_clicked())
@@ -68,7 +84,7 @@ pub fn draw_single_view(
                     );
                     ui.add_space(8.0);
                     ui.label(
-                        RichText::new(err.to_string())
+                        RichText::new(err.localized(text))
                             .color(Color32::GRAY)
                             .size(12.0),
                     );
@@ -87,6 +103,40 @@ pub fn draw_single_view(
         }
     }
 
+    let current_truncated = viewer.current().is_some_and(|path| {
+        viewer
+            .current
+            .properties
+            .as_ref()
+            .is_some_and(|p| p.truncated && p.path == path)
+    });
+    if current_texture.is_some() && viewer.current.unsaved {
+        let save_key = get_context_config(ui.ctx())
+            .keymap
+            .binding(Action::SaveAs)
+            .to_string();
+        let message = text.viewer.unsaved.replace("{key}", &save_key);
+        draw_banner(ui, rect, &message, UNSAVED_BANNER);
+    } else if current_texture.is_some() && current_truncated {
+        draw_banner(ui, rect, text.viewer.truncated, TRUNCATED_BANNER);
+    }
+
+    // 菜单和弹窗位于更高的图层，被挡住时 contains_pointer 为 false
+    viewer.view_hovered = ui
+        .interact(rect, Id::new("single_view_hover"), Sense::hover())
+        .contains_pointer();
+    if current_texture.is_some() && viewer.session.show_info_overlay {
+        let config = get_context_config(ui.ctx());
+        let overlay = draw_info_overlay(ui, rect, viewer, &config.viewer);
+        let pointer = ui.input(|i| i.pointer.hover_pos());
+        if overlay.is_some_and(|overlay| pointer.is_some_and(|pos| overlay.contains(pos))) {
+            viewer.view_hovered = false;
+        }
+    }
+    if current_texture.is_some() {
+        draw_channel_indicator(ui, rect, viewer.channel_view, text);
+    }
+
     if viewer.current().is_some()
         && let Some(action) = draw_arrows(ui, rect)
     {
@@ -186,7 +236,7 @@ fn render_normal_image(ui: &mut Ui, tex: &TextureHandle, viewer: &mut ViewerStat
 
     let is_loading_high_res = viewer.loader.is_loading;
     let zoom = viewer.zoom.max(0.01);
-    let img_size = tex.size_vec2() * zoom;
+    let img_size = viewer.display_size(tex) * zoom;
     let viewport = ui.max_rect();
 
     let is_draggable = img_size.x > viewport.width() || img_size.y > viewport.height();
@@ -203,6 +253,10 @@ fn render_normal_image(ui: &mut Ui, tex: &TextureHandle, viewer: &mut ViewerStat
     let panel_shift = egui::vec2(-viewer.panel_animation * 150.0, 0.0);
     let img_origin = viewport.min + viewer.viewport_offset + panel_shift;
     let img_rect = Rect::from_min_size(img_origin, img_size);
+    viewer.measure.transform = Some(ImageTransform {
+        origin: img_origin,
+        zoom: img_size.x / viewer.display_size(tex).x.max(1.0),
+    });
 
     // Handle drag
     let sense = if is_draggable {
@@ -216,6 +270,9 @@ fn render_normal_image(ui: &mut Ui, tex: &TextureHandle, viewer: &mut ViewerStat
         viewer.viewport_offset += response.drag_delta();
     }
 
+    let config = get_context_config(ui.ctx());
+    paint_image_background(ui.painter(), img_rect, 0, &config.viewer, Color32::WHITE);
+
     // Draw the image
     ui.painter().image(
         tex.id(),
@@ -224,6 +281,10 @@ fn render_normal_image(ui: &mut Ui, tex: &TextureHandle, viewer: &mut ViewerStat
         Color32::WHITE,
     );
 
+    if config.viewer.pixel_grid && zoom >= config.viewer.pixel_grid_zoom {
+        draw_pixel_grid(&ui.painter_at(viewport), img_rect, zoom);
+    }
+
     // Loading overlay
     let fade_alpha = ui.animate_bool_with_time(
         egui::Id::new(tex.id()).with("loading_fade"),
@@ -249,3 +310,49 @@ fn render_normal_image(ui: &mut Ui, tex: &TextureHandle, viewer: &mut ViewerStat
 
     is_draggable
 }
+
+const TRUNCATED_BANNER: Color32 = Color32::from_rgba_unmultiplied_const(190, 110, 0, 220);
+const UNSAVED_BANNER: Color32 = Color32::from_rgba_unmultiplied_const(40, 100, 170, 220);
+
+/// 在视图顶部显示提示条，例如图片不完整或尚未保存
+fn draw_banner(ui: &Ui, rect: Rect, message: &str, fill: Color32) {
+    let painter = ui.painter_at(rect);
+    let galley = painter.layout_no_wrap(
+        message.to_string(),
+        FontId::proportional(13.0),
+        Color32::WHITE,
+    );
+    let banner = Rect::from_center_size(
+        egui::pos2(rect.center().x, rect.top() + 24.0),
+        galley.size() + egui::vec2(24.0, 10.0),
+    );
+    painter.rect_filled(banner, 4.0, fill);
+    painter.galley(
+        banner.center() - galley.size() / 2.0,
+        galley,
+        Color32::WHITE,
+    );
+}
+
+/// 在每个像素边界画线，只绘制视口内可见的部分
+fn draw_pixel_grid(painter: &Painter, img_rect: Rect, zoom: f32) {
+    let visible = img_rect.intersect(painter.clip_rect());
+    if !visible.is_positive() {
+        return;
+    }
+    let stroke = Stroke::new(1.0, Color32::from_rgba_unmultiplied(128, 128, 128, 90));
+
+    let first_col = ((visible.min.x - img_rect.min.x) / zoom).ceil() as i32;
+    let last_col = ((visible.max.x - img_rect.min.x) / zoom).floor() as i32;
+    for col in first_col..=last_col {
+        let x = img_rect.min.x + col as f32 * zoom;
+        painter.vline(x, visible.y_range(), stroke);
+    }
+
+    let first_row = ((visible.min.y - img_rect.min.y) / zoom).ceil() as i32;
+    let last_row = ((visible.max.y - img_rect.min.y) / zoom).floor() as i32;
+    for row in first_row..=last_row {
+        let y = img_rect.min.y + row as f32 * zoom;
+        painter.hline(visible.x_range(), y, stroke);
+    }
+}
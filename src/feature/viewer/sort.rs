@@ -0,0 +1,43 @@
+use crate::i18n::lang::TextBundle;
+use crate::model::session::SortOrder;
+use rayon::prelude::*;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+impl SortOrder {
+    pub const ALL: [Self; 3] = [Self::Name, Self::Modified, Self::Size];
+
+    pub fn label(self, text: &TextBundle) -> &'static str {
+        match self {
+            Self::Name => text.grid.sort_name,
+            Self::Modified => text.grid.sort_modified,
+            Self::Size => text.grid.sort_size,
+        }
+    }
+}
+
+/// 按指定顺序排列图片，取不到元数据的文件排在最后，同值时按文件名
+pub fn sort_images(images: &mut Vec<PathBuf>, order: SortOrder) {
+    if order == SortOrder::Name {
+        images.sort();
+        return;
+    }
+    // 并行读取元数据，大文件夹里逐个 stat 会明显卡顿
+    let mut keyed: Vec<(Option<(SystemTime, u64)>, PathBuf)> = std::mem::take(images)
+        .into_par_iter()
+        .map(|path| {
+            let key = std::fs::metadata(&path)
+                .ok()
+                .map(|m| (m.modified().unwrap_or(SystemTime::UNIX_EPOCH), m.len()));
+            (key, path)
+        })
+        .collect();
+    keyed.sort_by(|(a, path_a), (b, path_b)| {
+        let by_key = match order {
+            SortOrder::Modified => b.map(|k| k.0).cmp(&a.map(|k| k.0)),
+            _ => b.map(|k| k.1).cmp(&a.map(|k| k.1)),
+        };
+        by_key.then_with(|| path_a.cmp(path_b))
+    });
+    images.extend(keyed.into_iter().map(|(_, path)| path));
+}
@@ -0,0 +1,66 @@
+use crate::feature::viewer::batch::unique_destination;
+use crate::feature::viewer::viewer_state::ViewerState;
+use crate::i18n::lang::get_i18n_text;
+use crate::ui::widgets::toast::ToastManager;
+use crate::utils::image::unmultiplied_rgba;
+use crate::utils::time::format_file_timestamp;
+use egui::Context;
+use rfd::FileDialog;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// 粘贴进来的图片使用的文件名，保存时如有重名再追加序号
+pub fn generated_name(time: SystemTime) -> String {
+    let stamp = format_file_timestamp(time).unwrap_or_default();
+    format!("pasted_{stamp}.png")
+}
+
+/// 把未保存的当前图片另存为 PNG：选择文件夹后在后台写入，取消选择则什么都不做
+/// 只写入像素，粘贴来的图片不带元数据，无需按元数据策略处理
+pub fn save_unsaved_as(ctx: &Context, viewer: &ViewerState, toast_manager: &ToastManager) {
+    if !viewer.current.unsaved {
+        return;
+    }
+    let (Some(texture), Some(pixels), Some(properties)) = (
+        viewer.current.texture.as_ref(),
+        viewer.current.raw_pixels.clone(),
+        viewer.current.properties.as_ref(),
+    ) else {
+        return;
+    };
+    let size = texture.size();
+    let name = properties.name.clone();
+    let text = get_i18n_text(ctx);
+    let saved = text.toast.saved;
+    let save_failed = text.toast.save_failed;
+    let toast = toast_manager.clone();
+
+    std::thread::spawn(move || {
+        let Some(dir) = FileDialog::new().pick_folder() else {
+            return;
+        };
+        let target = unique_destination(&dir, Path::new(&name));
+        let result = unmultiplied_rgba(&pixels, size)
+            .ok_or_else(|| "invalid image size".to_string())
+            .and_then(|img| img.save(&target).map_err(|e| e.to_string()));
+        match result {
+            Ok(()) => toast.success(saved.replace("{path}", &target.display().to_string())),
+            Err(e) => {
+                tracing::error!("保存粘贴的图片失败 {}: {}", target.display(), e);
+                toast.error(format!("{}: {}", save_failed, e));
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generated_name;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn generated_name_uses_timestamp() {
+        let time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        assert_eq!(generated_name(time), "pasted_20231114_221320.png");
+    }
+}
@@ -1,18 +1,34 @@
-use crate::core::image_loader::{ImageLoadError, ImageLoader, LoadResult};
+use crate::core::byte_cache::ByteLruCache;
+use crate::core::image_loader::{ImageLoadError, ImageLoader, LoadResult, LoadSuccess};
+use crate::core::load_queue::LoadPriority;
+use crate::feature::viewer::batch::BatchRunner;
+use crate::feature::viewer::channels::ChannelView;
+use crate::feature::viewer::filter::FilterState;
+use crate::feature::viewer::join::JoinRunner;
+use crate::feature::viewer::measure::MeasureState;
+use crate::feature::viewer::selection::Selection;
+use crate::feature::viewer::sequence::SequenceState;
+use crate::feature::viewer::sort::sort_images;
+use crate::feature::viewer::unsaved::generated_name;
+use crate::model::config::{CacheConfig, GridConfig, ViewerConfig};
 use crate::model::image_meta::ImageProperties;
-use crate::utils::image::{collect_images, is_image};
-use egui::{Color32, Context, TextureHandle};
-use lru::LruCache;
-use std::collections::HashSet;
-use std::num::NonZeroUsize;
+use crate::model::session::{FolderPrefs, Session, SortOrder, ViewMode, ZoomMode, save_session};
+use crate::utils::image::{collect_images, is_image_file};
+use crate::utils::palette::{Swatch, dominant_colors};
+use egui::{Color32, ColorImage, Context, TextureHandle, TextureId, TextureOptions, Vec2};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::SystemTime;
 
-#[derive(Clone, PartialEq)]
-pub enum ViewMode {
-    Single,
-    Grid,
-}
+/// 对比视图最多同时显示的图片数
+pub const COMPARE_MAX: usize = 4;
+
+/// 属性面板中显示的主色数量
+pub const PALETTE_SIZE: usize = 8;
+
+/// 缩略图解码上限，高度与网格行高对齐，宽度放宽以照顾全景图
+pub const THUMB_SIZE: (u32, u32) = (512, 256);
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum TransitionPhase {
@@ -27,10 +43,58 @@ pub struct CurrentImage {
     pub properties: Option<ImageProperties>,
     pub raw_pixels: Option<Arc<Vec<Color32>>>,
     pub error: Option<ImageLoadError>,
+    /// 通道视图纹理及其来源纹理和模式，任一变化后重建
+    pub channel_texture: Option<(TextureId, ChannelView, TextureHandle)>,
+    /// 主色及其来源纹理，纹理换掉后重新提取
+    pub palette: Option<(TextureId, Vec<Swatch>)>,
+    /// 正在显示的低分辨率预览纹理及原图尺寸，纹理换掉后自动失效
+    pub preview: Option<(TextureId, Vec2)>,
+    /// 从剪贴板粘贴、尚未保存到磁盘的图片，翻页后丢弃
+    pub unsaved: bool,
+}
+
+/// 原图缓存的条目，像素快照和属性随纹理一起保存，切回时无需重新解码
+#[derive(Clone)]
+pub struct CachedImage {
+    pub texture: TextureHandle,
+    pub raw_pixels: Arc<Vec<Color32>>,
+    pub properties: ImageProperties,
+}
+
+impl CachedImage {
+    /// 显存中的纹理与内存中的像素快照各占一份
+    fn bytes(&self) -> usize {
+        texture_bytes(&self.texture) + self.raw_pixels.len() * std::mem::size_of::<Color32>()
+    }
+}
+
+fn texture_bytes(texture: &TextureHandle) -> usize {
+    let [w, h] = texture.size();
+    w * h * std::mem::size_of::<Color32>()
+}
+
+/// 原图的宽高信息，纹理被淘汰后仍然保留，避免网格布局来回跳动
+#[derive(Clone, Copy)]
+pub struct ThumbInfo {
+    pub aspect: f32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ThumbInfo {
+    fn new(texture: &TextureHandle, properties: &ImageProperties) -> Self {
+        let [w, h] = texture.size();
+        Self {
+            aspect: w as f32 / h.max(1) as f32,
+            width: properties.width,
+            height: properties.height,
+        }
+    }
 }
 
 pub struct ThumbManager {
-    pub cache: LruCache<PathBuf, TextureHandle>,
+    pub cache: ByteLruCache<PathBuf, TextureHandle>,
+    pub info: HashMap<PathBuf, ThumbInfo>,
     pub failed: HashSet<PathBuf>,
     pub loading: HashSet<PathBuf>,
 }
@@ -45,39 +109,104 @@ pub struct TransitionState {
 
 pub struct ViewerState {
     pub loader: ImageLoader,
+    /// 当前打开的文件夹
+    pub folder: Option<PathBuf>,
+    /// 当前文件夹下的全部图片
+    pub all_images: Vec<PathBuf>,
+    /// 经过筛选后的图片，导航、预览条和网格都以此为准
     pub list: Vec<PathBuf>,
+    pub filter: FilterState,
+    pub sort: SortOrder,
+    /// 当前文件夹单独设置的网格行高，优先于全局配置
+    pub folder_row_height: Option<f32>,
+    /// 最近打开记录和各文件夹的浏览方式
+    pub session: Session,
+    /// 恢复上次会话时，第一张图片沿用退出前的缩放比例
+    restored_zoom: Option<f32>,
+    /// 网格视图中的多选
+    pub selection: Selection,
+    pub batch: BatchRunner,
+    pub join: JoinRunner,
+    /// 动图预览
+    pub sequence: SequenceState,
+    /// 对比视图中的图片
+    pub compare: Vec<PathBuf>,
     pub index: usize,
-    pub texture_cache: LruCache<PathBuf, TextureHandle>,
+    pub texture_cache: ByteLruCache<PathBuf, CachedImage>,
+    /// 已加载纹理使用的采样设置
+    texture_options: TextureOptions,
     pub current: CurrentImage,
     pub zoom: f32,
+    /// 打开图片时采用的缩放方式，滚轮缩放不会改变它
+    pub zoom_mode: ZoomMode,
+    /// 锁定后翻到同尺寸的图片时沿用当前的缩放和位置
+    pub zoom_locked: bool,
+    /// 单个通道、亮度或色觉模拟显示
+    pub channel_view: ChannelView,
+    pub measure: MeasureState,
+    /// 显示缓存调试读数
+    pub show_cache_stats: bool,
+    /// 上次套用缩放方式时的图片尺寸
+    zoom_image_size: Option<Vec2>,
     pub last_view_size: Option<egui::Vec2>,
+    /// 上一帧指针是否停在单图视图上，且没有被菜单、弹窗或信息浮层挡住
+    pub view_hovered: bool,
     pub viewport_offset: egui::Vec2,
     pub thumbs: ThumbManager,
     pub transition: TransitionState,
     pub view_mode: ViewMode,
     /// 属性面板打开/关闭动画进度 (0.0=关闭, 1.0=完全打开)
     pub panel_animation: f32,
+    /// 网格中调整过、尚未写回配置的显示设置
+    pub pending_grid_config: Option<GridConfig>,
+    /// 底栏中调整过、尚未写回配置的看图设置
+    pub pending_viewer_config: Option<ViewerConfig>,
 }
 
 impl ViewerState {
     pub fn new() -> Self {
         Self {
             loader: ImageLoader::new(),
+            folder: None,
+            all_images: Vec::new(),
             list: Vec::new(),
+            filter: FilterState::default(),
+            sort: SortOrder::default(),
+            folder_row_height: None,
+            session: Session::default(),
+            restored_zoom: None,
+            selection: Selection::default(),
+            batch: BatchRunner::new(),
+            join: JoinRunner::new(),
+            sequence: SequenceState::new(),
+            compare: Vec::new(),
             index: 0,
-            texture_cache: LruCache::new(NonZeroUsize::new(10).expect("10 is non-zero")),
+            texture_cache: ByteLruCache::new(CacheConfig::default().image_bytes()),
+            texture_options: ViewerConfig::default().texture_options(),
             current: CurrentImage {
                 texture: None,
                 texture_path: None,
                 properties: None,
                 raw_pixels: None,
                 error: None,
+                channel_texture: None,
+                palette: None,
+                preview: None,
+                unsaved: false,
             },
             zoom: 1.0,
+            zoom_mode: ZoomMode::default(),
+            zoom_locked: false,
+            channel_view: ChannelView::Normal,
+            measure: MeasureState::default(),
+            show_cache_stats: false,
+            zoom_image_size: None,
             last_view_size: None,
+            view_hovered: false,
             viewport_offset: egui::Vec2::ZERO,
             thumbs: ThumbManager {
-                cache: LruCache::new(NonZeroUsize::new(1000).expect("1000 is non-zero")),
+                cache: ByteLruCache::new(CacheConfig::default().thumb_bytes()),
+                info: HashMap::new(),
                 failed: HashSet::new(),
                 loading: HashSet::new(),
             },
@@ -90,23 +219,114 @@ impl ViewerState {
             },
             view_mode: ViewMode::Single,
             panel_animation: 0.0,
+            pending_grid_config: None,
+            pending_viewer_config: None,
         }
     }
 
     pub fn f_image(&mut self, path: &Path) {
         if let Some(dir) = path.parent() {
             let mut v = collect_images(dir);
-            v.sort();
+            sort_images(&mut v, self.sort);
             self.index = v.iter().position(|p| p == path).unwrap_or(0);
-            self.list = v;
+            self.folder = Some(dir.to_path_buf());
+            self.set_folder_images(v);
         }
     }
 
     pub fn f_folder(&mut self, path: &Path) {
         let mut v = collect_images(path);
-        v.sort();
+        sort_images(&mut v, self.sort);
         self.index = 0;
-        self.list = v;
+        self.folder = Some(path.to_path_buf());
+        self.set_folder_images(v);
+    }
+
+    fn set_folder_images(&mut self, images: Vec<PathBuf>) {
+        self.filter.reset();
+        self.selection.clear();
+        self.compare.clear();
+        self.all_images = images.clone();
+        self.list = images;
+    }
+
+    /// 按筛选条件重建列表，尽量保持当前图片不变。需要读取文件信息时在后台进行，读完后由 `poll_filter` 更新列表
+    pub fn apply_filter(&mut self, ctx: Context) {
+        if let Some(list) = self.filter.apply(&self.all_images, &ctx) {
+            self.replace_list(ctx, list);
+        }
+    }
+
+    /// 后台读取的文件信息到达后重新筛选，返回筛选后的图片数
+    pub fn poll_filter(&mut self, ctx: &Context) -> Option<usize> {
+        if !self.filter.poll() {
+            return None;
+        }
+        let list = self.filter.reapply(&self.all_images, ctx)?;
+        let count = list.len();
+        self.replace_list(ctx.clone(), list);
+        Some(count)
+    }
+
+    /// 批量操作后重新扫描文件夹，沿用已生效的筛选条件
+    pub fn refresh_folder(&mut self, ctx: Context, removed: &[PathBuf]) {
+        let Some(folder) = self.folder.clone() else {
+            return;
+        };
+        for path in removed {
+            self.texture_cache.pop(path);
+            self.thumbs.cache.pop(path);
+        }
+        self.compare.retain(|path| !removed.contains(path));
+
+        let mut v = collect_images(&folder);
+        sort_images(&mut v, self.sort);
+        self.all_images = v;
+        if let Some(list) = self.filter.reapply(&self.all_images, &ctx) {
+            self.replace_list(ctx, list);
+        }
+    }
+
+    /// 切换排序方式，筛选条件和当前图片保持不变
+    pub fn set_sort(&mut self, ctx: Context, order: SortOrder) {
+        if self.sort == order {
+            return;
+        }
+        self.sort = order;
+        sort_images(&mut self.all_images, order);
+        if let Some(list) = self.filter.reapply(&self.all_images, &ctx) {
+            self.replace_list(ctx, list);
+        }
+    }
+
+    fn replace_list(&mut self, ctx: Context, list: Vec<PathBuf>) {
+        let current = self.current();
+        self.list = list;
+        self.index = current
+            .as_ref()
+            .and_then(|path| self.list.iter().position(|p| p == path))
+            .unwrap_or_else(|| self.index.min(self.list.len().saturating_sub(1)));
+        self.selection.retain_in(&self.list);
+        if self.current() != current {
+            self.viewport_offset = egui::Vec2::ZERO;
+            self.load_current(ctx);
+        }
+    }
+
+    pub fn selected_paths(&self) -> Vec<PathBuf> {
+        self.selection.paths_in_order(&self.list)
+    }
+
+    /// 打开对比视图，缺少原图的先在后台加载
+    pub fn open_compare(&mut self, ctx: &Context, paths: Vec<PathBuf>) {
+        self.compare = paths.into_iter().take(COMPARE_MAX).collect();
+        for path in self.compare.clone() {
+            if !self.texture_cache.contains(&path) {
+                self.thumbs.failed.remove(&path);
+                self.loader.load_full(ctx, path, LoadPriority::Visible);
+            }
+        }
+        self.view_mode = ViewMode::Compare;
     }
 
     pub fn next(&mut self) -> Option<PathBuf> {
@@ -161,6 +381,99 @@ impl ViewerState {
     }
 
     pub fn open_new_context(&mut self, ctx: Context, path: PathBuf) {
+        self.remember_folder_prefs();
+        self.reset_context();
+        let is_dir = path.is_dir();
+        let folder = if is_dir {
+            Some(path.as_path())
+        } else {
+            path.parent()
+        };
+        let prefs = folder.and_then(|dir| self.session.folders.get(dir).cloned());
+        let FolderPrefs {
+            view_mode,
+            sort,
+            row_height,
+            ..
+        } = prefs.clone().unwrap_or_default();
+        self.sort = sort;
+        self.folder_row_height = row_height;
+        if is_dir {
+            self.f_folder(&path);
+            // 没来过的文件夹默认进入网格
+            self.view_mode = if prefs.is_some() {
+                view_mode
+            } else {
+                ViewMode::Grid
+            };
+        } else {
+            self.f_image(&path);
+            self.view_mode = ViewMode::Single;
+        }
+        self.session.record_open(&path);
+        self.save_session();
+        self.load_current(ctx);
+    }
+
+    /// 恢复上次退出时的文件夹、当前图片、视图和缩放，记录的路径已不存在时保持空白
+    pub fn restore_session(&mut self, ctx: &Context) {
+        let session = self.session.clone();
+        let target = session
+            .last_file
+            .filter(|path| path.is_file())
+            .or(session.last_folder.filter(|path| path.is_dir()));
+        let Some(target) = target else {
+            return;
+        };
+        self.zoom_mode = session.zoom_mode;
+        self.restored_zoom = session.zoom;
+        self.open_new_context(ctx.clone(), target);
+        self.view_mode = session.view_mode;
+    }
+
+    /// 把当前文件夹的视图、排序和行高记入会话
+    fn remember_folder_prefs(&mut self) {
+        let Some(folder) = self.folder.clone() else {
+            return;
+        };
+        let prefs = FolderPrefs {
+            view_mode: self.persisted_view_mode(),
+            sort: self.sort,
+            row_height: self.folder_row_height,
+            ..FolderPrefs::default()
+        };
+        self.session.remember_folder(folder, prefs);
+    }
+
+    /// 对比视图是临时的，记录时回到网格
+    fn persisted_view_mode(&self) -> ViewMode {
+        match self.view_mode {
+            ViewMode::Compare => ViewMode::Grid,
+            mode => mode,
+        }
+    }
+
+    /// 记下当前浏览位置并写入 session.json
+    pub fn save_session(&mut self) {
+        self.remember_folder_prefs();
+        self.session.last_folder = self.folder.clone();
+        self.session.last_file = self.current().filter(|_| !self.current.unsaved);
+        self.session.view_mode = self.persisted_view_mode();
+        self.session.zoom_mode = self.zoom_mode;
+        self.session.zoom = self.current.texture.as_ref().map(|_| self.zoom);
+        save_session(&self.session);
+    }
+
+    /// 清空最近打开记录
+    pub fn clear_recent(&mut self) {
+        self.session.clear_recent();
+        save_session(&self.session);
+    }
+
+    /// 切换到新的文件夹或图片列表前清空当前图片和过渡状态
+    fn reset_context(&mut self) {
+        self.loader.cancel_pending();
+        self.thumbs.loading.clear();
         self.current.texture = None;
         self.current.texture_path = None;
         self.current.properties = None;
@@ -172,15 +485,62 @@ impl ViewerState {
         self.transition.phase = TransitionPhase::None;
         self.transition.phase_start_time = None;
         self.transition.target_path = None;
+    }
 
-        if path.is_dir() {
-            self.f_folder(&path);
-            self.view_mode = ViewMode::Grid;
-        } else {
-            self.f_image(&path);
-            self.view_mode = ViewMode::Single;
+    /// 在单图中显示剪贴板里的图片，文件夹和列表保持不变，翻页即回到列表
+    pub fn open_unsaved(&mut self, ctx: &Context, image: ColorImage) {
+        self.loader.supersede_full();
+        self.transition.phase = TransitionPhase::None;
+        self.transition.phase_start_time = None;
+        self.transition.target_path = None;
+        self.transition.previous_texture = None;
+
+        let [width, height] = image.size;
+        let name = generated_name(SystemTime::now());
+        let raw_pixels = Arc::new(image.pixels.clone());
+        let texture = ctx.load_texture(name.clone(), image, self.texture_options);
+        self.current = CurrentImage {
+            texture: Some(texture),
+            texture_path: None,
+            properties: Some(ImageProperties {
+                path: PathBuf::from(&name),
+                name,
+                width: width as u32,
+                height: height as u32,
+                size: (raw_pixels.len() * std::mem::size_of::<Color32>()) as u64,
+                ..Default::default()
+            }),
+            raw_pixels: Some(raw_pixels),
+            error: None,
+            channel_texture: None,
+            palette: None,
+            preview: None,
+            unsaved: true,
+        };
+        self.view_mode = ViewMode::Single;
+        self.apply_zoom_mode(ctx, Vec2::new(width as f32, height as f32));
+    }
+
+    /// 打开粘贴的路径：单个路径按拖放处理，多个图片组成临时列表。返回是否打开了内容
+    pub fn open_paths(&mut self, ctx: &Context, paths: Vec<PathBuf>) -> bool {
+        if let [path] = paths.as_slice()
+            && (path.is_dir() || is_image_file(path))
+        {
+            self.open_new_context(ctx.clone(), path.clone());
+            return true;
         }
-        self.load_current(ctx);
+        let images: Vec<PathBuf> = paths.into_iter().filter(|p| is_image_file(p)).collect();
+        if images.is_empty() {
+            return false;
+        }
+        self.remember_folder_prefs();
+        self.reset_context();
+        self.folder = None;
+        self.index = 0;
+        self.set_folder_images(images);
+        self.view_mode = ViewMode::Single;
+        self.load_current(ctx.clone());
+        true
     }
 
     pub fn process_load_results(&mut self, ctx: &Context) -> bool {
@@ -193,32 +553,46 @@ impl ViewerState {
                 Ok(msg) => {
                     received_any = true;
                     match msg.result {
+                        LoadResult::Ok(success) if msg.is_preview => {
+                            self.show_preview(ctx, msg.path, *success);
+                        }
                         LoadResult::Ok(success) => {
                             let success = *success;
+                            self.thumbs.info.insert(
+                                msg.path.clone(),
+                                ThumbInfo::new(&success.texture, &success.properties),
+                            );
                             if msg.is_thumbnail {
                                 self.thumbs.loading.remove(&msg.path);
-                                self.thumbs
-                                    .cache
-                                    .put(msg.path.clone(), success.texture.clone());
+                                let bytes = texture_bytes(&success.texture);
+                                self.thumbs.cache.put(
+                                    msg.path.clone(),
+                                    success.texture.clone(),
+                                    bytes,
+                                );
                                 if Some(msg.path.clone()) == self.current()
                                     && !self.texture_cache.contains(&msg.path)
+                                    && !self.showing_preview()
+                                    && !self.current.unsaved
                                 {
                                     self.current.texture = Some(success.texture);
                                     self.current.texture_path = Some(msg.path);
                                 }
                             } else {
-                                self.current.raw_pixels = Some(success.raw_pixels);
+                                let cached = CachedImage {
+                                    texture: success.texture,
+                                    raw_pixels: success.raw_pixels,
+                                    properties: success.properties,
+                                };
+                                let bytes = cached.bytes();
                                 self.texture_cache
-                                    .put(msg.path.clone(), success.texture.clone());
-                                if Some(msg.path) == self.current() {
-                                    let new_zoom =
-                                        self.calc_fit_zoom(ctx, success.texture.size_vec2());
-                                    self.zoom = new_zoom;
-                                    self.viewport_offset = egui::Vec2::ZERO;
-
-                                    self.current.texture = Some(success.texture);
-                                    self.current.texture_path = self.current();
-                                    self.current.properties = Some(success.properties);
+                                    .put(msg.path.clone(), cached.clone(), bytes);
+                                if Some(msg.path) == self.current() && !self.current.unsaved {
+                                    // 预览已按原图尺寸设好缩放，换成原图时保留用户的缩放和平移
+                                    if !self.showing_preview() {
+                                        self.reset_view(ctx, cached.texture.size_vec2());
+                                    }
+                                    self.show_cached(cached);
                                     self.loader.is_loading = false;
                                     should_trigger_preloads = true;
                                 }
@@ -231,7 +605,7 @@ impl ViewerState {
                                 self.loader.is_loading = false;
                                 self.current.error = Some(e.clone());
                             }
-                            if Some(msg.path.clone()) == self.current() {
+                            if Some(msg.path.clone()) == self.current() && !self.current.unsaved {
                                 self.current.texture = None;
                                 self.current.texture_path = None;
                             }
@@ -251,10 +625,90 @@ impl ViewerState {
         received_any
     }
 
+    /// 采样设置改变后用像素快照重建当前纹理，其余缓存丢弃，之后按新设置重新加载
+    pub fn sync_texture_options(&mut self, options: TextureOptions) {
+        if self.texture_options == options {
+            return;
+        }
+        self.texture_options = options;
+        self.texture_cache.clear();
+        self.current.channel_texture = None;
+
+        let is_current =
+            self.current.texture_path.is_some() && self.current.texture_path == self.current();
+        if let (true, Some(tex), Some(pixels)) = (
+            is_current,
+            self.current.texture.as_mut(),
+            self.current.raw_pixels.as_ref(),
+        ) {
+            let size = tex.size();
+            if pixels.len() == size[0] * size[1] {
+                tex.set(ColorImage::new(size, pixels.to_vec()), options);
+                if let (Some(path), Some(properties)) = (
+                    self.current.texture_path.clone(),
+                    self.current.properties.clone(),
+                ) {
+                    let cached = CachedImage {
+                        texture: tex.clone(),
+                        raw_pixels: Arc::clone(pixels),
+                        properties,
+                    };
+                    let bytes = cached.bytes();
+                    self.texture_cache.put(path, cached, bytes);
+                }
+            }
+        }
+    }
+
+    /// 当前图片按通道视图变换后的纹理，按需从像素快照生成；正常显示或只有缩略图占位时返回 None
+    pub fn channel_texture(&mut self, ctx: &Context) -> Option<TextureHandle> {
+        let view = self.channel_view;
+        if view == ChannelView::Normal {
+            return None;
+        }
+        let texture = self.current.texture.as_ref()?;
+        if let Some((source, cached_view, cached)) = &self.current.channel_texture
+            && *source == texture.id()
+            && *cached_view == view
+        {
+            return Some(cached.clone());
+        }
+        let pixels = self.current.raw_pixels.as_ref()?;
+        let size = texture.size();
+        if pixels.len() != size[0] * size[1] {
+            return None;
+        }
+        let transformed = ctx.load_texture(
+            "channel_view",
+            ColorImage::new(size, view.transform(pixels)),
+            self.texture_options,
+        );
+        self.current.channel_texture = Some((texture.id(), view, transformed.clone()));
+        Some(transformed)
+    }
+
+    /// 当前图片的主色，按需从像素快照提取
+    pub fn palette(&mut self) -> Vec<Swatch> {
+        let Some(texture) = self.current.texture.as_ref() else {
+            return Vec::new();
+        };
+        if let Some((source, swatches)) = &self.current.palette
+            && *source == texture.id()
+        {
+            return swatches.clone();
+        }
+        let Some(pixels) = self.current.raw_pixels.as_ref() else {
+            return Vec::new();
+        };
+        let swatches = dominant_colors(pixels, PALETTE_SIZE);
+        self.current.palette = Some((texture.id(), swatches.clone()));
+        swatches
+    }
+
     pub fn trigger_preloads(&mut self, ctx: &Context) {
         let to_load = self.get_preview_window();
         for (_, path) in to_load {
-            if self.thumbs.cache.contains(&path)
+            if self.thumbs.cache.lookup(&path).is_some()
                 || self.thumbs.failed.contains(&path)
                 || self.thumbs.loading.contains(&path)
             {
@@ -262,21 +716,34 @@ impl ViewerState {
             }
             self.thumbs.loading.insert(path.clone());
             self.loader
-                .load_async(ctx.clone(), path, false, Some((160, 120)));
+                .load_thumbnail(ctx, path, THUMB_SIZE, LoadPriority::Neighbor);
+        }
+    }
+
+    /// 在后台解码前后相邻的原图，翻页时可直接命中缓存
+    fn prefetch_neighbors(&mut self, ctx: &Context) {
+        let len = self.list.len();
+        if len < 2 {
+            return;
+        }
+        for offset in [1, len - 1] {
+            let path = self.list[(self.index + offset) % len].clone();
+            if !self.texture_cache.contains(&path) && !self.thumbs.failed.contains(&path) {
+                self.loader.load_full(ctx, path, LoadPriority::Neighbor);
+            }
         }
     }
 
     pub fn load_current(&mut self, ctx: Context) {
         self.current.error = None;
+        self.current.unsaved = false;
+        self.loader.supersede_full();
         if let Some(path) = self.current() {
             self.trigger_preloads(&ctx);
-            let cached_tex = self.texture_cache.get(&path).cloned();
-            if let Some(tex) = cached_tex {
-                let new_zoom = self.calc_fit_zoom(&ctx, tex.size_vec2());
-                self.zoom = new_zoom;
-                self.viewport_offset = egui::Vec2::ZERO;
-                self.current.texture = Some(tex.clone());
-                self.current.texture_path = Some(path);
+            let cached = self.texture_cache.lookup(&path).cloned();
+            if let Some(cached) = cached {
+                self.reset_view(&ctx, cached.texture.size_vec2());
+                self.show_cached(cached);
                 self.loader.is_loading = false;
             } else if self.thumbs.failed.contains(&path) {
                 self.current.error =
@@ -285,18 +752,70 @@ impl ViewerState {
                 self.current.texture_path = None;
                 self.loader.is_loading = false;
             } else {
+                self.current.raw_pixels = None;
                 if let Some(thumb) = self.thumbs.cache.get(&path).cloned() {
                     self.current.texture = Some(thumb);
                     self.current.texture_path = Some(path.clone());
                 }
-                self.loader.load_async(ctx, path, true, None);
+                self.loader.load_current(&ctx, path);
             }
+            self.prefetch_neighbors(&ctx);
         } else {
             self.current.texture = None;
             self.current.texture_path = None;
         }
     }
 
+    /// 原图尚未解码完时先显示预览，视图按原图尺寸设置
+    fn show_preview(&mut self, ctx: &Context, path: PathBuf, preview: LoadSuccess) {
+        if Some(&path) != self.current().as_ref()
+            || self.texture_cache.contains(&path)
+            || self.current.unsaved
+        {
+            return;
+        }
+        let size = Vec2::new(
+            preview.properties.width as f32,
+            preview.properties.height as f32,
+        );
+        self.reset_view(ctx, size);
+        self.current.preview = Some((preview.texture.id(), size));
+        self.current.texture = Some(preview.texture);
+        self.current.texture_path = Some(path);
+        self.current.properties = Some(preview.properties);
+        self.current.raw_pixels = None;
+    }
+
+    fn showing_preview(&self) -> bool {
+        self.current.texture_path == self.current()
+            && self
+                .current
+                .texture
+                .as_ref()
+                .is_some_and(|tex| self.current.preview.is_some_and(|(id, _)| id == tex.id()))
+    }
+
+    /// 纹理按原图像素显示时的尺寸，预览纹理会被拉伸到原图大小
+    pub fn display_size(&self, tex: &TextureHandle) -> Vec2 {
+        match self.current.preview {
+            Some((id, size)) if id == tex.id() => size,
+            _ => tex.size_vec2(),
+        }
+    }
+
+    fn show_cached(&mut self, cached: CachedImage) {
+        self.current.texture = Some(cached.texture);
+        self.current.texture_path = self.current();
+        self.current.raw_pixels = Some(cached.raw_pixels);
+        self.current.properties = Some(cached.properties);
+    }
+
+    /// 配置中的内存预算变化后收缩缓存
+    pub fn set_cache_budget(&mut self, cache: &CacheConfig) {
+        self.texture_cache.set_budget(cache.image_bytes());
+        self.thumbs.cache.set_budget(cache.thumb_bytes());
+    }
+
     pub fn prev_image(&mut self, ctx: Context) {
         if self.prev().is_some() {
             self.start_transition(&ctx);
@@ -320,6 +839,7 @@ impl ViewerState {
     }
 
     fn start_transition(&mut self, ctx: &Context) {
+        self.current.unsaved = false;
         self.transition.previous_texture = None;
         self.transition.previous_zoom = None;
         self.current.texture = None;
@@ -330,22 +850,32 @@ impl ViewerState {
     }
 
     pub fn handle_dropped_file(&mut self, ctx: Context, path: PathBuf) {
-        if is_image(&path) {
+        if is_image_file(&path) {
             self.open_new_context(ctx, path);
         }
     }
 
+    /// 滚轮缩放，`delta` 为滚动的点数
     pub fn update_zoom(
         &mut self,
         delta: f32,
         pointer_pos: Option<egui::Pos2>,
         viewport: egui::Rect,
     ) {
-        if delta == 0.0 {
-            return;
+        if delta != 0.0 {
+            self.zoom_to(self.zoom + delta * 0.001, pointer_pos, viewport);
         }
+    }
+
+    /// 缩放到指定比例，保持指针下的像素不动
+    pub fn zoom_to(
+        &mut self,
+        new_zoom: f32,
+        pointer_pos: Option<egui::Pos2>,
+        viewport: egui::Rect,
+    ) {
         let old_zoom = self.zoom;
-        let new_zoom = (old_zoom + delta * 0.001).clamp(0.1, 10.0);
+        let new_zoom = new_zoom.clamp(0.1, 10.0);
         if (new_zoom - old_zoom).abs() < f32::EPSILON {
             return;
         }
@@ -353,7 +883,7 @@ impl ViewerState {
         if let Some(pointer) = pointer_pos
             && let Some(tex) = self.current.texture.as_ref()
         {
-            let img_size = tex.size_vec2();
+            let img_size = self.display_size(tex);
             let img_origin = viewport.min + self.viewport_offset;
             // pointer position in image pixel space
             let img_coord = (pointer - img_origin) / old_zoom;
@@ -370,13 +900,45 @@ impl ViewerState {
         }
     }
 
-    pub(crate) fn calc_fit_zoom(&self, ctx: &Context, tex_size: egui::Vec2) -> f32 {
+    /// 新图片加载完成时按缩放方式重置视图；锁定且尺寸相同时保持不变
+    fn reset_view(&mut self, ctx: &Context, tex_size: Vec2) {
+        if self.zoom_locked && self.zoom_image_size == Some(tex_size) {
+            return;
+        }
+        self.apply_zoom_mode(ctx, tex_size);
+        if let Some(zoom) = self.restored_zoom.take() {
+            let available = self
+                .last_view_size
+                .unwrap_or_else(|| ctx.content_rect().size());
+            self.zoom = zoom;
+            self.viewport_offset = self.zoom_mode.initial_offset(tex_size * zoom, available);
+        }
+    }
+
+    /// 切换缩放方式并立即作用于当前图片
+    pub fn set_zoom_mode(&mut self, ctx: &Context, mode: ZoomMode) {
+        self.zoom_mode = mode;
+        if let Some(tex_size) = self
+            .current
+            .texture
+            .as_ref()
+            .map(|tex| self.display_size(tex))
+        {
+            self.apply_zoom_mode(ctx, tex_size);
+        }
+    }
+
+    fn apply_zoom_mode(&mut self, ctx: &Context, tex_size: Vec2) {
         let available = self
             .last_view_size
             .unwrap_or_else(|| ctx.content_rect().size());
-        let scale_v = available.y / tex_size.y;
-        let scale_h = (available.x - 120.0) / tex_size.x;
-
-        scale_v.min(scale_h).min(1.0) * 0.9
+        self.zoom = self
+            .zoom_mode
+            .zoom_for(tex_size, available)
+            .clamp(0.1, 10.0);
+        self.viewport_offset = self
+            .zoom_mode
+            .initial_offset(tex_size * self.zoom, available);
+        self.zoom_image_size = Some(tex_size);
     }
 }
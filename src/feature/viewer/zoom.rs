@@ -0,0 +1,102 @@
+use crate::core::keymap::Action;
+use crate::i18n::lang::TextBundle;
+use crate::model::session::ZoomMode;
+use egui::Vec2;
+
+/// 左右两侧留给翻页箭头的宽度之和
+const ARROW_ZONE_WIDTH: f32 = 120.0;
+/// 适应窗口时四周留出的余量
+const FIT_MARGIN: f32 = 0.9;
+
+impl ZoomMode {
+    pub const ALL: [Self; 5] = [
+        Self::Fit,
+        Self::Fill,
+        Self::Actual,
+        Self::FitWidth,
+        Self::FitHeight,
+    ];
+
+    pub fn label(self, text: &TextBundle) -> &'static str {
+        match self {
+            Self::Fit => text.zoom.fit,
+            Self::Fill => text.zoom.fill,
+            Self::Actual => text.zoom.actual,
+            Self::FitWidth => text.zoom.fit_width,
+            Self::FitHeight => text.zoom.fit_height,
+        }
+    }
+
+    /// 对应的快捷键动作，按键在 keymap 中配置
+    pub fn action(self) -> Action {
+        match self {
+            Self::Fit => Action::ZoomFit,
+            Self::Fill => Action::ZoomFill,
+            Self::Actual => Action::ZoomActual,
+            Self::FitWidth => Action::ZoomFitWidth,
+            Self::FitHeight => Action::ZoomFitHeight,
+        }
+    }
+
+    /// 计算图片在给定视口中的缩放比例
+    pub fn zoom_for(self, image: Vec2, view: Vec2) -> f32 {
+        let scale_h = (view.x - ARROW_ZONE_WIDTH).max(1.0) / image.x.max(1.0);
+        let scale_v = view.y.max(1.0) / image.y.max(1.0);
+        match self {
+            Self::Fit => scale_v.min(scale_h).min(1.0) * FIT_MARGIN,
+            Self::Fill => scale_v.max(view.x / image.x.max(1.0)),
+            Self::Actual => 1.0,
+            Self::FitWidth => scale_h,
+            Self::FitHeight => scale_v,
+        }
+    }
+
+    /// 图片超出视口时的初始偏移：适应宽度从顶部开始看，其余居中
+    pub fn initial_offset(self, scaled: Vec2, view: Vec2) -> Vec2 {
+        let centered = (view - scaled) * 0.5;
+        match self {
+            Self::FitWidth => Vec2::new(centered.x, centered.y.max(0.0)),
+            _ => centered,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ZoomMode;
+    use egui::Vec2;
+
+    #[test]
+    fn fit_never_upscales_small_images() {
+        let view = Vec2::new(1120.0, 800.0);
+        assert_eq!(ZoomMode::Fit.zoom_for(Vec2::new(100.0, 100.0), view), 0.9);
+        assert_eq!(
+            ZoomMode::Fit.zoom_for(Vec2::new(2000.0, 1600.0), view),
+            0.45
+        );
+    }
+
+    #[test]
+    fn fill_and_axis_fits_cover_the_expected_side() {
+        let view = Vec2::new(1120.0, 800.0);
+        let image = Vec2::new(2000.0, 1000.0);
+        assert_eq!(ZoomMode::Fill.zoom_for(image, view), 0.8);
+        assert_eq!(ZoomMode::FitWidth.zoom_for(image, view), 0.5);
+        assert_eq!(ZoomMode::FitHeight.zoom_for(image, view), 0.8);
+        assert_eq!(ZoomMode::Actual.zoom_for(image, view), 1.0);
+    }
+
+    #[test]
+    fn fit_width_starts_at_the_top() {
+        let view = Vec2::new(800.0, 600.0);
+        let scaled = Vec2::new(800.0, 2000.0);
+        assert_eq!(
+            ZoomMode::FitWidth.initial_offset(scaled, view),
+            Vec2::new(0.0, 0.0)
+        );
+        assert_eq!(
+            ZoomMode::Actual.initial_offset(scaled, view),
+            Vec2::new(0.0, -700.0)
+        );
+    }
+}
@@ -62,12 +62,29 @@ pub struct MenuText {
     pub settings: &'static str,
     pub about: &'static str,
     pub exit: &'static str,
+    pub recent: &'static str,
+    pub recent_folders: &'static str,
+    pub recent_files: &'static str,
+    pub clear_recent: &'static str,
+    pub no_recent: &'static str,
 }
 
 pub struct ContextMenuText {
     pub copy: &'static str,
     pub copy_path: &'static str,
     pub properties: &'static str,
+    pub copy_to: &'static str,
+    pub move_to: &'static str,
+    pub convert_to: &'static str,
+    pub delete: &'static str,
+    pub compare: &'static str,
+    pub save_as: &'static str,
+    pub open_with: &'static str,
+    pub show_in_folder: &'static str,
+    pub no_external_apps: &'static str,
+    pub contact_sheet: &'static str,
+    pub join: &'static str,
+    pub export_animation: &'static str,
 }
 
 pub struct SettingsText {
@@ -81,6 +98,20 @@ pub struct SettingsText {
     pub close: &'static str,
     pub apply: &'static str,
     pub shortcut_key: &'static str,
+    pub viewer: &'static str,
+    pub smooth_magnification: &'static str,
+    pub pixel_grid: &'static str,
+    pub pixel_grid_zoom: &'static str,
+    pub image_cache: &'static str,
+    pub thumb_cache: &'static str,
+    pub external_apps: &'static str,
+    pub external_apps_hint: &'static str,
+    pub app_name: &'static str,
+    pub app_command: &'static str,
+    pub add_app: &'static str,
+    pub remove: &'static str,
+    pub no_hotkey: &'static str,
+    pub mouse: &'static str,
 }
 
 pub struct ShortcutText {
@@ -89,6 +120,70 @@ pub struct ShortcutText {
     pub modified: &'static str,
 }
 
+pub struct KeymapText {
+    pub prev_image: &'static str,
+    pub next_image: &'static str,
+    pub cache_stats: &'static str,
+    pub zoom_lock: &'static str,
+    pub show_alpha: &'static str,
+    pub select_all: &'static str,
+    pub clear_selection: &'static str,
+    pub exit_compare: &'static str,
+    pub undo: &'static str,
+    pub redo: &'static str,
+    pub confirm: &'static str,
+    pub cancel: &'static str,
+    pub search_hint: &'static str,
+    pub group_global: &'static str,
+    pub group_viewer: &'static str,
+    pub group_screenshot: &'static str,
+    pub group_external: &'static str,
+    pub conflict: &'static str,
+    pub reset: &'static str,
+    pub record_hint: &'static str,
+    pub info_overlay: &'static str,
+    pub channel_view: &'static str,
+}
+
+pub struct MouseText {
+    pub wheel: &'static str,
+    pub ctrl_wheel: &'static str,
+    pub shift_wheel: &'static str,
+    pub middle_click: &'static str,
+    pub back_button: &'static str,
+    pub forward_button: &'static str,
+    pub double_click: &'static str,
+    pub pinch_zoom: &'static str,
+    pub trackpad_pan: &'static str,
+    pub zoom_sensitivity: &'static str,
+    pub zoom: &'static str,
+    pub navigate: &'static str,
+    pub pan: &'static str,
+    pub none: &'static str,
+    pub toggle_fit: &'static str,
+    pub fullscreen: &'static str,
+}
+
+pub struct OverlayText {
+    pub title: &'static str,
+    pub corner: &'static str,
+    pub template: &'static str,
+    pub placeholders: &'static str,
+    pub toggle_hint: &'static str,
+    pub top_left: &'static str,
+    pub top_right: &'static str,
+    pub bottom_left: &'static str,
+    pub bottom_right: &'static str,
+}
+
+pub struct MetadataText {
+    pub title: &'static str,
+    pub hint: &'static str,
+    pub keep_all: &'static str,
+    pub strip_gps: &'static str,
+    pub strip_all: &'static str,
+}
+
 pub struct AboutText {
     pub title: &'static str,
     pub description: &'static str,
@@ -102,6 +197,18 @@ pub struct ViewerText {
     pub error: &'static str,
     pub drag_hint: &'static str,
     pub no_images: &'static str,
+    pub truncated: &'static str,
+    pub unsaved: &'static str,
+}
+
+pub struct LoadErrorText {
+    pub file_not_found: &'static str,
+    pub read_error: &'static str,
+    pub unsupported_format: &'static str,
+    pub decode_error: &'static str,
+    pub truncated: &'static str,
+    pub unsupported_feature: &'static str,
+    pub too_large: &'static str,
 }
 
 pub struct LoadingText {
@@ -113,6 +220,11 @@ pub struct ToastText {
     pub copy_failed: &'static str,
     pub copying: &'static str,
     pub launch_on_startup_failed: &'static str,
+    pub saved: &'static str,
+    pub save_failed: &'static str,
+    pub paste_empty: &'static str,
+    pub recent_missing: &'static str,
+    pub launch_failed: &'static str,
 }
 
 pub struct ImageText {
@@ -166,10 +278,141 @@ pub struct OcrText {
 
 pub struct PropertiesText {
     pub no_image: &'static str,
+    pub extension_mismatch: &'static str,
+    pub has_location: &'static str,
 }
 
 pub struct GridText {
     pub loading: &'static str,
+    pub thumb_size: &'static str,
+    pub captions: &'static str,
+    pub sort: &'static str,
+    pub sort_name: &'static str,
+    pub sort_modified: &'static str,
+    pub sort_size: &'static str,
+}
+
+pub struct FilterText {
+    pub name_hint: &'static str,
+    pub extension_hint: &'static str,
+    pub more: &'static str,
+    pub width: &'static str,
+    pub height: &'static str,
+    pub size_kb: &'static str,
+    pub date: &'static str,
+    pub date_hint: &'static str,
+    pub apply: &'static str,
+    pub clear: &'static str,
+    pub no_match: &'static str,
+    pub probing: &'static str,
+    pub matched: &'static str,
+    pub probe_cancelled: &'static str,
+}
+
+pub struct BatchText {
+    pub selected: &'static str,
+    pub processing: &'static str,
+    pub done: &'static str,
+    pub partial: &'static str,
+    pub delete_title: &'static str,
+    pub delete_confirm: &'static str,
+    pub confirm_delete: &'static str,
+    pub cancel: &'static str,
+}
+
+pub struct ContactSheetText {
+    pub title: &'static str,
+    pub count: &'static str,
+    pub format: &'static str,
+    pub page_size: &'static str,
+    pub page_auto: &'static str,
+    pub landscape: &'static str,
+    pub columns: &'static str,
+    pub cell_size: &'static str,
+    pub margin: &'static str,
+    pub captions: &'static str,
+    pub caption_name: &'static str,
+    pub caption_date: &'static str,
+    pub caption_dimensions: &'static str,
+    pub export: &'static str,
+    pub cancel: &'static str,
+}
+
+pub struct JoinText {
+    pub title: &'static str,
+    pub count: &'static str,
+    pub direction: &'static str,
+    pub horizontal: &'static str,
+    pub vertical: &'static str,
+    pub grid: &'static str,
+    pub columns: &'static str,
+    pub spacing: &'static str,
+    pub align: &'static str,
+    pub align_start: &'static str,
+    pub align_center: &'static str,
+    pub align_end: &'static str,
+    pub scale: &'static str,
+    pub scale_none: &'static str,
+    pub scale_smallest: &'static str,
+    pub scale_largest: &'static str,
+    pub background: &'static str,
+    pub join: &'static str,
+    pub cancel: &'static str,
+    pub failed: &'static str,
+    pub done: &'static str,
+}
+
+pub struct SequenceText {
+    pub title: &'static str,
+    pub count: &'static str,
+    pub format: &'static str,
+    pub delay: &'static str,
+    pub plays: &'static str,
+    pub plays_hint: &'static str,
+    pub scale: &'static str,
+    pub quality: &'static str,
+    pub preview: &'static str,
+    pub export: &'static str,
+    pub cancel: &'static str,
+    pub stop_preview: &'static str,
+    pub preview_ready: &'static str,
+    pub preview_failed: &'static str,
+}
+
+pub struct ZoomText {
+    pub fit: &'static str,
+    pub fill: &'static str,
+    pub actual: &'static str,
+    pub fit_width: &'static str,
+    pub fit_height: &'static str,
+    pub lock: &'static str,
+}
+
+pub struct BackgroundText {
+    pub title: &'static str,
+    pub checkerboard: &'static str,
+    pub black: &'static str,
+    pub white: &'static str,
+    pub custom: &'static str,
+}
+
+pub struct ChannelText {
+    pub title: &'static str,
+    pub normal: &'static str,
+    pub red: &'static str,
+    pub green: &'static str,
+    pub blue: &'static str,
+    pub alpha: &'static str,
+    pub luminance: &'static str,
+    pub deuteranopia: &'static str,
+    pub protanopia: &'static str,
+    pub tritanopia: &'static str,
+}
+
+pub struct CacheText {
+    pub images: &'static str,
+    pub thumbs: &'static str,
+    pub summary: &'static str,
 }
 
 pub struct MagnifierText {
@@ -177,13 +420,34 @@ pub struct MagnifierText {
     pub hex: &'static str,
 }
 
+pub struct MeasureText {
+    pub rulers: &'static str,
+    pub tool: &'static str,
+    pub clear_guides: &'static str,
+    pub distance: &'static str,
+    pub angle: &'static str,
+    pub size: &'static str,
+}
+
+pub struct PaletteText {
+    pub title: &'static str,
+    pub export: &'static str,
+    pub copy_hint: &'static str,
+    pub empty: &'static str,
+}
+
 pub struct TextBundle {
     pub menu: MenuText,
     pub context_menu: ContextMenuText,
     pub settings: SettingsText,
     pub shortcuts: ShortcutText,
+    pub keymap: KeymapText,
+    pub mouse: MouseText,
+    pub overlay: OverlayText,
+    pub metadata: MetadataText,
     pub about: AboutText,
     pub viewer: ViewerText,
+    pub load_error: LoadErrorText,
     pub loading: LoadingText,
     pub toast: ToastText,
     pub image: ImageText,
@@ -193,7 +457,18 @@ pub struct TextBundle {
     pub ocr: OcrText,
     pub properties: PropertiesText,
     pub grid: GridText,
+    pub filter: FilterText,
+    pub batch: BatchText,
+    pub contact_sheet: ContactSheetText,
+    pub join: JoinText,
+    pub sequence: SequenceText,
+    pub zoom: ZoomText,
+    pub background: BackgroundText,
+    pub channel: ChannelText,
+    pub cache: CacheText,
     pub magnifier: MagnifierText,
+    pub measure: MeasureText,
+    pub palette: PaletteText,
 }
 
 pub const ZH_TEXT: TextBundle = TextBundle {
@@ -207,11 +482,28 @@ pub const ZH_TEXT: TextBundle = TextBundle {
         settings: "设置",
         about: "关于",
         exit: "退出",
+        recent: "最近打开",
+        recent_folders: "文件夹",
+        recent_files: "文件",
+        clear_recent: "清空记录",
+        no_recent: "暂无记录",
     },
     context_menu: ContextMenuText {
         copy: "复制",
         copy_path: "复制路径",
         properties: "属性",
+        copy_to: "复制到…",
+        move_to: "移动到…",
+        convert_to: "转换为",
+        delete: "删除",
+        compare: "对比",
+        save_as: "另存为...",
+        open_with: "打开方式",
+        show_in_folder: "在文件夹中显示",
+        no_external_apps: "可在设置中添加程序",
+        contact_sheet: "导出索引图…",
+        join: "拼接…",
+        export_animation: "导出动图…",
     },
     settings: SettingsText {
         title: "设置",
@@ -224,12 +516,86 @@ pub const ZH_TEXT: TextBundle = TextBundle {
         close: "关闭",
         apply: "应用",
         shortcut_key: "快捷键",
+        viewer: "看图",
+        smooth_magnification: "放大时平滑插值",
+        pixel_grid: "放大时显示像素网格",
+        pixel_grid_zoom: "像素网格显示倍率",
+        image_cache: "原图缓存上限",
+        thumb_cache: "缩略图缓存上限",
+        external_apps: "打开方式",
+        external_apps_hint: "命令中可使用 {path}、{dir}、{name}，不写时路径追加在末尾",
+        app_name: "名称",
+        app_command: "命令",
+        add_app: "添加程序",
+        remove: "删除",
+        no_hotkey: "无",
+        mouse: "鼠标与触控板",
     },
     shortcuts: ShortcutText {
         screenshot: "截图",
         copy_color: "复制颜色",
         modified: "请按下按键...",
     },
+    keymap: KeymapText {
+        prev_image: "上一张",
+        next_image: "下一张",
+        cache_stats: "缓存调试信息",
+        zoom_lock: "锁定缩放",
+        show_alpha: "只显示透明通道",
+        select_all: "全选",
+        clear_selection: "取消选择",
+        exit_compare: "退出对比",
+        undo: "撤销",
+        redo: "重做",
+        confirm: "完成截图",
+        cancel: "取消截图",
+        search_hint: "搜索动作或按键",
+        group_global: "全局",
+        group_viewer: "看图",
+        group_screenshot: "截图",
+        group_external: "打开方式",
+        conflict: "与“{action}”冲突",
+        reset: "恢复默认",
+        record_hint: "点击后按下新的组合键，Esc 取消，退格键清除",
+        info_overlay: "信息浮层",
+        channel_view: "切换通道视图",
+    },
+    mouse: MouseText {
+        wheel: "滚轮",
+        ctrl_wheel: "Ctrl + 滚轮",
+        shift_wheel: "Shift + 滚轮",
+        middle_click: "中键单击",
+        back_button: "侧键（后退）",
+        forward_button: "侧键（前进）",
+        double_click: "双击",
+        pinch_zoom: "双指捏合缩放",
+        trackpad_pan: "双指滑动平移",
+        zoom_sensitivity: "缩放灵敏度",
+        zoom: "缩放",
+        navigate: "上一张 / 下一张",
+        pan: "平移",
+        none: "无",
+        toggle_fit: "适应窗口 / 1:1 切换",
+        fullscreen: "全屏",
+    },
+    overlay: OverlayText {
+        title: "信息浮层",
+        corner: "位置",
+        template: "模板",
+        placeholders: "可用占位符：",
+        toggle_hint: "在单图模式下按 {key} 显示或隐藏",
+        top_left: "左上",
+        top_right: "右上",
+        bottom_left: "左下",
+        bottom_right: "右下",
+    },
+    metadata: MetadataText {
+        title: "导出时的元数据",
+        hint: "批量复制和转换格式时生效。复制图片到剪贴板和另存粘贴的图片只包含像素，不带元数据",
+        keep_all: "全部保留",
+        strip_gps: "去除位置信息",
+        strip_all: "全部去除",
+    },
     about: AboutText {
         title: "关于项目",
         description: "Rust实现的图片查看器和截图工具",
@@ -242,6 +608,17 @@ pub const ZH_TEXT: TextBundle = TextBundle {
         error: "文件损坏或格式不支持",
         drag_hint: "拖拽或打开文件夹",
         no_images: "该文件夹下没有图片",
+        truncated: "图片不完整，只显示了能解码的部分",
+        unsaved: "未保存的图片，{key} 另存为",
+    },
+    load_error: LoadErrorText {
+        file_not_found: "文件不存在：{detail}",
+        read_error: "文件读取失败：{detail}",
+        unsupported_format: "不支持的图片格式：{detail}",
+        decode_error: "图片解码失败：{detail}",
+        truncated: "文件不完整，可能尚未下载完成",
+        unsupported_feature: "图片使用了尚不支持的编码特性：{detail}",
+        too_large: "图片尺寸过大（{width}×{height}），单边最大支持 {max} 像素",
     },
     loading: LoadingText {
         parsing: "正在解析像素...",
@@ -251,6 +628,11 @@ pub const ZH_TEXT: TextBundle = TextBundle {
         copy_failed: "复制失败",
         copying: "正在复制中...",
         launch_on_startup_failed: "设置开机自启失败:",
+        saved: "已保存到 {path}",
+        save_failed: "保存失败",
+        paste_empty: "剪贴板中没有图片或图片文件",
+        recent_missing: "该路径已不存在",
+        launch_failed: "无法启动程序",
     },
     image: ImageText {
         properties: "属性",
@@ -283,11 +665,11 @@ pub const ZH_TEXT: TextBundle = TextBundle {
         start_screenshot: "开始截图",
         select_region: "左键拖拽 : 选择区域",
         exit_with_right_click: "右键 : 退出截图",
-        esc: "Esc : 退出截图",
-        undo: "Ctrl+Z : 撤销上一步绘制",
-        redo: "Ctrl+Y : 重做上一步绘制",
+        esc: "退出截图",
+        undo: "撤销上一步绘制",
+        redo: "重做上一步绘制",
         copy_color: "复制颜色",
-        copy_to_clipboard: "Enter : 复制截图到剪贴板",
+        copy_to_clipboard: "复制截图到剪贴板",
         tools: "【工具说明】",
     },
     ocr: OcrText {
@@ -298,14 +680,150 @@ pub const ZH_TEXT: TextBundle = TextBundle {
     },
     properties: PropertiesText {
         no_image: "未加载图片。",
+        extension_mismatch: "扩展名与实际格式不符，实际为 {format}",
+        has_location: "此图片包含 GPS 位置信息，分享前请注意",
     },
     grid: GridText {
         loading: "加载中...",
+        thumb_size: "缩略图大小（Ctrl + 滚轮）",
+        captions: "显示信息",
+        sort: "排序",
+        sort_name: "文件名",
+        sort_modified: "修改时间",
+        sort_size: "文件大小",
+    },
+    filter: FilterText {
+        name_hint: "筛选文件名，支持 * ?",
+        extension_hint: "扩展名，如 png, jpg",
+        more: "更多条件",
+        width: "宽度",
+        height: "高度",
+        size_kb: "大小 (KB)",
+        date: "修改日期",
+        date_hint: "YYYY-MM-DD",
+        apply: "应用",
+        clear: "清除筛选",
+        no_match: "没有符合筛选条件的图片",
+        probing: "正在读取文件信息…",
+        matched: "筛选出 {count} 张图片",
+        probe_cancelled: "已停止读取文件信息",
+    },
+    batch: BatchText {
+        selected: "已选择 {count} 项",
+        processing: "处理中…",
+        done: "已完成 {count} 项",
+        partial: "{ok} 项完成，{failed} 项失败",
+        delete_title: "删除文件",
+        delete_confirm: "确定要将选中的 {count} 个文件移到回收站吗？",
+        confirm_delete: "删除",
+        cancel: "取消",
+    },
+    contact_sheet: ContactSheetText {
+        title: "导出索引图",
+        count: "共 {count} 张图片",
+        format: "格式",
+        page_size: "页面尺寸",
+        page_auto: "自动（长图，过长时分页）",
+        landscape: "横向",
+        columns: "列数",
+        cell_size: "单元格大小",
+        margin: "页边距",
+        captions: "标题",
+        caption_name: "文件名",
+        caption_date: "日期",
+        caption_dimensions: "尺寸",
+        export: "导出",
+        cancel: "取消",
+    },
+    join: JoinText {
+        title: "拼接图片",
+        count: "共 {count} 张图片",
+        direction: "方向",
+        horizontal: "横向",
+        vertical: "纵向",
+        grid: "网格",
+        columns: "列数",
+        spacing: "间距",
+        align: "对齐",
+        align_start: "顶部 / 左侧",
+        align_center: "居中",
+        align_end: "底部 / 右侧",
+        scale: "统一尺寸",
+        scale_none: "保持原尺寸",
+        scale_smallest: "缩小到最小的一张",
+        scale_largest: "放大到最大的一张",
+        background: "背景色",
+        join: "拼接",
+        cancel: "取消",
+        failed: "拼接失败",
+        done: "已拼接为新图片",
+    },
+    sequence: SequenceText {
+        title: "导出动图",
+        count: "共 {count} 帧",
+        format: "格式",
+        delay: "帧间隔",
+        plays: "播放次数",
+        plays_hint: "0 表示无限循环",
+        scale: "缩放",
+        quality: "调色板质量",
+        preview: "预览",
+        export: "导出…",
+        cancel: "取消",
+        stop_preview: "结束预览",
+        preview_ready: "预览已就绪，按 Esc 结束",
+        preview_failed: "预览失败",
+    },
+    zoom: ZoomText {
+        fit: "适应窗口",
+        fill: "填满窗口",
+        actual: "实际像素 (1:1)",
+        fit_width: "适应宽度",
+        fit_height: "适应高度",
+        lock: "锁定缩放和位置 (L)",
+    },
+    background: BackgroundText {
+        title: "背景",
+        checkerboard: "棋盘格",
+        black: "黑色",
+        white: "白色",
+        custom: "自定义颜色",
+    },
+    channel: ChannelText {
+        title: "通道视图",
+        normal: "正常",
+        red: "红色通道",
+        green: "绿色通道",
+        blue: "蓝色通道",
+        alpha: "透明通道",
+        luminance: "亮度",
+        deuteranopia: "绿色盲模拟",
+        protanopia: "红色盲模拟",
+        tritanopia: "蓝色盲模拟",
+    },
+    cache: CacheText {
+        images: "原图缓存",
+        thumbs: "缩略图缓存",
+        summary: "{count} 项 · {used} / {budget} MB · 命中率 {rate}%",
     },
     magnifier: MagnifierText {
         pos: "坐标: ",
         hex: "色值: ",
     },
+    measure: MeasureText {
+        rulers: "标尺和参考线",
+        tool: "测量工具",
+        clear_guides: "清除参考线",
+        distance: "距离",
+        angle: "角度",
+        size: "尺寸",
+    },
+    palette: PaletteText {
+        title: "主色",
+        export: "导出调色板",
+        copy_hint: "点击复制",
+        empty: "没有可提取的颜色",
+    },
 };
 
 pub const EN_TEXT: TextBundle = TextBundle {
@@ -319,11 +837,28 @@ pub const EN_TEXT: TextBundle = TextBundle {
         settings: "Settings",
         about: "About",
         exit: "Exit",
+        recent: "Recent",
+        recent_folders: "Folders",
+        recent_files: "Files",
+        clear_recent: "Clear recent",
+        no_recent: "Nothing yet",
     },
     context_menu: ContextMenuText {
         copy: "Copy",
         copy_path: "Copy Path",
         properties: "Properties",
+        copy_to: "Copy to...",
+        move_to: "Move to...",
+        convert_to: "Convert to",
+        delete: "Delete",
+        compare: "Compare",
+        save_as: "Save as...",
+        open_with: "Open with",
+        show_in_folder: "Show in file manager",
+        no_external_apps: "Add programs in Settings",
+        contact_sheet: "Contact sheet…",
+        join: "Join…",
+        export_animation: "Export animation…",
     },
     settings: SettingsText {
         title: "Settings",
@@ -336,12 +871,86 @@ pub const EN_TEXT: TextBundle = TextBundle {
         close: "Close",
         apply: "Apply",
         shortcut_key: "Keyboard Shortcut",
+        viewer: "Viewer",
+        smooth_magnification: "Smooth magnification",
+        pixel_grid: "Show pixel grid when zoomed in",
+        pixel_grid_zoom: "Pixel grid zoom threshold",
+        image_cache: "Image cache limit",
+        thumb_cache: "Thumbnail cache limit",
+        external_apps: "Open with",
+        external_apps_hint: "Commands may use {path}, {dir} and {name}; without them the path is appended",
+        app_name: "Name",
+        app_command: "Command",
+        add_app: "Add program",
+        remove: "Remove",
+        no_hotkey: "None",
+        mouse: "Mouse & Trackpad",
     },
     shortcuts: ShortcutText {
         screenshot: "Screenshot",
         copy_color: "Copy Color",
         modified: "Please Press The Key…",
     },
+    keymap: KeymapText {
+        prev_image: "Previous image",
+        next_image: "Next image",
+        cache_stats: "Cache statistics",
+        zoom_lock: "Lock zoom",
+        show_alpha: "Show alpha channel",
+        select_all: "Select all",
+        clear_selection: "Clear selection",
+        exit_compare: "Exit compare",
+        undo: "Undo",
+        redo: "Redo",
+        confirm: "Finish screenshot",
+        cancel: "Cancel screenshot",
+        search_hint: "Search actions or keys",
+        group_global: "Global",
+        group_viewer: "Viewer",
+        group_screenshot: "Screenshot",
+        group_external: "Open with",
+        conflict: "Conflicts with \"{action}\"",
+        reset: "Reset",
+        record_hint: "Click, then press a new combination. Esc cancels, Backspace clears",
+        info_overlay: "Info overlay",
+        channel_view: "Cycle channel view",
+    },
+    mouse: MouseText {
+        wheel: "Wheel",
+        ctrl_wheel: "Ctrl + Wheel",
+        shift_wheel: "Shift + Wheel",
+        middle_click: "Middle click",
+        back_button: "Back button",
+        forward_button: "Forward button",
+        double_click: "Double click",
+        pinch_zoom: "Pinch to zoom",
+        trackpad_pan: "Two-finger pan",
+        zoom_sensitivity: "Zoom sensitivity",
+        zoom: "Zoom",
+        navigate: "Previous / next image",
+        pan: "Pan",
+        none: "None",
+        toggle_fit: "Toggle fit / 1:1",
+        fullscreen: "Fullscreen",
+    },
+    overlay: OverlayText {
+        title: "Info overlay",
+        corner: "Position",
+        template: "Template",
+        placeholders: "Placeholders:",
+        toggle_hint: "Press {key} in single view to show or hide",
+        top_left: "Top left",
+        top_right: "Top right",
+        bottom_left: "Bottom left",
+        bottom_right: "Bottom right",
+    },
+    metadata: MetadataText {
+        title: "Metadata on export",
+        hint: "Applies to batch copy and convert. Copying an image to the clipboard and saving pasted images only write pixels, never metadata",
+        keep_all: "Keep all",
+        strip_gps: "Strip GPS",
+        strip_all: "Strip all",
+    },
     about: AboutText {
         title: "About",
         description: "Image viewer and screenshot tool implemented in Rust",
@@ -354,6 +963,17 @@ pub const EN_TEXT: TextBundle = TextBundle {
         error: "File damaged or format not supported",
         drag_hint: "Drag and drop or open a folder",
         no_images: "No images in this folder",
+        truncated: "Image is incomplete, showing only the part that could be decoded",
+        unsaved: "Unsaved image, press {key} to save",
+    },
+    load_error: LoadErrorText {
+        file_not_found: "File not found: {detail}",
+        read_error: "Failed to read file: {detail}",
+        unsupported_format: "Unsupported image format: {detail}",
+        decode_error: "Failed to decode image: {detail}",
+        truncated: "The file is incomplete, it may not have finished downloading",
+        unsupported_feature: "The image uses an unsupported codec feature: {detail}",
+        too_large: "Image is too large ({width}×{height}), each side can be at most {max} pixels",
     },
     loading: LoadingText {
         parsing: "Parsing pixels...",
@@ -363,6 +983,11 @@ pub const EN_TEXT: TextBundle = TextBundle {
         copy_failed: "Copy failed",
         copying: "Copying...",
         launch_on_startup_failed: "Failed to update run at startup:",
+        saved: "Saved to {path}",
+        save_failed: "Failed to save",
+        paste_empty: "No image or image file in the clipboard",
+        recent_missing: "This path no longer exists",
+        launch_failed: "Failed to launch program",
     },
     image: ImageText {
         properties: "Properties",
@@ -395,11 +1020,11 @@ pub const EN_TEXT: TextBundle = TextBundle {
         start_screenshot: "Start Screenshot",
         select_region: "Left Click / Drag : Select Area",
         exit_with_right_click: "Right Click : Exit Screenshot",
-        esc: "Esc : Exit Screenshot",
-        undo: "Ctrl + Z : Undo Drawing",
-        redo: "Ctrl + Y : Redo Drawing",
+        esc: "Exit Screenshot",
+        undo: "Undo Drawing",
+        redo: "Redo Drawing",
         copy_color: "Copy Color",
-        copy_to_clipboard: "Enter : Copy Screenshot to Clipboard",
+        copy_to_clipboard: "Copy Screenshot to Clipboard",
         tools: "[ Tools ]",
     },
     ocr: OcrText {
@@ -410,14 +1035,150 @@ pub const EN_TEXT: TextBundle = TextBundle {
     },
     properties: PropertiesText {
         no_image: "No image loaded.",
+        extension_mismatch: "File extension does not match its content, actual format is {format}",
+        has_location: "This image contains GPS location data. Check before sharing",
     },
     grid: GridText {
         loading: "Loading...",
+        thumb_size: "Thumbnail size (Ctrl + wheel)",
+        captions: "Captions",
+        sort: "Sort",
+        sort_name: "Name",
+        sort_modified: "Date modified",
+        sort_size: "File size",
+    },
+    filter: FilterText {
+        name_hint: "Filter by name, * ? supported",
+        extension_hint: "Extensions, e.g. png, jpg",
+        more: "More",
+        width: "Width",
+        height: "Height",
+        size_kb: "Size (KB)",
+        date: "Modified",
+        date_hint: "YYYY-MM-DD",
+        apply: "Apply",
+        clear: "Clear Filter",
+        no_match: "No images match the filter",
+        probing: "Reading file info…",
+        matched: "{count} images match",
+        probe_cancelled: "Stopped reading file info",
+    },
+    batch: BatchText {
+        selected: "{count} selected",
+        processing: "Processing...",
+        done: "{count} done",
+        partial: "{ok} done, {failed} failed",
+        delete_title: "Delete Files",
+        delete_confirm: "Move {count} selected file(s) to the Recycle Bin?",
+        confirm_delete: "Delete",
+        cancel: "Cancel",
+    },
+    contact_sheet: ContactSheetText {
+        title: "Contact Sheet",
+        count: "{count} images",
+        format: "Format",
+        page_size: "Page size",
+        page_auto: "Auto (long image, split when too tall)",
+        landscape: "Landscape",
+        columns: "Columns",
+        cell_size: "Cell size",
+        margin: "Margin",
+        captions: "Captions",
+        caption_name: "File name",
+        caption_date: "Date",
+        caption_dimensions: "Dimensions",
+        export: "Export",
+        cancel: "Cancel",
+    },
+    join: JoinText {
+        title: "Join Images",
+        count: "{count} images",
+        direction: "Direction",
+        horizontal: "Horizontal",
+        vertical: "Vertical",
+        grid: "Grid",
+        columns: "Columns",
+        spacing: "Spacing",
+        align: "Alignment",
+        align_start: "Top / left",
+        align_center: "Center",
+        align_end: "Bottom / right",
+        scale: "Common size",
+        scale_none: "Keep original",
+        scale_smallest: "Shrink to smallest",
+        scale_largest: "Enlarge to largest",
+        background: "Background",
+        join: "Join",
+        cancel: "Cancel",
+        failed: "Failed to join images",
+        done: "Images joined",
+    },
+    sequence: SequenceText {
+        title: "Export Animation",
+        count: "{count} frames",
+        format: "Format",
+        delay: "Frame delay",
+        plays: "Play count",
+        plays_hint: "0 loops forever",
+        scale: "Scale",
+        quality: "Palette quality",
+        preview: "Preview",
+        export: "Export…",
+        cancel: "Cancel",
+        stop_preview: "Stop preview",
+        preview_ready: "Preview ready, press Esc to stop",
+        preview_failed: "Preview failed",
+    },
+    zoom: ZoomText {
+        fit: "Fit to Window",
+        fill: "Fill Window",
+        actual: "Actual Pixels (1:1)",
+        fit_width: "Fit Width",
+        fit_height: "Fit Height",
+        lock: "Lock Zoom and Position (L)",
+    },
+    background: BackgroundText {
+        title: "Background",
+        checkerboard: "Checkerboard",
+        black: "Black",
+        white: "White",
+        custom: "Custom Color",
+    },
+    channel: ChannelText {
+        title: "Channel view",
+        normal: "Normal",
+        red: "Red channel",
+        green: "Green channel",
+        blue: "Blue channel",
+        alpha: "Alpha channel",
+        luminance: "Luminance",
+        deuteranopia: "Deuteranopia simulation",
+        protanopia: "Protanopia simulation",
+        tritanopia: "Tritanopia simulation",
+    },
+    cache: CacheText {
+        images: "Images",
+        thumbs: "Thumbnails",
+        summary: "{count} items · {used} / {budget} MB · hit rate {rate}%",
     },
     magnifier: MagnifierText {
         pos: "POS: ",
         hex: "HEX: ",
     },
+    measure: MeasureText {
+        rulers: "Rulers and guides",
+        tool: "Measure tool",
+        clear_guides: "Clear guides",
+        distance: "Distance",
+        angle: "Angle",
+        size: "Size",
+    },
+    palette: PaletteText {
+        title: "Dominant colors",
+        export: "Export palette",
+        copy_hint: "Click to copy",
+        empty: "No colors to extract",
+    },
 };
 
 pub const JA_TEXT: TextBundle = TextBundle {
@@ -431,11 +1192,28 @@ pub const JA_TEXT: TextBundle = TextBundle {
         settings: "設定",
         about: "について",
         exit: "終了",
+        recent: "最近使った項目",
+        recent_folders: "フォルダー",
+        recent_files: "ファイル",
+        clear_recent: "履歴を消去",
+        no_recent: "履歴なし",
     },
     context_menu: ContextMenuText {
         copy: "コピー",
         copy_path: "パスコピー",
         properties: "プロパティ",
+        copy_to: "コピー先...",
+        move_to: "移動先...",
+        convert_to: "形式を変換",
+        delete: "削除",
+        compare: "比較",
+        save_as: "名前を付けて保存...",
+        open_with: "プログラムから開く",
+        show_in_folder: "フォルダーに表示",
+        no_external_apps: "設定でプログラムを追加",
+        contact_sheet: "コンタクトシート…",
+        join: "結合…",
+        export_animation: "アニメーションを書き出し…",
     },
     settings: SettingsText {
         title: "設定",
@@ -448,12 +1226,86 @@ pub const JA_TEXT: TextBundle = TextBundle {
         close: "閉じる",
         apply: "設定",
         shortcut_key: "ショートカットキー",
+        viewer: "ビューアー",
+        smooth_magnification: "拡大時にスムージング",
+        pixel_grid: "拡大時にピクセルグリッドを表示",
+        pixel_grid_zoom: "ピクセルグリッドの表示倍率",
+        image_cache: "画像キャッシュ上限",
+        thumb_cache: "サムネイルキャッシュ上限",
+        external_apps: "プログラムから開く",
+        external_apps_hint: "コマンドでは {path}・{dir}・{name} が使えます。省略時はパスを末尾に追加",
+        app_name: "名前",
+        app_command: "コマンド",
+        add_app: "プログラムを追加",
+        remove: "削除",
+        no_hotkey: "なし",
+        mouse: "マウスとトラックパッド",
     },
     shortcuts: ShortcutText {
         screenshot: "スクリーンショット",
         copy_color: "カラーコピー",
         modified: "キーを押してください…",
     },
+    keymap: KeymapText {
+        prev_image: "前の画像",
+        next_image: "次の画像",
+        cache_stats: "キャッシュ統計",
+        zoom_lock: "ズームを固定",
+        show_alpha: "アルファチャンネルを表示",
+        select_all: "すべて選択",
+        clear_selection: "選択を解除",
+        exit_compare: "比較を終了",
+        undo: "元に戻す",
+        redo: "やり直し",
+        confirm: "スクリーンショットを確定",
+        cancel: "スクリーンショットをキャンセル",
+        search_hint: "操作またはキーを検索",
+        group_global: "グローバル",
+        group_viewer: "ビューアー",
+        group_screenshot: "スクリーンショット",
+        group_external: "プログラムから開く",
+        conflict: "「{action}」と競合しています",
+        reset: "既定に戻す",
+        record_hint: "クリックして新しいキーを押します。Esc でキャンセル、Backspace で消去",
+        info_overlay: "情報オーバーレイ",
+        channel_view: "チャンネル表示を切り替え",
+    },
+    mouse: MouseText {
+        wheel: "ホイール",
+        ctrl_wheel: "Ctrl + ホイール",
+        shift_wheel: "Shift + ホイール",
+        middle_click: "中クリック",
+        back_button: "サイドボタン（戻る）",
+        forward_button: "サイドボタン（進む）",
+        double_click: "ダブルクリック",
+        pinch_zoom: "ピンチでズーム",
+        trackpad_pan: "2本指スクロールで移動",
+        zoom_sensitivity: "ズーム感度",
+        zoom: "ズーム",
+        navigate: "前 / 次の画像",
+        pan: "移動",
+        none: "なし",
+        toggle_fit: "フィット / 1:1 切替",
+        fullscreen: "全画面",
+    },
+    overlay: OverlayText {
+        title: "情報オーバーレイ",
+        corner: "位置",
+        template: "テンプレート",
+        placeholders: "使用できるプレースホルダー：",
+        toggle_hint: "単一表示で {key} を押して表示 / 非表示",
+        top_left: "左上",
+        top_right: "右上",
+        bottom_left: "左下",
+        bottom_right: "右下",
+    },
+    metadata: MetadataText {
+        title: "書き出し時のメタデータ",
+        hint: "一括コピーと形式変換に適用されます。画像のクリップボードへのコピーと貼り付け画像の保存はピクセルのみで、メタデータは含まれません",
+        keep_all: "すべて保持",
+        strip_gps: "位置情報を削除",
+        strip_all: "すべて削除",
+    },
     about: AboutText {
         title: "プロジェクトについて",
         description: "Rust製の画像ビューアおよびスクリーンショットツール",
@@ -466,6 +1318,17 @@ pub const JA_TEXT: TextBundle = TextBundle {
         error: "ファイルが破損しているか、形式がサポートされていません",
         drag_hint: "ドラッグ＆ドロップまたはフォルダを開く",
         no_images: "このフォルダに画像はありません",
+        truncated: "画像が不完全です。デコードできた部分のみ表示しています",
+        unsaved: "未保存の画像です。{key} で保存",
+    },
+    load_error: LoadErrorText {
+        file_not_found: "ファイルが存在しません：{detail}",
+        read_error: "ファイルの読み込みに失敗しました：{detail}",
+        unsupported_format: "サポートされていない画像形式：{detail}",
+        decode_error: "画像のデコードに失敗しました：{detail}",
+        truncated: "ファイルが不完全です。ダウンロードが完了していない可能性があります",
+        unsupported_feature: "画像がサポートされていないエンコード機能を使用しています：{detail}",
+        too_large: "画像が大きすぎます（{width}×{height}）。一辺の最大は {max} ピクセルです",
     },
     loading: LoadingText {
         parsing: "ピクセルを解析中...",
@@ -475,6 +1338,11 @@ pub const JA_TEXT: TextBundle = TextBundle {
         copy_failed: "コピーに失敗しました",
         copying: "コピー中...",
         launch_on_startup_failed: "起動時実行の設定に失敗しました:",
+        saved: "{path} に保存しました",
+        save_failed: "保存に失敗しました",
+        paste_empty: "クリップボードに画像または画像ファイルがありません",
+        recent_missing: "このパスは存在しません",
+        launch_failed: "プログラムを起動できません",
     },
     image: ImageText {
         properties: "プロパティーズ",
@@ -507,11 +1375,11 @@ pub const JA_TEXT: TextBundle = TextBundle {
         start_screenshot: "スクリーンショット開始",
         select_region: "左クリック / ドラッグ : 範囲選択",
         exit_with_right_click: "右クリック : スクリーンショットを終了",
-        esc: "Esc : スクリーンショットを終了",
-        undo: "Ctrl + Z : 元に戻す",
-        redo: "Ctrl + Y : やり直す",
+        esc: "スクリーンショットを終了",
+        undo: "元に戻す",
+        redo: "やり直す",
         copy_color: "色をコピー",
-        copy_to_clipboard: "Enter : スクリーンショットをクリップボードにコピー",
+        copy_to_clipboard: "スクリーンショットをクリップボードにコピー",
         tools: "【ツール】",
     },
     ocr: OcrText {
@@ -522,14 +1390,150 @@ pub const JA_TEXT: TextBundle = TextBundle {
     },
     properties: PropertiesText {
         no_image: "画像が読み込まれていません。",
+        extension_mismatch: "拡張子が実際の形式と一致しません（実際は {format}）",
+        has_location: "この画像には GPS 位置情報が含まれています。共有前にご注意ください",
     },
     grid: GridText {
         loading: "読み込み中...",
+        thumb_size: "サムネイルサイズ（Ctrl + ホイール）",
+        captions: "キャプション",
+        sort: "並べ替え",
+        sort_name: "名前",
+        sort_modified: "更新日時",
+        sort_size: "ファイルサイズ",
+    },
+    filter: FilterText {
+        name_hint: "ファイル名で絞り込み（* ? 対応）",
+        extension_hint: "拡張子（例: png, jpg）",
+        more: "詳細条件",
+        width: "幅",
+        height: "高さ",
+        size_kb: "サイズ (KB)",
+        date: "更新日",
+        date_hint: "YYYY-MM-DD",
+        apply: "適用",
+        clear: "絞り込み解除",
+        no_match: "条件に一致する画像はありません",
+        probing: "ファイル情報を読み込み中…",
+        matched: "{count} 枚が該当",
+        probe_cancelled: "ファイル情報の読み込みを中止しました",
+    },
+    batch: BatchText {
+        selected: "{count} 件選択",
+        processing: "処理中...",
+        done: "{count} 件完了",
+        partial: "{ok} 件完了、{failed} 件失敗",
+        delete_title: "ファイルを削除",
+        delete_confirm: "選択した {count} 件のファイルをごみ箱に移動しますか？",
+        confirm_delete: "削除",
+        cancel: "キャンセル",
+    },
+    contact_sheet: ContactSheetText {
+        title: "コンタクトシート",
+        count: "{count} 枚の画像",
+        format: "形式",
+        page_size: "用紙サイズ",
+        page_auto: "自動（縦長、長すぎると分割）",
+        landscape: "横向き",
+        columns: "列数",
+        cell_size: "セルサイズ",
+        margin: "余白",
+        captions: "キャプション",
+        caption_name: "ファイル名",
+        caption_date: "日付",
+        caption_dimensions: "サイズ",
+        export: "書き出し",
+        cancel: "キャンセル",
+    },
+    join: JoinText {
+        title: "画像を結合",
+        count: "{count} 枚の画像",
+        direction: "方向",
+        horizontal: "横",
+        vertical: "縦",
+        grid: "グリッド",
+        columns: "列数",
+        spacing: "間隔",
+        align: "配置",
+        align_start: "上 / 左",
+        align_center: "中央",
+        align_end: "下 / 右",
+        scale: "サイズを揃える",
+        scale_none: "元のサイズ",
+        scale_smallest: "最小に合わせる",
+        scale_largest: "最大に合わせる",
+        background: "背景色",
+        join: "結合",
+        cancel: "キャンセル",
+        failed: "結合に失敗しました",
+        done: "画像を結合しました",
+    },
+    sequence: SequenceText {
+        title: "アニメーションを書き出し",
+        count: "{count} フレーム",
+        format: "形式",
+        delay: "フレーム間隔",
+        plays: "再生回数",
+        plays_hint: "0 で無限ループ",
+        scale: "拡大率",
+        quality: "パレット品質",
+        preview: "プレビュー",
+        export: "書き出し…",
+        cancel: "キャンセル",
+        stop_preview: "プレビューを終了",
+        preview_ready: "プレビュー準備完了、Esc で終了",
+        preview_failed: "プレビューに失敗しました",
+    },
+    zoom: ZoomText {
+        fit: "ウィンドウに合わせる",
+        fill: "ウィンドウを埋める",
+        actual: "実際のピクセル (1:1)",
+        fit_width: "幅に合わせる",
+        fit_height: "高さに合わせる",
+        lock: "ズームと位置を固定 (L)",
+    },
+    background: BackgroundText {
+        title: "背景",
+        checkerboard: "チェッカーボード",
+        black: "黒",
+        white: "白",
+        custom: "カスタムカラー",
+    },
+    channel: ChannelText {
+        title: "チャンネル表示",
+        normal: "通常",
+        red: "赤チャンネル",
+        green: "緑チャンネル",
+        blue: "青チャンネル",
+        alpha: "アルファチャンネル",
+        luminance: "輝度",
+        deuteranopia: "2 型色覚シミュレーション",
+        protanopia: "1 型色覚シミュレーション",
+        tritanopia: "3 型色覚シミュレーション",
+    },
+    cache: CacheText {
+        images: "画像キャッシュ",
+        thumbs: "サムネイル",
+        summary: "{count} 件 · {used} / {budget} MB · ヒット率 {rate}%",
     },
     magnifier: MagnifierText {
         pos: "座標: ",
         hex: "カラー: ",
     },
+    measure: MeasureText {
+        rulers: "ルーラーとガイド",
+        tool: "計測ツール",
+        clear_guides: "ガイドを消去",
+        distance: "距離",
+        angle: "角度",
+        size: "サイズ",
+    },
+    palette: PaletteText {
+        title: "主要色",
+        export: "パレットを書き出す",
+        copy_hint: "クリックでコピー",
+        empty: "抽出できる色がありません",
+    },
 };
 
 pub fn get_text(lang: Language) -> &'static TextBundle {
@@ -1,5 +1,6 @@
+use crate::core::keymap::Keymap;
 use crate::i18n::lang::Language;
-use egui::{Context, Id};
+use egui::{Context, Id, TextureFilter, TextureOptions};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::sync::RwLock;
@@ -7,7 +8,7 @@ use std::{fs, path::PathBuf};
 
 /// 获取配置目录
 /// 优先使用系统配置目录，如果不存在则使用exe目录（向后兼容）
-fn get_config_dir() -> PathBuf {
+pub fn get_config_dir() -> PathBuf {
     // 首先尝试系统配置目录
     if let Some(config_dir) = dirs::config_dir() {
         let app_config_dir = config_dir.join("CloverViewer");
@@ -50,6 +51,314 @@ impl Default for HotkeysConfig {
     }
 }
 
+/// 网格视图的显示设置
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct GridConfig {
+    /// 两端对齐布局的目标行高
+    #[serde(default = "default_grid_row_height")]
+    pub row_height: f32,
+    /// 缩略图下方显示文件名、尺寸和格式
+    #[serde(default)]
+    pub show_captions: bool,
+}
+
+pub const GRID_ROW_HEIGHT_RANGE: std::ops::RangeInclusive<f32> = 80.0..=320.0;
+
+fn default_grid_row_height() -> f32 {
+    150.0
+}
+
+impl Default for GridConfig {
+    fn default() -> Self {
+        Self {
+            row_height: default_grid_row_height(),
+            show_captions: false,
+        }
+    }
+}
+
+/// 图片缓存的内存预算，单位 MB。纹理和像素快照都计入
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct CacheConfig {
+    #[serde(default = "default_image_cache_mb")]
+    pub image_mb: u32,
+    #[serde(default = "default_thumb_cache_mb")]
+    pub thumb_mb: u32,
+}
+
+pub const IMAGE_CACHE_MB_RANGE: std::ops::RangeInclusive<u32> = 256..=8192;
+pub const THUMB_CACHE_MB_RANGE: std::ops::RangeInclusive<u32> = 64..=2048;
+
+fn default_image_cache_mb() -> u32 {
+    1024
+}
+
+fn default_thumb_cache_mb() -> u32 {
+    512
+}
+
+impl CacheConfig {
+    pub fn image_bytes(&self) -> usize {
+        self.image_mb as usize * 1024 * 1024
+    }
+
+    pub fn thumb_bytes(&self) -> usize {
+        self.thumb_mb as usize * 1024 * 1024
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            image_mb: default_image_cache_mb(),
+            thumb_mb: default_thumb_cache_mb(),
+        }
+    }
+}
+
+/// 图片透明区域下方的背景
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum ImageBackground {
+    #[default]
+    Checkerboard,
+    Black,
+    White,
+    Custom,
+}
+
+impl ImageBackground {
+    pub const ALL: [Self; 4] = [Self::Checkerboard, Self::Black, Self::White, Self::Custom];
+}
+
+/// 信息浮层所在的角落
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum OverlayCorner {
+    TopLeft,
+    TopRight,
+    #[default]
+    BottomLeft,
+    BottomRight,
+}
+
+impl OverlayCorner {
+    pub const ALL: [Self; 4] = [
+        Self::TopLeft,
+        Self::TopRight,
+        Self::BottomLeft,
+        Self::BottomRight,
+    ];
+}
+
+/// 单图查看的显示设置
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct ViewerConfig {
+    /// 放大时平滑插值；关闭后放大按最近邻采样，适合像素图
+    #[serde(default = "default_true")]
+    pub smooth_magnification: bool,
+    /// 缩放达到阈值后显示像素网格
+    #[serde(default = "default_pixel_grid")]
+    pub pixel_grid: bool,
+    #[serde(default = "default_pixel_grid_zoom")]
+    pub pixel_grid_zoom: f32,
+    #[serde(default)]
+    pub background: ImageBackground,
+    /// 背景为自定义颜色时使用的 sRGB 颜色
+    #[serde(default = "default_background_color")]
+    pub background_color: [u8; 3],
+    /// 信息浮层模板，占位符见 `overlay::fill_template`
+    #[serde(default = "default_overlay_template")]
+    pub overlay_template: String,
+    #[serde(default)]
+    pub overlay_corner: OverlayCorner,
+}
+
+pub const PIXEL_GRID_ZOOM_RANGE: std::ops::RangeInclusive<f32> = 4.0..=10.0;
+
+fn default_pixel_grid() -> bool {
+    true
+}
+
+fn default_pixel_grid_zoom() -> f32 {
+    8.0
+}
+
+fn default_background_color() -> [u8; 3] {
+    [64, 64, 64]
+}
+
+fn default_overlay_template() -> String {
+    "{name}  {index}/{total}\n{width} x {height}  {zoom}  {size}\n{exposure}  {aperture}  {iso}  {focal}\n{date}"
+        .to_string()
+}
+
+impl ViewerConfig {
+    /// 放大和缩小分别设置采样方式，由 GPU 按实际缩放比例选用
+    pub fn texture_options(&self) -> TextureOptions {
+        let magnification = if self.smooth_magnification {
+            TextureFilter::Linear
+        } else {
+            TextureFilter::Nearest
+        };
+        TextureOptions {
+            magnification,
+            minification: TextureFilter::Linear,
+            ..TextureOptions::default()
+        }
+    }
+}
+
+impl Default for ViewerConfig {
+    fn default() -> Self {
+        Self {
+            smooth_magnification: default_true(),
+            pixel_grid: default_pixel_grid(),
+            pixel_grid_zoom: default_pixel_grid_zoom(),
+            background: ImageBackground::default(),
+            background_color: default_background_color(),
+            overlay_template: default_overlay_template(),
+            overlay_corner: OverlayCorner::default(),
+        }
+    }
+}
+
+/// 单图模式下滚轮的作用
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum WheelAction {
+    Zoom,
+    /// 上一张 / 下一张
+    Navigate,
+    /// 平移图片，配合 Shift 时为水平方向
+    Pan,
+    None,
+}
+
+impl WheelAction {
+    pub const ALL: [Self; 4] = [Self::Zoom, Self::Navigate, Self::Pan, Self::None];
+}
+
+/// 单图模式下鼠标按键和双击的作用
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ClickAction {
+    None,
+    /// 在适应窗口和 1:1 之间切换
+    ToggleFit,
+    Fit,
+    Actual,
+    Fullscreen,
+    PrevImage,
+    NextImage,
+}
+
+impl ClickAction {
+    pub const ALL: [Self; 7] = [
+        Self::None,
+        Self::ToggleFit,
+        Self::Fit,
+        Self::Actual,
+        Self::Fullscreen,
+        Self::PrevImage,
+        Self::NextImage,
+    ];
+}
+
+/// 鼠标和触控板手势
+#[derive(Serialize, Deserialize, PartialEq, Clone)]
+pub struct MouseConfig {
+    #[serde(default = "default_wheel")]
+    pub wheel: WheelAction,
+    #[serde(default = "default_wheel")]
+    pub ctrl_wheel: WheelAction,
+    #[serde(default = "default_shift_wheel")]
+    pub shift_wheel: WheelAction,
+    #[serde(default = "default_click")]
+    pub middle_click: ClickAction,
+    /// 侧键（后退 / 前进）
+    #[serde(default = "default_back_button")]
+    pub back_button: ClickAction,
+    #[serde(default = "default_forward_button")]
+    pub forward_button: ClickAction,
+    #[serde(default = "default_double_click")]
+    pub double_click: ClickAction,
+    /// 触控板双指捏合缩放
+    #[serde(default = "default_true")]
+    pub pinch_zoom: bool,
+    /// 触控板双指滑动平移图片，不受滚轮设置影响。
+    /// 高精度滚轮同样按点上报，开启后这类滚轮也会平移，因此默认关闭
+    #[serde(default)]
+    pub trackpad_pan: bool,
+}
+
+fn default_wheel() -> WheelAction {
+    WheelAction::Zoom
+}
+
+fn default_shift_wheel() -> WheelAction {
+    WheelAction::Pan
+}
+
+fn default_click() -> ClickAction {
+    ClickAction::None
+}
+
+fn default_back_button() -> ClickAction {
+    ClickAction::PrevImage
+}
+
+fn default_forward_button() -> ClickAction {
+    ClickAction::NextImage
+}
+
+fn default_double_click() -> ClickAction {
+    ClickAction::ToggleFit
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for MouseConfig {
+    fn default() -> Self {
+        Self {
+            wheel: default_wheel(),
+            ctrl_wheel: default_wheel(),
+            shift_wheel: default_shift_wheel(),
+            middle_click: default_click(),
+            back_button: default_back_button(),
+            forward_button: default_forward_button(),
+            double_click: default_double_click(),
+            pinch_zoom: default_true(),
+            trackpad_pan: false,
+        }
+    }
+}
+
+/// 复制到其他位置或转换格式时如何处理 EXIF 等元数据
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum MetadataPolicy {
+    #[default]
+    KeepAll,
+    /// 只去除位置信息
+    StripGps,
+    /// 去除全部元数据，仅保留方向以免照片显示歪
+    StripAll,
+}
+
+impl MetadataPolicy {
+    pub const ALL: [Self; 3] = [Self::KeepAll, Self::StripGps, Self::StripAll];
+}
+
+/// "打开方式"中的外部程序
+#[derive(Serialize, Deserialize, PartialEq, Clone, Default)]
+pub struct ExternalApp {
+    pub name: String,
+    /// 命令模板，如 `"C:\Program Files\GIMP 2\bin\gimp-2.10.exe" "{path}"`，
+    /// 可用占位符 {path}、{dir}、{name}
+    pub command: String,
+    /// 看图窗口内的快捷键，为空表示不设置
+    #[serde(default)]
+    pub hotkey: String,
+}
+
 #[derive(Serialize, Deserialize, PartialEq, Clone)]
 pub struct Config {
     #[serde(default)]
@@ -58,6 +367,9 @@ pub struct Config {
     pub zoom_sensitivity: f32,
     #[serde(default)]
     pub hotkeys: HotkeysConfig,
+    /// 看图和截图中的快捷键，只保存改动过的项
+    #[serde(default)]
+    pub keymap: Keymap,
     #[serde(default = "default_minimize_on_close")]
     pub minimize_on_close: bool,
     #[serde(default = "default_magnifier_enabled")]
@@ -66,6 +378,18 @@ pub struct Config {
     pub screenshot_hides_main_window: bool,
     #[serde(default = "default_launch_on_startup")]
     pub launch_on_startup: bool,
+    #[serde(default)]
+    pub grid: GridConfig,
+    #[serde(default)]
+    pub viewer: ViewerConfig,
+    #[serde(default)]
+    pub mouse: MouseConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub external_apps: Vec<ExternalApp>,
+    #[serde(default)]
+    pub metadata_policy: MetadataPolicy,
 
     #[serde(default)]
     pub window_pos: Option<(f32, f32)>,
@@ -73,6 +397,8 @@ pub struct Config {
     pub window_size: Option<(f32, f32)>,
 }
 
+pub const ZOOM_SENSITIVITY_RANGE: std::ops::RangeInclusive<f32> = 0.2..=5.0;
+
 fn default_zoom_sensitivity() -> f32 {
     1.0
 }
@@ -95,10 +421,17 @@ impl Default for Config {
             language: Language::default(),
             zoom_sensitivity: default_zoom_sensitivity(),
             hotkeys: HotkeysConfig::default(),
+            keymap: Keymap::default(),
             minimize_on_close: default_minimize_on_close(),
             magnifier_enabled: default_magnifier_enabled(),
             screenshot_hides_main_window: false,
             launch_on_startup: default_launch_on_startup(),
+            grid: GridConfig::default(),
+            viewer: ViewerConfig::default(),
+            mouse: MouseConfig::default(),
+            cache: CacheConfig::default(),
+            external_apps: Vec::new(),
+            metadata_policy: MetadataPolicy::default(),
             window_pos: None,
             window_size: None,
         }
@@ -8,6 +8,18 @@ pub struct ImageProperties {
     pub size: u64,
     pub name: String,
     pub date: String,
+    /// 以下为 EXIF 拍摄参数，缺失时为空字符串
+    pub camera: String,
+    pub exposure_time: String,
+    pub f_number: String,
+    pub iso: String,
+    pub focal_length: String,
+    /// EXIF 中带有 GPS 位置
+    pub has_location: bool,
+    /// 扩展名与文件头识别出的格式不一致时，记录实际格式
+    pub extension_mismatch: Option<String>,
+    /// 文件不完整，只解码出了一部分
+    pub truncated: bool,
 }
 
 impl Default for ImageProperties {
@@ -19,10 +31,19 @@ impl Default for ImageProperties {
             size: 0,
             name: "".to_string(),
             date: "".to_string(),
+            camera: "".to_string(),
+            exposure_time: "".to_string(),
+            f_number: "".to_string(),
+            iso: "".to_string(),
+            focal_length: "".to_string(),
+            has_location: false,
+            extension_mismatch: None,
+            truncated: false,
         }
     }
 }
 
 // 支持的图片类型
-pub const SUPPORTED_IMAGE_EXTENSIONS: &[&str] =
-    &["png", "jpg", "jpeg", "bmp", "gif", "webp", "tiff", "avif"];
+pub const SUPPORTED_IMAGE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "jfif", "bmp", "gif", "webp", "tif", "tiff", "avif",
+];
@@ -2,5 +2,6 @@ pub mod config;
 pub mod device;
 pub mod image_meta;
 pub mod mode;
+pub mod session;
 pub mod state;
 pub mod window_state;
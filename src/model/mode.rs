@@ -1,5 +1,6 @@
 use crate::model::config::Config;
 use eframe::egui::Pos2;
+use std::path::PathBuf;
 
 /// 应用级功能模式 - 顶层状态机
 #[derive(Clone, PartialEq, Debug)]
@@ -13,8 +14,18 @@ pub enum AppMode {
 pub enum PopupMode {
     None,
     About,
-    Settings { config: Config },
+    Settings {
+        config: Config,
+    },
     ContextMenu(Pos2),
+    /// 批量删除前的确认框
+    ConfirmDelete(Vec<PathBuf>),
+    /// 索引图导出选项，附带要导出的图片
+    ContactSheet(Vec<PathBuf>),
+    /// 拼接选项，附带要拼接的图片
+    Join(Vec<PathBuf>),
+    /// 动图导出选项，附带按顺序排列的帧
+    Sequence(Vec<PathBuf>),
 }
 
 /// 侧边面板状态 - 仅在 Viewer 模式下使用，可常驻
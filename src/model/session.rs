@@ -0,0 +1,354 @@
+use crate::model::config::get_config_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 最近打开列表的最大长度
+pub const RECENT_LIMIT: usize = 10;
+/// 最多记住多少个文件夹的浏览方式，超出时丢弃最久没打开的
+pub const FOLDER_PREFS_LIMIT: usize = 200;
+
+/// 浏览方式：单图、网格或对比
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ViewMode {
+    #[default]
+    Single,
+    Grid,
+    Compare,
+}
+
+/// 打开新图片时采用的缩放方式
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ZoomMode {
+    /// 完整显示，不放大小图
+    #[default]
+    Fit,
+    /// 铺满窗口，超出部分可拖动查看
+    Fill,
+    /// 1:1 实际像素
+    Actual,
+    FitWidth,
+    FitHeight,
+}
+
+/// 文件夹内图片的排列顺序
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SortOrder {
+    #[default]
+    Name,
+    /// 最新修改的在前
+    Modified,
+    /// 最大的文件在前
+    Size,
+}
+
+/// 索引图导出格式
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SheetFormat {
+    #[default]
+    Png,
+    Jpeg,
+    Pdf,
+}
+
+/// 页面尺寸，`Auto` 时整张索引图是一张随行数增高的长图
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PageSize {
+    #[default]
+    Auto,
+    A4,
+    Letter,
+    A3,
+}
+
+/// 索引图导出选项，记在会话里下次沿用
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct ContactSheetOptions {
+    pub format: SheetFormat,
+    pub page: PageSize,
+    pub landscape: bool,
+    pub columns: u32,
+    /// 缩略图区域的边长，只在 `PageSize::Auto` 时生效，分页时由页宽和列数决定
+    pub cell_size: u32,
+    pub margin: u32,
+    pub caption_name: bool,
+    pub caption_date: bool,
+    pub caption_dimensions: bool,
+}
+
+impl Default for ContactSheetOptions {
+    fn default() -> Self {
+        Self {
+            format: SheetFormat::Png,
+            page: PageSize::Auto,
+            landscape: false,
+            columns: 5,
+            cell_size: 240,
+            margin: 40,
+            caption_name: true,
+            caption_date: false,
+            caption_dimensions: true,
+        }
+    }
+}
+
+/// 拼接方向
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum JoinDirection {
+    #[default]
+    Horizontal,
+    Vertical,
+    Grid,
+}
+
+/// 尺寸不一时在行（列）内的对齐方式
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum JoinAlign {
+    Start,
+    #[default]
+    Center,
+    End,
+}
+
+/// 自动缩放到统一的高度（横向、网格）或宽度（纵向）
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum JoinScale {
+    None,
+    /// 缩小到最小的一张，不放大
+    #[default]
+    Smallest,
+    Largest,
+}
+
+/// 拼接选项，记在会话里下次沿用
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct JoinOptions {
+    pub direction: JoinDirection,
+    /// 网格的列数
+    pub columns: u32,
+    pub spacing: u32,
+    pub align: JoinAlign,
+    pub scale: JoinScale,
+    /// 间距和空白处的颜色，未预乘的 RGBA
+    pub background: [u8; 4],
+}
+
+impl Default for JoinOptions {
+    fn default() -> Self {
+        Self {
+            direction: JoinDirection::Horizontal,
+            columns: 2,
+            spacing: 0,
+            align: JoinAlign::Center,
+            scale: JoinScale::Smallest,
+            background: [255, 255, 255, 255],
+        }
+    }
+}
+
+/// 动图导出格式
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SequenceFormat {
+    #[default]
+    Gif,
+    Webp,
+}
+
+/// 动图导出选项，记在会话里下次沿用
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct SequenceOptions {
+    pub format: SequenceFormat,
+    /// 每帧停留的毫秒数
+    pub delay_ms: u32,
+    /// 播放次数，0 表示无限循环
+    pub plays: u32,
+    /// 相对第一帧尺寸的百分比
+    pub scale: u32,
+    /// GIF 调色板质量，越高越慢；WebP 为无损编码，不使用此项
+    pub quality: u32,
+}
+
+impl Default for SequenceOptions {
+    fn default() -> Self {
+        Self {
+            format: SequenceFormat::Gif,
+            delay_ms: 100,
+            plays: 0,
+            scale: 100,
+            quality: 20,
+        }
+    }
+}
+
+/// 每个文件夹单独记住的浏览方式
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct FolderPrefs {
+    #[serde(default)]
+    pub view_mode: ViewMode,
+    #[serde(default)]
+    pub sort: SortOrder,
+    /// 网格行高，未调整过时沿用全局设置
+    #[serde(default)]
+    pub row_height: Option<f32>,
+    /// 最近一次记录的时间（Unix 秒），用于淘汰最久没打开的文件夹
+    #[serde(default)]
+    pub last_used: u64,
+}
+
+/// 上次退出时的浏览状态，与 config.json 放在同一目录
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct Session {
+    #[serde(default)]
+    pub last_folder: Option<PathBuf>,
+    #[serde(default)]
+    pub last_file: Option<PathBuf>,
+    #[serde(default)]
+    pub view_mode: ViewMode,
+    #[serde(default)]
+    pub zoom_mode: ZoomMode,
+    #[serde(default)]
+    pub zoom: Option<f32>,
+    /// 单图模式的信息浮层，用快捷键切换
+    #[serde(default)]
+    pub show_info_overlay: bool,
+    /// 上次导出索引图时的选项
+    #[serde(default)]
+    pub contact_sheet: ContactSheetOptions,
+    /// 上次拼接图片时的选项
+    #[serde(default)]
+    pub join: JoinOptions,
+    /// 上次导出动图时的选项
+    #[serde(default)]
+    pub sequence: SequenceOptions,
+    /// 最近打开的文件夹和文件，最新的在前
+    #[serde(default)]
+    pub recent_folders: Vec<PathBuf>,
+    #[serde(default)]
+    pub recent_files: Vec<PathBuf>,
+    #[serde(default)]
+    pub folders: HashMap<PathBuf, FolderPrefs>,
+}
+
+impl Session {
+    /// 记录一次打开：文件同时把所在文件夹记入最近文件夹
+    pub fn record_open(&mut self, path: &Path) {
+        if path.is_dir() {
+            push_recent(&mut self.recent_folders, path);
+        } else {
+            push_recent(&mut self.recent_files, path);
+            if let Some(dir) = path.parent() {
+                push_recent(&mut self.recent_folders, dir);
+            }
+        }
+    }
+
+    pub fn clear_recent(&mut self) {
+        self.recent_folders.clear();
+        self.recent_files.clear();
+    }
+
+    /// 记录文件夹的浏览方式，超过 `FOLDER_PREFS_LIMIT` 时丢弃最久没打开的
+    pub fn remember_folder(&mut self, folder: PathBuf, mut prefs: FolderPrefs) {
+        prefs.last_used = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        self.folders.insert(folder, prefs);
+        prune_folders(&mut self.folders, FOLDER_PREFS_LIMIT);
+    }
+}
+
+fn prune_folders(folders: &mut HashMap<PathBuf, FolderPrefs>, limit: usize) {
+    let excess = folders.len().saturating_sub(limit);
+    if excess == 0 {
+        return;
+    }
+    let mut by_age: Vec<(u64, PathBuf)> = folders
+        .iter()
+        .map(|(path, prefs)| (prefs.last_used, path.clone()))
+        .collect();
+    by_age.sort_unstable();
+    for (_, path) in by_age.into_iter().take(excess) {
+        folders.remove(&path);
+    }
+}
+
+/// 放到列表最前面，去掉重复项并截断到 `RECENT_LIMIT`
+pub fn push_recent(list: &mut Vec<PathBuf>, path: &Path) {
+    list.retain(|p| p != path);
+    list.insert(0, path.to_path_buf());
+    list.truncate(RECENT_LIMIT);
+}
+
+fn get_session_path() -> PathBuf {
+    get_config_dir().join("session.json")
+}
+
+/// 读取会话，文件不存在或格式错误时返回空会话
+pub fn load_session() -> Session {
+    let path = get_session_path();
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Session::default();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        tracing::warn!("会话文件格式错误: {}", e);
+        Session::default()
+    })
+}
+
+pub fn save_session(session: &Session) {
+    let path = get_session_path();
+    let saved = serde_json::to_string_pretty(session)
+        .map_err(|e| e.to_string())
+        .and_then(|content| fs::write(&path, content).map_err(|e| e.to_string()));
+    if let Err(e) = saved {
+        tracing::error!("无法保存会话到 {:?}: {}", path, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FolderPrefs, RECENT_LIMIT, prune_folders, push_recent};
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn push_recent_moves_to_front_and_caps_length() {
+        let mut list: Vec<PathBuf> = (0..RECENT_LIMIT)
+            .map(|i| PathBuf::from(format!("/{i}")))
+            .collect();
+        push_recent(&mut list, Path::new("/3"));
+        assert_eq!(list.len(), RECENT_LIMIT);
+        assert_eq!(list[0], Path::new("/3"));
+        assert_eq!(list.iter().filter(|p| *p == Path::new("/3")).count(), 1);
+
+        push_recent(&mut list, Path::new("/new"));
+        assert_eq!(list.len(), RECENT_LIMIT);
+        assert_eq!(list[0], Path::new("/new"));
+        assert!(!list.contains(&PathBuf::from(format!("/{}", RECENT_LIMIT - 1))));
+    }
+
+    #[test]
+    fn prune_folders_drops_least_recently_used() {
+        let mut folders: HashMap<PathBuf, FolderPrefs> = (0..5u64)
+            .map(|i| {
+                let prefs = FolderPrefs {
+                    last_used: 100 - i,
+                    ..FolderPrefs::default()
+                };
+                (PathBuf::from(format!("/{i}")), prefs)
+            })
+            .collect();
+        prune_folders(&mut folders, 3);
+        assert_eq!(folders.len(), 3);
+        assert!(folders.contains_key(Path::new("/0")));
+        assert!(folders.contains_key(Path::new("/2")));
+        assert!(!folders.contains_key(Path::new("/3")));
+        assert!(!folders.contains_key(Path::new("/4")));
+    }
+}
@@ -1,8 +1,9 @@
-use super::{OcrEngine, ScreenshotPlatform, ThumbnailProvider, WindowManager};
+use super::{FileManager, OcrEngine, ScreenshotPlatform, ThumbnailProvider, WindowManager};
 use crate::i18n::lang::Language;
 use egui::{ColorImage, Rect};
 use image::DynamicImage;
 use std::path::Path;
+use std::process::Command;
 
 pub struct LinuxPlatform;
 
@@ -39,3 +40,29 @@ impl OcrEngine for LinuxPlatform {
         Err("Not implemented on Linux".to_string())
     }
 }
+
+impl FileManager for LinuxPlatform {
+    fn reveal_in_file_manager(&self, path: &Path) -> Result<(), String> {
+        // 文件管理器各不相同，统一用 xdg-open 打开所在文件夹
+        let dir = path.parent().unwrap_or(path);
+        Command::new("xdg-open")
+            .arg(dir)
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    fn move_to_trash(&self, path: &Path) -> Result<(), String> {
+        // gio 按 freedesktop 回收站规范处理，桌面环境都能恢复
+        let output = Command::new("gio")
+            .arg("trash")
+            .arg(path)
+            .output()
+            .map_err(|e| e.to_string())?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+        }
+    }
+}
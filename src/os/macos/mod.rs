@@ -1,8 +1,9 @@
-use super::{OcrEngine, ScreenshotPlatform, ThumbnailProvider, WindowManager};
+use super::{FileManager, OcrEngine, ScreenshotPlatform, ThumbnailProvider, WindowManager};
 use crate::i18n::lang::Language;
 use egui::{ColorImage, Rect};
 use image::DynamicImage;
 use std::path::Path;
+use std::process::Command;
 
 pub struct MacosPlatform;
 
@@ -39,3 +40,35 @@ impl OcrEngine for MacosPlatform {
         Err("Not implemented on macOS".to_string())
     }
 }
+
+impl FileManager for MacosPlatform {
+    fn reveal_in_file_manager(&self, path: &Path) -> Result<(), String> {
+        Command::new("open")
+            .arg("-R")
+            .arg(path)
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    fn move_to_trash(&self, path: &Path) -> Result<(), String> {
+        // 通过 Finder 删除才会进入废纸篓并支持“放回原处”
+        let path = path
+            .display()
+            .to_string()
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"");
+        let output = Command::new("osascript")
+            .arg("-e")
+            .arg(format!(
+                "tell application \"Finder\" to delete POSIX file \"{path}\""
+            ))
+            .output()
+            .map_err(|e| e.to_string())?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+        }
+    }
+}
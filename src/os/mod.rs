@@ -34,9 +34,22 @@ pub trait OcrEngine {
     fn recognize_text(&self, img: DynamicImage, language: Language) -> Result<String, String>;
 }
 
-pub trait Platform: WindowManager + ScreenshotPlatform + ThumbnailProvider + OcrEngine {}
+pub trait FileManager {
+    /// 在系统文件管理器中显示文件，能选中时选中它
+    fn reveal_in_file_manager(&self, path: &Path) -> Result<(), String>;
+    /// 把文件移到回收站，可以从系统回收站恢复
+    fn move_to_trash(&self, path: &Path) -> Result<(), String>;
+}
+
+pub trait Platform:
+    WindowManager + ScreenshotPlatform + ThumbnailProvider + OcrEngine + FileManager
+{
+}
 
-impl<T> Platform for T where T: WindowManager + ScreenshotPlatform + ThumbnailProvider + OcrEngine {}
+impl<T> Platform for T where
+    T: WindowManager + ScreenshotPlatform + ThumbnailProvider + OcrEngine + FileManager
+{
+}
 
 // 获取当前平台的 Handler
 pub fn current_platform() -> &'static dyn Platform {
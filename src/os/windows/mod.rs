@@ -3,7 +3,9 @@ use egui::{ColorImage, Rect, pos2};
 use image::DynamicImage;
 use raw_window_handle::{HasWindowHandle, RawWindowHandle};
 use std::os::windows::ffi::OsStrExt;
+use std::os::windows::process::CommandExt;
 use std::path::Path;
+use std::process::Command;
 use std::{iter, slice};
 use windows::Win32::Foundation::{HWND, POINT, RECT, SIZE};
 use windows::Win32::Foundation::{LPARAM, LRESULT, WPARAM};
@@ -13,8 +15,9 @@ use windows::Win32::Graphics::Gdi::{
 };
 use windows::Win32::System::Threading::{AttachThreadInput, GetCurrentThreadId};
 use windows::Win32::UI::Shell::{
-    IShellItem, IShellItemImageFactory, SHCreateItemFromParsingName, SIIGBF_BIGGERSIZEOK,
-    SIIGBF_RESIZETOFIT,
+    FO_DELETE, FOF_ALLOWUNDO, FOF_NOCONFIRMATION, FOF_NOERRORUI, FOF_SILENT, IShellItem,
+    IShellItemImageFactory, SHCreateItemFromParsingName, SHFILEOPSTRUCTW, SHFileOperationW,
+    SIIGBF_BIGGERSIZEOK, SIIGBF_RESIZETOFIT,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
     BringWindowToTop, ClipCursor, FindWindowA, FindWindowExA, GetCursorPos, GetForegroundWindow,
@@ -24,7 +27,7 @@ use windows::Win32::UI::WindowsAndMessaging::{
 };
 use windows::core::{Interface, PCSTR, PCWSTR, s};
 
-use super::{OcrEngine, ScreenshotPlatform, ThumbnailProvider, WindowManager};
+use super::{FileManager, OcrEngine, ScreenshotPlatform, ThumbnailProvider, WindowManager};
 
 pub mod ocr;
 pub mod startup;
@@ -385,3 +388,37 @@ impl OcrEngine for WindowsPlatform {
         ocr::recognize_text_windows(img, language)
     }
 }
+
+impl FileManager for WindowsPlatform {
+    fn reveal_in_file_manager(&self, path: &Path) -> Result<(), String> {
+        // explorer 不认标准的参数转义，路径需要手动加引号拼在 /select, 后面
+        Command::new("explorer")
+            .raw_arg(format!("/select,\"{}\"", path.display()))
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    fn move_to_trash(&self, path: &Path) -> Result<(), String> {
+        // pFrom 是以两个 0 结尾的路径列表
+        let from: Vec<u16> = path
+            .as_os_str()
+            .encode_wide()
+            .chain(iter::repeat_n(0, 2))
+            .collect();
+        let mut operation = SHFILEOPSTRUCTW {
+            wFunc: FO_DELETE,
+            pFrom: PCWSTR(from.as_ptr()),
+            fFlags: (FOF_ALLOWUNDO | FOF_NOCONFIRMATION | FOF_NOERRORUI | FOF_SILENT).0 as u16,
+            ..Default::default()
+        };
+        let code = unsafe { SHFileOperationW(&mut operation) };
+        if code != 0 {
+            return Err(format!("SHFileOperationW failed: {code:#x}"));
+        }
+        if operation.fAnyOperationsAborted.as_bool() {
+            return Err("operation aborted".to_string());
+        }
+        Ok(())
+    }
+}
@@ -1,24 +1,61 @@
 use crate::{
-    feature::viewer::viewer_state::ViewerState,
-    i18n::lang::get_i18n_text,
-    model::mode::PanelMode,
+    feature::viewer::{
+        batch::{BatchOperation, ConvertFormat},
+        open_with::{open_with, reveal_in_file_manager},
+        unsaved::save_unsaved_as,
+        viewer_state::{COMPARE_MAX, ViewerState},
+    },
+    i18n::lang::{TextBundle, get_i18n_text},
+    model::{
+        config::{ExternalApp, get_context_config},
+        mode::{PanelMode, PopupMode},
+    },
     ui::widgets::toast::ToastManager,
-    utils::clipboard::{copy_image_path_to_clipboard, copy_image_to_clipboard_async},
+    utils::clipboard::{
+        copy_image_path_to_clipboard, copy_image_to_clipboard_async, copy_paths_to_clipboard,
+    },
 };
 use eframe::emath::Pos2;
-use egui::{Align, Area, Context, Frame, Id, Layout, Order, Sense};
+use egui::{Align, Area, Button, Context, Frame, Id, Layout, Order, RichText, Sense, Ui};
 
-const CONTEXT_MENU_WIDTH: f32 = 120.0;
+const CONTEXT_MENU_WIDTH: f32 = 150.0;
+
+/// 右键菜单的作用对象
+#[derive(Clone, Copy, PartialEq)]
+pub enum ContextMenuTarget {
+    /// 单图模式下的当前图片
+    Current,
+    /// 网格模式下的选中项，附带数量
+    Selection(usize),
+    /// 单图模式下粘贴进来、尚未保存的图片
+    Unsaved,
+}
 
 pub enum ContextMenuAction {
     Copy,
     CopyPath,
     ShowProperties,
+    CopySelectedPaths,
+    Batch(BatchOperation),
+    OpenInCompare,
+    SaveAs,
+    /// 用配置中的第几个外部程序打开
+    OpenWith(usize),
+    RevealInFileManager,
+    ContactSheet,
+    Join,
+    ExportAnimation,
 }
 
-pub fn render_context_menu(ctx: &Context, pos: &mut Option<Pos2>) -> Option<ContextMenuAction> {
+pub fn render_context_menu(
+    ctx: &Context,
+    pos: &mut Option<Pos2>,
+    target: ContextMenuTarget,
+) -> Option<ContextMenuAction> {
     let mut action = None;
     let text = get_i18n_text(ctx);
+    let config = get_context_config(ctx);
+    let apps = config.external_apps.as_slice();
     if let Some(position) = pos {
         let mut close_menu = false;
 
@@ -40,16 +77,15 @@ pub fn render_context_menu(ctx: &Context, pos: &mut Option<Pos2>) -> Option<Cont
                 Frame::menu(ui.style()).show(ui, |ui| {
                     ui.set_width(CONTEXT_MENU_WIDTH);
                     ui.with_layout(Layout::top_down_justified(Align::Min), |ui| {
-                        if ui.button(text.context_menu.copy).clicked() {
-                            action = Some(ContextMenuAction::Copy);
-                            close_menu = true;
-                        }
-                        if ui.button(text.context_menu.copy_path).clicked() {
-                            action = Some(ContextMenuAction::CopyPath);
-                            close_menu = true;
-                        }
-                        if ui.button(text.context_menu.properties).clicked() {
-                            action = Some(ContextMenuAction::ShowProperties);
+                        let picked = match target {
+                            ContextMenuTarget::Current => current_menu_items(ui, text, apps),
+                            ContextMenuTarget::Selection(count) => {
+                                selection_menu_items(ui, text, count, apps)
+                            }
+                            ContextMenuTarget::Unsaved => unsaved_menu_items(ui, text),
+                        };
+                        if picked.is_some() {
+                            action = picked;
                             close_menu = true;
                         }
                     });
@@ -64,11 +100,127 @@ pub fn render_context_menu(ctx: &Context, pos: &mut Option<Pos2>) -> Option<Cont
     action
 }
 
+fn current_menu_items(
+    ui: &mut Ui,
+    text: &TextBundle,
+    apps: &[ExternalApp],
+) -> Option<ContextMenuAction> {
+    if ui.button(text.context_menu.copy).clicked() {
+        return Some(ContextMenuAction::Copy);
+    }
+    if ui.button(text.context_menu.copy_path).clicked() {
+        return Some(ContextMenuAction::CopyPath);
+    }
+    if let Some(action) = open_with_items(ui, text, apps) {
+        return Some(action);
+    }
+    if ui.button(text.context_menu.properties).clicked() {
+        return Some(ContextMenuAction::ShowProperties);
+    }
+    None
+}
+
+fn unsaved_menu_items(ui: &mut Ui, text: &TextBundle) -> Option<ContextMenuAction> {
+    if ui.button(text.context_menu.copy).clicked() {
+        return Some(ContextMenuAction::Copy);
+    }
+    if ui.button(text.context_menu.save_as).clicked() {
+        return Some(ContextMenuAction::SaveAs);
+    }
+    if ui.button(text.context_menu.properties).clicked() {
+        return Some(ContextMenuAction::ShowProperties);
+    }
+    None
+}
+
+/// 外部程序子菜单和“在文件夹中显示”
+fn open_with_items(
+    ui: &mut Ui,
+    text: &TextBundle,
+    apps: &[ExternalApp],
+) -> Option<ContextMenuAction> {
+    let mut action = None;
+    ui.menu_button(text.context_menu.open_with, |ui| {
+        if apps.is_empty() {
+            ui.label(RichText::new(text.context_menu.no_external_apps).weak());
+        }
+        for (index, app) in apps.iter().enumerate() {
+            if ui
+                .add(Button::new(&app.name).shortcut_text(&app.hotkey))
+                .clicked()
+            {
+                action = Some(ContextMenuAction::OpenWith(index));
+            }
+        }
+    });
+    if ui.button(text.context_menu.show_in_folder).clicked() {
+        action = Some(ContextMenuAction::RevealInFileManager);
+    }
+    action
+}
+
+fn selection_menu_items(
+    ui: &mut Ui,
+    text: &TextBundle,
+    count: usize,
+    apps: &[ExternalApp],
+) -> Option<ContextMenuAction> {
+    let mut action = None;
+
+    ui.label(RichText::new(text.batch.selected.replace("{count}", &count.to_string())).weak());
+    ui.separator();
+    if ui.button(text.context_menu.copy_path).clicked() {
+        action = Some(ContextMenuAction::CopySelectedPaths);
+    }
+    if ui.button(text.context_menu.copy_to).clicked() {
+        action = Some(ContextMenuAction::Batch(BatchOperation::CopyTo));
+    }
+    if ui.button(text.context_menu.move_to).clicked() {
+        action = Some(ContextMenuAction::Batch(BatchOperation::MoveTo));
+    }
+    action = open_with_items(ui, text, apps).or(action);
+    ui.menu_button(text.context_menu.convert_to, |ui| {
+        for format in ConvertFormat::ALL {
+            if ui.button(format.label()).clicked() {
+                action = Some(ContextMenuAction::Batch(BatchOperation::Convert(format)));
+            }
+        }
+    });
+    let can_compare = (2..=COMPARE_MAX).contains(&count);
+    if ui
+        .add_enabled(can_compare, Button::new(text.context_menu.compare))
+        .clicked()
+    {
+        action = Some(ContextMenuAction::OpenInCompare);
+    }
+    if ui
+        .add_enabled(count >= 2, Button::new(text.context_menu.join))
+        .clicked()
+    {
+        action = Some(ContextMenuAction::Join);
+    }
+    if ui
+        .add_enabled(count >= 2, Button::new(text.context_menu.export_animation))
+        .clicked()
+    {
+        action = Some(ContextMenuAction::ExportAnimation);
+    }
+    if ui.button(text.context_menu.contact_sheet).clicked() {
+        action = Some(ContextMenuAction::ContactSheet);
+    }
+    ui.separator();
+    if ui.button(text.context_menu.delete).clicked() {
+        action = Some(ContextMenuAction::Batch(BatchOperation::Delete));
+    }
+    action
+}
+
 pub fn handle_context_menu_action(
     ctx: &Context,
     action: ContextMenuAction,
-    viewer: &ViewerState,
+    viewer: &mut ViewerState,
     panel: &mut PanelMode,
+    popup: &mut PopupMode,
     toast_manager: &ToastManager,
 ) {
     match action {
@@ -93,5 +245,39 @@ pub fn handle_context_menu_action(
                 PanelMode::Properties
             };
         }
+        ContextMenuAction::CopySelectedPaths => {
+            copy_paths_to_clipboard(ctx, &viewer.selected_paths(), toast_manager);
+        }
+        ContextMenuAction::Batch(BatchOperation::Delete) => {
+            // 删除不可撤销，先弹出确认框
+            *popup = PopupMode::ConfirmDelete(viewer.selected_paths());
+        }
+        ContextMenuAction::Batch(operation) => {
+            viewer
+                .batch
+                .start(ctx, viewer.selected_paths(), operation, toast_manager);
+        }
+        ContextMenuAction::OpenInCompare => {
+            viewer.open_compare(ctx, viewer.selected_paths());
+        }
+        ContextMenuAction::SaveAs => save_unsaved_as(ctx, viewer, toast_manager),
+        ContextMenuAction::OpenWith(index) => open_with(ctx, viewer, index, toast_manager),
+        ContextMenuAction::RevealInFileManager => {
+            reveal_in_file_manager(ctx, viewer, toast_manager);
+        }
+        ContextMenuAction::ContactSheet => {
+            // 没有选中时导出整个文件夹
+            let mut paths = viewer.selected_paths();
+            if paths.is_empty() {
+                paths = viewer.list.clone();
+            }
+            *popup = PopupMode::ContactSheet(paths);
+        }
+        ContextMenuAction::Join => {
+            *popup = PopupMode::Join(viewer.selected_paths());
+        }
+        ContextMenuAction::ExportAnimation => {
+            *popup = PopupMode::Sequence(viewer.selected_paths());
+        }
     }
 }
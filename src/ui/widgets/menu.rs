@@ -1,7 +1,10 @@
+use crate::i18n::lang::TextBundle;
 use crate::i18n::lang::get_i18n_text;
 use crate::model::config::get_context_config;
 use crate::model::mode::PopupMode;
-use egui::{Button, MenuBar, Panel, Ui};
+use crate::model::session::Session;
+use egui::{Button, MenuBar, Panel, RichText, Ui};
+use std::path::{Path, PathBuf};
 
 /// 菜单动作
 #[derive(Default)]
@@ -9,6 +12,9 @@ pub enum MenuAction {
     #[default]
     None,
     ShowScreenshot,
+    /// 从最近打开列表中选择了路径
+    OpenRecent(PathBuf),
+    ClearRecent,
 }
 
 /// 绘制主菜单栏
@@ -19,7 +25,11 @@ pub enum MenuAction {
 /// 1. 是否点击了"打开文件"
 /// 2. 是否点击了"打开文件夹"
 /// 3. 菜单动作
-pub fn draw_menu(ui: &mut Ui, popup: &mut PopupMode) -> (bool, bool, MenuAction) {
+pub fn draw_menu(
+    ui: &mut Ui,
+    popup: &mut PopupMode,
+    session: &Session,
+) -> (bool, bool, MenuAction) {
     let mut open_file_dialog = false;
     let mut open_folder_dialog = false;
     let mut action = MenuAction::None;
@@ -38,6 +48,12 @@ pub fn draw_menu(ui: &mut Ui, popup: &mut PopupMode) -> (bool, bool, MenuAction)
                     open_folder_dialog = true;
                     ui.close();
                 }
+                ui.menu_button(text.menu.recent, |ui| {
+                    if let Some(recent) = recent_menu_items(ui, session, text) {
+                        action = recent;
+                        ui.close();
+                    }
+                });
 
                 ui.separator();
 
@@ -80,3 +96,41 @@ pub fn draw_menu(ui: &mut Ui, popup: &mut PopupMode) -> (bool, bool, MenuAction)
 
     (open_file_dialog, open_folder_dialog, action)
 }
+
+/// 最近打开的文件夹和文件，菜单项显示名称，悬停显示完整路径
+fn recent_menu_items(ui: &mut Ui, session: &Session, text: &TextBundle) -> Option<MenuAction> {
+    if session.recent_folders.is_empty() && session.recent_files.is_empty() {
+        ui.add_enabled(false, Button::new(text.menu.no_recent));
+        return None;
+    }
+    let mut action = None;
+    for (title, paths) in [
+        (text.menu.recent_folders, &session.recent_folders),
+        (text.menu.recent_files, &session.recent_files),
+    ] {
+        if paths.is_empty() {
+            continue;
+        }
+        ui.label(RichText::new(title).weak());
+        for path in paths {
+            if ui
+                .button(recent_label(path))
+                .on_hover_text(path.display().to_string())
+                .clicked()
+            {
+                action = Some(MenuAction::OpenRecent(path.clone()));
+            }
+        }
+        ui.separator();
+    }
+    if ui.button(text.menu.clear_recent).clicked() {
+        action = Some(MenuAction::ClearRecent);
+    }
+    action
+}
+
+pub fn recent_label(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
@@ -1,26 +1,55 @@
 use egui::{
-    Align, Button, ComboBox, Context, Frame, Id, Key, Layout, Margin, Modifiers, ScrollArea, Ui,
+    Align, Button, ComboBox, Context, Frame, Id, Key, Layout, Margin, Modifiers, ScrollArea,
+    Slider, TextEdit, Ui,
 };
 
 use crate::ui::widgets::toggle::toggle;
 use crate::{
+    core::keymap::{Action, KeyScope, find_conflicts},
+    feature::viewer::overlay::PLACEHOLDERS,
     i18n::lang::{Language, TextBundle, get_i18n_text},
-    model::config::Config,
+    model::config::{
+        ClickAction, Config, ExternalApp, IMAGE_CACHE_MB_RANGE, ImageBackground, MetadataPolicy,
+        MouseConfig, OverlayCorner, PIXEL_GRID_ZOOM_RANGE, THUMB_CACHE_MB_RANGE, WheelAction,
+        ZOOM_SENSITIVITY_RANGE,
+    },
     ui::widgets::modal::{ModalAction, ModalFrame},
 };
 
 #[derive(PartialEq, Clone, Copy, Hash)]
 enum SettingsTab {
     General,
+    Viewer,
+    Mouse,
     Hotkeys,
+    ExternalApps,
 }
 
-// 用于在 egui 的内存中存储哪个快捷键正在被录制
-#[derive(PartialEq, Clone, Copy, Hash)]
+// 用于在 egui 的内存中存储哪个快捷键正在被录制，也用来标识冲突检测中的各个绑定
+#[derive(PartialEq, Eq, Clone, Copy, Hash)]
 enum RecordingState {
     None,
     ShowScreenshot,
     CopyScreenshot,
+    /// 外部程序列表中的第几项
+    ExternalApp(usize),
+    Action(Action),
+}
+
+impl RecordingState {
+    /// 可以留空的绑定，录制时退格键清除
+    fn is_optional(self) -> bool {
+        matches!(self, Self::ExternalApp(_) | Self::Action(_))
+    }
+}
+
+/// 快捷键行右侧的附加信息
+#[derive(Default)]
+struct HotkeyRowExtras {
+    /// 与默认值不同时显示恢复按钮
+    default: Option<&'static str>,
+    /// 冲突对象的名称
+    conflict: Option<String>,
 }
 
 pub fn render_settings_window(
@@ -96,6 +125,24 @@ fn render_sidebar(ui: &mut Ui, current_tab: &mut SettingsTab, text: &TextBundle)
         {
             *current_tab = SettingsTab::General;
         }
+        if ui
+            .selectable_label(
+                *current_tab == SettingsTab::Viewer,
+                format!("  {}", text.settings.viewer),
+            )
+            .clicked()
+        {
+            *current_tab = SettingsTab::Viewer;
+        }
+        if ui
+            .selectable_label(
+                *current_tab == SettingsTab::Mouse,
+                format!("  {}", text.settings.mouse),
+            )
+            .clicked()
+        {
+            *current_tab = SettingsTab::Mouse;
+        }
         if ui
             .selectable_label(
                 *current_tab == SettingsTab::Hotkeys,
@@ -105,6 +152,15 @@ fn render_sidebar(ui: &mut Ui, current_tab: &mut SettingsTab, text: &TextBundle)
         {
             *current_tab = SettingsTab::Hotkeys;
         }
+        if ui
+            .selectable_label(
+                *current_tab == SettingsTab::ExternalApps,
+                format!("  {}", text.settings.external_apps),
+            )
+            .clicked()
+        {
+            *current_tab = SettingsTab::ExternalApps;
+        }
         ui.add_space(ui.available_height());
     });
 }
@@ -114,7 +170,10 @@ fn render_content_header(ui: &mut Ui, current_tab: SettingsTab, text: &TextBundl
         ui.add_space(5.0);
         let title = match current_tab {
             SettingsTab::General => &text.settings.general,
+            SettingsTab::Viewer => &text.settings.viewer,
+            SettingsTab::Mouse => &text.settings.mouse,
             SettingsTab::Hotkeys => &text.settings.shortcut_key,
+            SettingsTab::ExternalApps => &text.settings.external_apps,
         };
         ui.label(egui::RichText::new(format!("{} > {}", text.settings.title, title)).weak());
     });
@@ -217,33 +276,469 @@ fn render_content_body(
                             ui.add(toggle(&mut config.launch_on_startup));
                         });
                     });
+                    light_separator(ui);
+
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}:", text.metadata.title))
+                            .on_hover_text(text.metadata.hint);
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            ComboBox::from_id_salt("metadata_policy_selector")
+                                .selected_text(config.metadata_policy.label(text))
+                                .show_ui(ui, |ui| {
+                                    for policy in MetadataPolicy::ALL {
+                                        ui.selectable_value(
+                                            &mut config.metadata_policy,
+                                            policy,
+                                            policy.label(text),
+                                        );
+                                    }
+                                });
+                        });
+                    });
                 });
             }
-            SettingsTab::Hotkeys => {
-                ui.heading(text.settings.shortcut_key);
+            SettingsTab::Viewer => {
+                ui.heading(text.settings.viewer);
                 ui.add_space(10.0);
 
                 setting_card(ui, |ui| {
-                    render_hotkey_row(
-                        ui,
-                        text.shortcuts.screenshot,
-                        &mut config.hotkeys.show_screenshot,
-                        recording_state,
-                        RecordingState::ShowScreenshot,
-                    );
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}:", text.settings.smooth_magnification));
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            ui.add(toggle(&mut config.viewer.smooth_magnification));
+                        });
+                    });
                     light_separator(ui);
 
-                    render_hotkey_row(
-                        ui,
-                        text.shortcuts.copy_color,
-                        &mut config.hotkeys.copy_color,
-                        recording_state,
-                        RecordingState::CopyScreenshot,
-                    );
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}:", text.settings.pixel_grid));
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            ui.add(toggle(&mut config.viewer.pixel_grid));
+                        });
+                    });
+                    light_separator(ui);
+
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}:", text.settings.pixel_grid_zoom));
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            ui.add_enabled(
+                                config.viewer.pixel_grid,
+                                Slider::new(
+                                    &mut config.viewer.pixel_grid_zoom,
+                                    PIXEL_GRID_ZOOM_RANGE,
+                                )
+                                .suffix("x"),
+                            );
+                        });
+                    });
+                    light_separator(ui);
+
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}:", text.background.title));
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            if config.viewer.background == ImageBackground::Custom {
+                                ui.color_edit_button_srgb(&mut config.viewer.background_color);
+                            }
+                            ComboBox::from_id_salt("background_selector")
+                                .selected_text(config.viewer.background.label(text))
+                                .show_ui(ui, |ui| {
+                                    for background in ImageBackground::ALL {
+                                        ui.selectable_value(
+                                            &mut config.viewer.background,
+                                            background,
+                                            background.label(text),
+                                        );
+                                    }
+                                });
+                        });
+                    });
+                    light_separator(ui);
+
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}:", text.settings.image_cache));
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            ui.add(
+                                Slider::new(&mut config.cache.image_mb, IMAGE_CACHE_MB_RANGE)
+                                    .logarithmic(true)
+                                    .suffix(" MB"),
+                            );
+                        });
+                    });
+                    light_separator(ui);
+
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}:", text.settings.thumb_cache));
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            ui.add(
+                                Slider::new(&mut config.cache.thumb_mb, THUMB_CACHE_MB_RANGE)
+                                    .logarithmic(true)
+                                    .suffix(" MB"),
+                            );
+                        });
+                    });
+                });
+                ui.add_space(8.0);
+                render_overlay_settings(ui, config, text);
+            }
+            SettingsTab::Mouse => {
+                ui.heading(text.settings.mouse);
+                ui.add_space(10.0);
+                render_mouse_settings(ui, &mut config.mouse, &mut config.zoom_sensitivity, text);
+            }
+            SettingsTab::Hotkeys => {
+                ui.heading(text.settings.shortcut_key);
+                ui.add_space(10.0);
+                render_keymap_editor(ui, config, text, recording_state);
+            }
+            SettingsTab::ExternalApps => {
+                ui.heading(text.settings.external_apps);
+                ui.label(egui::RichText::new(text.settings.external_apps_hint).weak());
+                ui.add_space(10.0);
+                render_external_apps(ui, config, text, recording_state);
+            }
+        }
+    });
+}
+
+/// 外部程序列表：每项一张卡片，可编辑名称、命令和快捷键
+fn render_external_apps(
+    ui: &mut Ui,
+    config: &mut Config,
+    text: &TextBundle,
+    recording_state: &mut RecordingState,
+) {
+    let mut removed = None;
+    for (index, app) in config.external_apps.iter_mut().enumerate() {
+        setting_card(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", text.settings.app_name));
+                ui.add(TextEdit::singleline(&mut app.name).desired_width(200.0));
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    if ui.button(text.settings.remove).clicked() {
+                        removed = Some(index);
+                    }
+                });
+            });
+            light_separator(ui);
+
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", text.settings.app_command));
+                ui.add(
+                    TextEdit::singleline(&mut app.command)
+                        .hint_text(r#""C:\Program Files\GIMP 2\bin\gimp-2.10.exe" "{path}""#)
+                        .desired_width(f32::INFINITY),
+                );
+            });
+            light_separator(ui);
+
+            render_hotkey_row(
+                ui,
+                text.settings.shortcut_key,
+                &mut app.hotkey,
+                recording_state,
+                RecordingState::ExternalApp(index),
+                HotkeyRowExtras::default(),
+            );
+        });
+        ui.add_space(8.0);
+    }
+
+    if let Some(index) = removed {
+        config.external_apps.remove(index);
+        *recording_state = RecordingState::None;
+    }
+    if ui.button(text.settings.add_app).clicked() {
+        config.external_apps.push(ExternalApp::default());
+    }
+}
+
+fn render_overlay_settings(ui: &mut Ui, config: &mut Config, text: &TextBundle) {
+    ui.label(egui::RichText::new(text.overlay.title).strong());
+    setting_card(ui, |ui| {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", text.overlay.corner));
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                ComboBox::from_id_salt("overlay_corner_selector")
+                    .selected_text(config.viewer.overlay_corner.label(text))
+                    .show_ui(ui, |ui| {
+                        for corner in OverlayCorner::ALL {
+                            ui.selectable_value(
+                                &mut config.viewer.overlay_corner,
+                                corner,
+                                corner.label(text),
+                            );
+                        }
+                    });
+            });
+        });
+        light_separator(ui);
+
+        ui.label(format!("{}:", text.overlay.template));
+        ui.add(
+            TextEdit::multiline(&mut config.viewer.overlay_template)
+                .font(egui::TextStyle::Monospace)
+                .desired_rows(4)
+                .desired_width(f32::INFINITY),
+        );
+        let placeholders: Vec<String> = PLACEHOLDERS.iter().map(|p| format!("{{{p}}}")).collect();
+        ui.label(
+            egui::RichText::new(format!(
+                "{} {}",
+                text.overlay.placeholders,
+                placeholders.join(" ")
+            ))
+            .weak(),
+        );
+        let key = config.keymap.binding(Action::ToggleInfoOverlay);
+        ui.label(egui::RichText::new(text.overlay.toggle_hint.replace("{key}", key)).weak());
+    });
+}
+
+fn render_mouse_settings(
+    ui: &mut Ui,
+    mouse: &mut MouseConfig,
+    zoom_sensitivity: &mut f32,
+    text: &TextBundle,
+) {
+    setting_card(ui, |ui| {
+        let wheels = [
+            (text.mouse.wheel, &mut mouse.wheel),
+            (text.mouse.ctrl_wheel, &mut mouse.ctrl_wheel),
+            (text.mouse.shift_wheel, &mut mouse.shift_wheel),
+        ];
+        for (label, value) in wheels {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", label));
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    ComboBox::from_id_salt(label)
+                        .selected_text(value.label(text))
+                        .show_ui(ui, |ui| {
+                            for action in WheelAction::ALL {
+                                ui.selectable_value(value, action, action.label(text));
+                            }
+                        });
                 });
+            });
+            light_separator(ui);
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", text.mouse.zoom_sensitivity));
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                ui.add(Slider::new(zoom_sensitivity, ZOOM_SENSITIVITY_RANGE).suffix("x"));
+            });
+        });
+    });
+    ui.add_space(8.0);
+
+    setting_card(ui, |ui| {
+        let clicks = [
+            (text.mouse.double_click, &mut mouse.double_click),
+            (text.mouse.middle_click, &mut mouse.middle_click),
+            (text.mouse.back_button, &mut mouse.back_button),
+            (text.mouse.forward_button, &mut mouse.forward_button),
+        ];
+        for (index, (label, value)) in clicks.into_iter().enumerate() {
+            if index > 0 {
+                light_separator(ui);
             }
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", label));
+                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                    ComboBox::from_id_salt(label)
+                        .selected_text(value.label(text))
+                        .show_ui(ui, |ui| {
+                            for action in ClickAction::ALL {
+                                ui.selectable_value(value, action, action.label(text));
+                            }
+                        });
+                });
+            });
         }
     });
+    ui.add_space(8.0);
+
+    setting_card(ui, |ui| {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", text.mouse.pinch_zoom));
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                ui.add(toggle(&mut mouse.pinch_zoom));
+            });
+        });
+        light_separator(ui);
+
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", text.mouse.trackpad_pan));
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                ui.add(toggle(&mut mouse.trackpad_pan));
+            });
+        });
+    });
+}
+
+/// 按键绑定编辑器：全局热键、看图与截图动作、外部程序，可按名称或按键搜索
+fn render_keymap_editor(
+    ui: &mut Ui,
+    config: &mut Config,
+    text: &TextBundle,
+    recording_state: &mut RecordingState,
+) {
+    let search_id = Id::new("keymap_search");
+    let mut query: String = ui.data(|d| d.get_temp(search_id)).unwrap_or_default();
+    ui.add(
+        TextEdit::singleline(&mut query)
+            .hint_text(text.keymap.search_hint)
+            .desired_width(f32::INFINITY),
+    );
+    ui.label(egui::RichText::new(text.keymap.record_hint).weak());
+    ui.add_space(8.0);
+    let needle = query.trim().to_lowercase();
+    ui.data_mut(|d| d.insert_temp(search_id, query));
+    let matches = |label: &str, binding: &str| {
+        needle.is_empty()
+            || label.to_lowercase().contains(&needle)
+            || binding.to_lowercase().contains(&needle)
+    };
+
+    let conflicts = find_conflicts(keymap_entries(config));
+
+    // 先收集各组要显示的行，再统一绘制，避免借用冲突
+    let mut groups: Vec<(&str, Vec<RecordingState>)> = vec![
+        (
+            text.keymap.group_global,
+            vec![RecordingState::ShowScreenshot],
+        ),
+        (text.keymap.group_viewer, Vec::new()),
+        (
+            text.keymap.group_screenshot,
+            vec![RecordingState::CopyScreenshot],
+        ),
+    ];
+    for action in Action::ALL {
+        let group = if action.scope() == KeyScope::Screenshot {
+            2
+        } else {
+            1
+        };
+        groups[group].1.push(RecordingState::Action(action));
+    }
+    groups[1]
+        .1
+        .extend((0..config.external_apps.len()).map(RecordingState::ExternalApp));
+
+    for (title, rows) in groups {
+        let rows: Vec<(RecordingState, String, Option<String>)> = rows
+            .into_iter()
+            .map(|row| {
+                let conflict = conflicts
+                    .get(&row)
+                    .map(|other| binding_label(*other, config, text));
+                (row, binding_label(row, config, text), conflict)
+            })
+            .filter(|(row, label, _)| matches(label, binding_of(*row, config)))
+            .collect();
+        if rows.is_empty() {
+            continue;
+        }
+        ui.label(egui::RichText::new(title).strong());
+        setting_card(ui, |ui| {
+            for (index, (row, label, conflict)) in rows.into_iter().enumerate() {
+                if index > 0 {
+                    light_separator(ui);
+                }
+                let default = match row {
+                    RecordingState::Action(action) => Some(action.default_binding()),
+                    _ => None,
+                };
+                let mut binding = binding_of(row, config).to_string();
+                render_hotkey_row(
+                    ui,
+                    &label,
+                    &mut binding,
+                    recording_state,
+                    row,
+                    HotkeyRowExtras { default, conflict },
+                );
+                set_binding(row, config, binding);
+            }
+        });
+        ui.add_space(8.0);
+    }
+}
+
+/// 参与冲突检测的全部绑定
+fn keymap_entries(config: &Config) -> Vec<(RecordingState, KeyScope, String)> {
+    let mut entries = vec![
+        (
+            RecordingState::ShowScreenshot,
+            KeyScope::Global,
+            config.hotkeys.show_screenshot.clone(),
+        ),
+        (
+            RecordingState::CopyScreenshot,
+            KeyScope::Screenshot,
+            config.hotkeys.copy_color.clone(),
+        ),
+    ];
+    entries.extend(Action::ALL.into_iter().map(|action| {
+        (
+            RecordingState::Action(action),
+            action.scope(),
+            config.keymap.binding(action).to_string(),
+        )
+    }));
+    entries.extend(config.external_apps.iter().enumerate().map(|(index, app)| {
+        (
+            RecordingState::ExternalApp(index),
+            KeyScope::Viewer,
+            app.hotkey.clone(),
+        )
+    }));
+    entries
+}
+
+fn binding_label(row: RecordingState, config: &Config, text: &TextBundle) -> String {
+    match row {
+        RecordingState::ShowScreenshot => text.shortcuts.screenshot.to_string(),
+        RecordingState::CopyScreenshot => text.shortcuts.copy_color.to_string(),
+        RecordingState::Action(action) => action.label(text).to_string(),
+        RecordingState::ExternalApp(index) => config
+            .external_apps
+            .get(index)
+            .map(|app| format!("{}: {}", text.keymap.group_external, app.name))
+            .unwrap_or_default(),
+        RecordingState::None => String::new(),
+    }
+}
+
+fn binding_of(row: RecordingState, config: &Config) -> &str {
+    match row {
+        RecordingState::ShowScreenshot => &config.hotkeys.show_screenshot,
+        RecordingState::CopyScreenshot => &config.hotkeys.copy_color,
+        RecordingState::Action(action) => config.keymap.binding(action),
+        RecordingState::ExternalApp(index) => config
+            .external_apps
+            .get(index)
+            .map_or("", |app| app.hotkey.as_str()),
+        RecordingState::None => "",
+    }
+}
+
+fn set_binding(row: RecordingState, config: &mut Config, binding: String) {
+    if binding == binding_of(row, config) {
+        return;
+    }
+    match row {
+        RecordingState::ShowScreenshot => config.hotkeys.show_screenshot = binding,
+        RecordingState::CopyScreenshot => config.hotkeys.copy_color = binding,
+        RecordingState::Action(action) => config.keymap.set(action, binding),
+        RecordingState::ExternalApp(index) => {
+            if let Some(app) = config.external_apps.get_mut(index) {
+                app.hotkey = binding;
+            }
+        }
+        RecordingState::None => {}
+    }
 }
 
 fn render_hotkey_row(
@@ -252,15 +747,35 @@ fn render_hotkey_row(
     hotkey_str: &mut String,
     recording_state: &mut RecordingState,
     this_recorder: RecordingState,
+    extras: HotkeyRowExtras,
 ) {
     let text = get_i18n_text(ui);
     ui.horizontal(|ui| {
         ui.label(format!("{}:", label));
+        if let Some(other) = &extras.conflict {
+            let warn = ui.visuals().warn_fg_color;
+            ui.label(
+                egui::RichText::new(text.keymap.conflict.replace("{action}", other))
+                    .small()
+                    .color(warn),
+            );
+        }
 
         ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+            if let Some(default) = extras.default
+                && hotkey_str != default
+                && ui
+                    .small_button("↺")
+                    .on_hover_text(format!("{} ({default})", text.keymap.reset))
+                    .clicked()
+            {
+                *hotkey_str = default.to_string();
+            }
             let is_recording = *recording_state == this_recorder;
             let button_text = if is_recording {
                 text.shortcuts.modified
+            } else if hotkey_str.is_empty() {
+                text.settings.no_hotkey
             } else {
                 hotkey_str.as_str()
             };
@@ -282,6 +797,12 @@ fn render_hotkey_row(
                         *recording_state = RecordingState::None;
                         return;
                     }
+                    // 外部程序和看图、截图动作的快捷键可以留空，退格键清除
+                    if this_recorder.is_optional() && i.key_pressed(Key::Backspace) {
+                        hotkey_str.clear();
+                        *recording_state = RecordingState::None;
+                        return;
+                    }
 
                     let modifiers = i.modifiers;
 
@@ -1,20 +1,32 @@
 use crate::i18n::lang::{Language, get_text};
 use crate::model::config::get_context_config;
+use crate::model::session::Session;
 use crate::os::current_platform;
+use crate::ui::widgets::menu::recent_label;
 use crate::utils::image::load_tray_icon;
-use egui::{ViewportCommand, WindowLevel};
+use egui::{Context, ViewportCommand, WindowLevel};
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
-use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu};
 use tray_icon::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
 
 pub struct AppTray {
     _tray_icon: Option<TrayIcon>,
     screenshot_item: Option<MenuItem>,
     exit_item: Option<MenuItem>,
+    recent_menu: Option<Submenu>,
+    /// 最近打开子菜单中各项对应的路径，菜单事件按序号取用
+    recent_paths: Arc<Mutex<Vec<PathBuf>>>,
+    /// 子菜单当前显示的内容，未变化时不重建
+    recent_shown: Option<(Language, Vec<PathBuf>, usize)>,
     current_language: Language,
     screenshot_hotkey_text: String,
 }
 
+/// 最近打开菜单项的 id 前缀，后接在 `recent_paths` 中的序号
+const RECENT_ID_PREFIX: &str = "recent_";
+
 impl AppTray {
     pub fn refresh_labels(&mut self, language: Language, screenshot_hotkey_text: &str) {
         if self.current_language == language
@@ -33,10 +45,105 @@ impl AppTray {
         if let Some(item) = &self.exit_item {
             item.set_text(text.menu.exit);
         }
+        if let Some(menu) = &self.recent_menu {
+            menu.set_text(text.menu.recent);
+        }
 
         self.current_language = language;
         self.screenshot_hotkey_text = screenshot_hotkey_text.to_string();
     }
+
+    /// 按会话中的记录重建最近打开子菜单，内容没变时直接返回
+    pub fn refresh_recent(&mut self, language: Language, session: &Session) {
+        let Some(menu) = &self.recent_menu else {
+            return;
+        };
+        let paths: Vec<PathBuf> = session
+            .recent_folders
+            .iter()
+            .chain(&session.recent_files)
+            .cloned()
+            .collect();
+        let folder_count = session.recent_folders.len();
+        if self
+            .recent_shown
+            .as_ref()
+            .is_some_and(|(lang, shown, count)| {
+                *lang == language && *shown == paths && *count == folder_count
+            })
+        {
+            return;
+        }
+
+        while menu.remove_at(0).is_some() {}
+        let text = get_text(language);
+        if paths.is_empty() {
+            let _ = menu.append(&MenuItem::new(text.menu.no_recent, false, None));
+        }
+        for (index, path) in paths.iter().enumerate() {
+            if index == folder_count && index > 0 {
+                let _ = menu.append(&PredefinedMenuItem::separator());
+            }
+            let item = MenuItem::with_id(
+                format!("{RECENT_ID_PREFIX}{index}"),
+                recent_label(path),
+                true,
+                None,
+            );
+            let _ = menu.append(&item);
+        }
+
+        if let Ok(mut recent) = self.recent_paths.lock() {
+            *recent = paths.clone();
+        }
+        self.recent_shown = Some((language, paths, folder_count));
+    }
+}
+
+/// 从托盘唤出主窗口：隐藏时完整恢复位置和样式，否则取消最小化并聚焦
+fn restore_main_window(
+    ctx: &Context,
+    hwnd_usize: usize,
+    visible: &Arc<Mutex<bool>>,
+    tray_restore_requested: &Arc<Mutex<bool>>,
+) {
+    let Ok(mut vis) = visible.lock() else {
+        return;
+    };
+    if !*vis {
+        // 隐藏状态下恢复
+        current_platform().show_window_restore(hwnd_usize);
+        *vis = true;
+        // 设置标志，通知 app.rs 的 update loop 重置模式为 Viewer
+        if let Ok(mut flag) = tray_restore_requested.lock() {
+            *flag = true;
+        }
+        let config = get_context_config(ctx);
+        if let Some((x, y)) = config.window_pos {
+            ctx.send_viewport_cmd(ViewportCommand::OuterPosition(egui::pos2(x, y)));
+        }
+        if let Some((w, h)) = config.window_size {
+            ctx.send_viewport_cmd(ViewportCommand::InnerSize(egui::vec2(w, h)));
+        }
+        ctx.send_viewport_cmd(ViewportCommand::Decorations(true));
+        ctx.send_viewport_cmd(ViewportCommand::Transparent(false));
+        ctx.send_viewport_cmd(ViewportCommand::WindowLevel(WindowLevel::Normal));
+        ctx.send_viewport_cmd(ViewportCommand::Visible(true));
+        ctx.send_viewport_cmd(ViewportCommand::Focus);
+
+        ctx.request_repaint();
+    } else {
+        // 最小化状态下恢复
+        let info = ctx.input(|i| i.viewport().clone());
+        if info.minimized == Some(true) {
+            ctx.send_viewport_cmd(ViewportCommand::Minimized(false));
+        }
+        ctx.send_viewport_cmd(ViewportCommand::Visible(true));
+        // 通常还需要聚焦窗口
+        ctx.send_viewport_cmd(ViewportCommand::Focus);
+
+        ctx.request_repaint();
+    }
 }
 
 fn build_screenshot_menu_label(label: &str, screenshot_hotkey_text: &str) -> String {
@@ -44,7 +151,9 @@ fn build_screenshot_menu_label(label: &str, screenshot_hotkey_text: &str) -> Str
 }
 
 /// 创建托盘
+/// `path_sender` - 最近打开菜单选中的路径经由它交给查看器
 /// `tray_restore_requested` - 当点击托盘且窗口处于隐藏状态时设置为 true，app.rs 的 update loop 会重置模式并清除此标志
+#[allow(clippy::too_many_arguments)]
 pub fn init_tray(
     cc: &eframe::CreationContext<'_>,
     visible: &Arc<Mutex<bool>>,
@@ -52,6 +161,7 @@ pub fn init_tray(
     hwnd_usize: usize,
     tray_restore_requested: &Arc<Mutex<bool>>,
     tray_screenshot_requested: &Arc<Mutex<bool>>,
+    path_sender: &Sender<PathBuf>,
     screenshot_hotkey_text: &str,
 ) -> AppTray {
     let tray_menu = Menu::new();
@@ -65,7 +175,12 @@ pub fn init_tray(
     let item_exit = MenuItem::new(text.menu.exit, true, None);
     let item_exit_id = item_exit.id().clone();
 
+    // 最近打开的内容由 refresh_recent 填充
+    let recent_menu = Submenu::new(text.menu.recent, true);
+    let recent_paths = Arc::new(Mutex::new(Vec::new()));
+
     let _ = tray_menu.append(&item_screenshot);
+    let _ = tray_menu.append(&recent_menu);
     let _ = tray_menu.append(&PredefinedMenuItem::separator()); // 添加一条分割线
     let _ = tray_menu.append(&item_exit);
 
@@ -77,6 +192,9 @@ pub fn init_tray(
                 _tray_icon: None,
                 screenshot_item: None,
                 exit_item: None,
+                recent_menu: None,
+                recent_paths,
+                recent_shown: None,
                 current_language: config.language,
                 screenshot_hotkey_text: screenshot_hotkey_text.to_string(),
             };
@@ -98,6 +216,9 @@ pub fn init_tray(
                 _tray_icon: None,
                 screenshot_item: None,
                 exit_item: None,
+                recent_menu: None,
+                recent_paths,
+                recent_shown: None,
                 current_language: config.language,
                 screenshot_hotkey_text: screenshot_hotkey_text.to_string(),
             };
@@ -110,6 +231,9 @@ pub fn init_tray(
     let allow_quit_1 = Arc::clone(allow_quit);
     let tray_restore_for_tray = Arc::clone(tray_restore_requested);
     let tray_screenshot_for_menu = Arc::clone(tray_screenshot_requested);
+    let tray_restore_for_menu = Arc::clone(tray_restore_requested);
+    let recent_paths_for_menu = Arc::clone(&recent_paths);
+    let path_sender = path_sender.clone();
 
     // 托盘图标处理
     let ctx = cc.egui_ctx.clone();
@@ -120,43 +244,7 @@ pub fn init_tray(
             ..
         } = event
         {
-            let Ok(mut vis) = visible_for_tray.lock() else {
-                return;
-            };
-            if !*vis {
-                // 隐藏状态下恢复
-                current_platform().show_window_restore(hwnd_usize);
-                *vis = true;
-                // 设置标志，通知 app.rs 的 update loop 重置模式为 Viewer
-                if let Ok(mut flag) = tray_restore_for_tray.lock() {
-                    *flag = true;
-                }
-                let config = get_context_config(&ctx);
-                if let Some((x, y)) = config.window_pos {
-                    ctx.send_viewport_cmd(ViewportCommand::OuterPosition(egui::pos2(x, y)));
-                }
-                if let Some((w, h)) = config.window_size {
-                    ctx.send_viewport_cmd(ViewportCommand::InnerSize(egui::vec2(w, h)));
-                }
-                ctx.send_viewport_cmd(ViewportCommand::Decorations(true));
-                ctx.send_viewport_cmd(ViewportCommand::Transparent(false));
-                ctx.send_viewport_cmd(ViewportCommand::WindowLevel(WindowLevel::Normal));
-                ctx.send_viewport_cmd(ViewportCommand::Visible(true));
-                ctx.send_viewport_cmd(ViewportCommand::Focus);
-
-                ctx.request_repaint();
-            } else {
-                // 最小化状态下恢复
-                let info = ctx.input(|i| i.viewport().clone());
-                if info.minimized == Some(true) {
-                    ctx.send_viewport_cmd(ViewportCommand::Minimized(false));
-                }
-                ctx.send_viewport_cmd(ViewportCommand::Visible(true));
-                // 通常还需要聚焦窗口
-                ctx.send_viewport_cmd(ViewportCommand::Focus);
-
-                ctx.request_repaint();
-            }
+            restore_main_window(&ctx, hwnd_usize, &visible_for_tray, &tray_restore_for_tray);
         }
     }));
 
@@ -190,6 +278,25 @@ pub fn init_tray(
 
             // 发送关闭请求进入退出流程
             ctx_2.send_viewport_cmd(ViewportCommand::Close);
+        } else if let Some(index) = event
+            .id
+            .0
+            .strip_prefix(RECENT_ID_PREFIX)
+            .and_then(|index| index.parse::<usize>().ok())
+        {
+            let path = recent_paths_for_menu
+                .lock()
+                .ok()
+                .and_then(|paths| paths.get(index).cloned());
+            if let Some(path) = path {
+                path_sender.send(path).ok();
+                restore_main_window(
+                    &ctx_2,
+                    hwnd_usize,
+                    &visible_for_tray_menu,
+                    &tray_restore_for_menu,
+                );
+            }
         }
     }));
 
@@ -197,6 +304,9 @@ pub fn init_tray(
         _tray_icon: Some(tray_icon),
         screenshot_item: Some(item_screenshot),
         exit_item: Some(item_exit),
+        recent_menu: Some(recent_menu),
+        recent_paths,
+        recent_shown: None,
         current_language: config.language,
         screenshot_hotkey_text: screenshot_hotkey_text.to_string(),
     }
@@ -0,0 +1,146 @@
+use crate::utils::metadata::webp_chunks;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::codecs::webp::WebPEncoder;
+use image::{Delay, ExtendedColorType, Frame, RgbaImage};
+
+const WEBP_ANIMATION_FLAG: u8 = 0x02;
+const WEBP_ALPHA_FLAG: u8 = 0x10;
+
+/// 编码为 GIF。`plays` 为播放次数，0 表示无限循环；`speed` 为调色板量化速度，1 最慢、质量最好，30 最快。
+/// 帧逐个取出并编码，调用方可以边解码边编码，不必让所有帧同时驻留内存
+pub fn encode_gif(
+    frames: impl IntoIterator<Item = Result<RgbaImage, String>>,
+    delay_ms: u32,
+    plays: u32,
+    speed: i32,
+) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = GifEncoder::new_with_speed(&mut bytes, speed.clamp(1, 30));
+        // GIF 记录的是首次播放之后的重复次数
+        let repeat = match plays {
+            0 => Repeat::Infinite,
+            n => Repeat::Finite((n - 1).min(u16::MAX as u32) as u16),
+        };
+        encoder.set_repeat(repeat).map_err(|e| e.to_string())?;
+        let delay = Delay::from_numer_denom_ms(delay_ms, 1);
+        for frame in frames {
+            encoder
+                .encode_frame(Frame::from_parts(frame?, 0, 0, delay))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(bytes)
+}
+
+fn push_chunk(out: &mut Vec<u8>, kind: &[u8; 4], body: &[u8]) {
+    out.extend(kind);
+    out.extend((body.len() as u32).to_le_bytes());
+    out.extend(body);
+    if body.len() % 2 == 1 {
+        out.push(0);
+    }
+}
+
+fn u24(value: u32) -> [u8; 3] {
+    let [a, b, c, _] = value.to_le_bytes();
+    [a, b, c]
+}
+
+/// 编码为动态 WebP：每帧单独无损编码，再装进 ANIM/ANMF 容器。所有帧的尺寸须一致。
+/// 与 GIF 一样逐帧取出，内存中只保留压缩后的帧数据
+pub fn encode_animated_webp(
+    frames: impl IntoIterator<Item = Result<RgbaImage, String>>,
+    delay_ms: u32,
+    plays: u32,
+) -> Result<Vec<u8>, String> {
+    let mut size = None;
+    let mut anmf_chunks = Vec::new();
+    for frame in frames {
+        let frame = frame?;
+        let (width, height) = *size.get_or_insert(frame.dimensions());
+        if frame.dimensions() != (width, height) {
+            return Err("frame size mismatch".to_string());
+        }
+        let mut encoded = Vec::new();
+        WebPEncoder::new_lossless(&mut encoded)
+            .encode(frame.as_raw(), width, height, ExtendedColorType::Rgba8)
+            .map_err(|e| e.to_string())?;
+
+        // 帧位于画布原点，整帧覆盖，不与上一帧混合
+        let mut anmf = Vec::new();
+        anmf.extend(u24(0));
+        anmf.extend(u24(0));
+        anmf.extend(u24(width - 1));
+        anmf.extend(u24(height - 1));
+        anmf.extend(u24(delay_ms.min(0xFF_FFFF)));
+        anmf.push(0x02);
+        for (kind, data) in webp_chunks(&encoded)? {
+            if &kind != b"VP8X" {
+                push_chunk(&mut anmf, &kind, data);
+            }
+        }
+        push_chunk(&mut anmf_chunks, b"ANMF", &anmf);
+    }
+    let (width, height) = size.ok_or("no frames")?;
+
+    let mut vp8x = vec![WEBP_ANIMATION_FLAG | WEBP_ALPHA_FLAG, 0, 0, 0];
+    vp8x.extend(u24(width - 1));
+    vp8x.extend(u24(height - 1));
+    // 背景色（BGRA）和循环次数，0 为无限循环
+    let mut anim = vec![0u8; 4];
+    anim.extend((plays.min(u16::MAX as u32) as u16).to_le_bytes());
+
+    let mut body = b"WEBP".to_vec();
+    push_chunk(&mut body, b"VP8X", &vp8x);
+    push_chunk(&mut body, b"ANIM", &anim);
+    body.extend(anmf_chunks);
+
+    let mut out = b"RIFF".to_vec();
+    out.extend((body.len() as u32).to_le_bytes());
+    out.extend(body);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode_animated_webp, encode_gif};
+    use image::codecs::gif::GifDecoder;
+    use image::codecs::webp::WebPDecoder;
+    use image::{AnimationDecoder, Rgba, RgbaImage};
+    use std::io::Cursor;
+
+    fn frames() -> Vec<Result<RgbaImage, String>> {
+        [[255, 0, 0, 255], [0, 0, 255, 128], [0, 255, 0, 255]]
+            .into_iter()
+            .map(|color| Ok(RgbaImage::from_pixel(5, 3, Rgba(color))))
+            .collect()
+    }
+
+    #[test]
+    fn encoded_animations_decode_frame_by_frame() {
+        let gif = encode_gif(frames(), 80, 0, 10).unwrap();
+        let decoded: Vec<_> = GifDecoder::new(Cursor::new(gif))
+            .unwrap()
+            .into_frames()
+            .collect_frames()
+            .unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[0].delay().numer_denom_ms(), (80, 1));
+        assert_eq!(decoded[2].buffer().get_pixel(4, 2), &Rgba([0, 255, 0, 255]));
+
+        let webp = encode_animated_webp(frames(), 120, 2).unwrap();
+        let decoder = WebPDecoder::new(Cursor::new(webp)).unwrap();
+        assert!(decoder.has_animation());
+        let decoded: Vec<_> = decoder.into_frames().collect_frames().unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[1].delay().numer_denom_ms(), (120, 1));
+        assert_eq!(decoded[1].buffer().get_pixel(0, 0), &Rgba([0, 0, 255, 128]));
+
+        // 中途解码失败的帧让整个导出失败
+        let mut broken = frames();
+        broken[1] = Err("decode failed".to_string());
+        assert!(encode_gif(broken.clone(), 80, 0, 10).is_err());
+        assert!(encode_animated_webp(broken, 120, 2).is_err());
+    }
+}
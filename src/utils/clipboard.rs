@@ -1,7 +1,42 @@
 use crate::{i18n::lang::get_i18n_text, ui::widgets::toast::ToastManager};
-use egui::{Color32, Context};
+use egui::{Color32, ColorImage, Context};
 use std::{borrow::Cow, path::PathBuf, sync::Arc, thread};
 
+/// 剪贴板中可以在看图中打开的内容
+pub enum ClipboardContent {
+    Image(ColorImage),
+    Paths(Vec<PathBuf>),
+}
+
+/// 依次尝试文件列表、图片和每行一个路径的文本
+pub fn read_clipboard() -> Option<ClipboardContent> {
+    let mut clipboard = arboard::Clipboard::new().ok()?;
+    if let Ok(paths) = clipboard.get().file_list()
+        && !paths.is_empty()
+    {
+        return Some(ClipboardContent::Paths(paths));
+    }
+    if let Ok(image) = clipboard.get_image() {
+        return Some(ClipboardContent::Image(ColorImage::from_rgba_unmultiplied(
+            [image.width, image.height],
+            &image.bytes,
+        )));
+    }
+    let paths = parse_path_list(&clipboard.get_text().ok()?);
+    (!paths.is_empty()).then_some(ClipboardContent::Paths(paths))
+}
+
+/// 文本中每行一个路径，去掉首尾空白和引号，只保留存在的路径
+fn parse_path_list(text: &str) -> Vec<PathBuf> {
+    text.lines()
+        .map(|line| line.trim().trim_matches('"'))
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .filter(|path| path.exists())
+        .collect()
+}
+
+/// 剪贴板只写入解码后的像素，不带 EXIF 等元数据，任何元数据策略下都不会泄露位置信息
 pub fn copy_image_to_clipboard_async(
     ctx: &Context,
     pixels_arc: Arc<Vec<Color32>>,
@@ -47,3 +82,37 @@ pub fn copy_image_path_to_clipboard(ctx: &Context, path: PathBuf, toast_manager:
     let _ = clipboard.set_text(path.to_string_lossy().to_string());
     toast_manager.success(text.toast.copied);
 }
+
+/// 多个路径以换行分隔复制为文本
+pub fn copy_paths_to_clipboard(ctx: &Context, paths: &[PathBuf], toast_manager: &ToastManager) {
+    let text = get_i18n_text(ctx);
+    let Ok(mut clipboard) = arboard::Clipboard::new() else {
+        tracing::error!("{}", text.toast.copy_failed);
+        toast_manager.error(text.toast.copy_failed.to_string());
+        return;
+    };
+    let joined = paths
+        .iter()
+        .map(|path| path.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let _ = clipboard.set_text(joined);
+    toast_manager.success(text.toast.copied);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_path_list;
+    use std::env;
+
+    #[test]
+    fn parse_path_list_keeps_existing_quoted_paths() {
+        let dir = env::temp_dir();
+        let text = format!(
+            "\"{}\"\r\n\n  /definitely/missing/file.png  \n",
+            dir.display()
+        );
+
+        assert_eq!(parse_path_list(&text), vec![dir]);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::model::config::ExternalApp;
+use std::path::Path;
+use std::process::Command;
+
+/// 按空白拆分命令模板，双引号内的空白不拆分。反斜杠不做转义，以便直接书写 Windows 路径
+fn split_command(template: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_token = false;
+    for ch in template.chars() {
+        match ch {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_token = true;
+            }
+            ch if ch.is_whitespace() && !in_quotes => {
+                if has_token {
+                    args.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            ch => {
+                current.push(ch);
+                has_token = true;
+            }
+        }
+    }
+    if has_token {
+        args.push(current);
+    }
+    args
+}
+
+/// 拆分模板并替换占位符，返回程序和参数。先拆分后替换，路径中的空格不会把参数拆开
+pub fn build_command(template: &str, path: &Path) -> Option<(String, Vec<String>)> {
+    let path_str = path.display().to_string();
+    let dir = path
+        .parent()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_default();
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut parts = split_command(template).into_iter().map(|arg| {
+        arg.replace("{path}", &path_str)
+            .replace("{dir}", &dir)
+            .replace("{name}", &name)
+    });
+    let program = parts.next().filter(|program| !program.is_empty())?;
+    let mut args: Vec<String> = parts.collect();
+    // 没写占位符时默认把文件路径追加到最后
+    if !template.contains("{path}") && !template.contains("{dir}") && !template.contains("{name}") {
+        args.push(path_str);
+    }
+    Some((program, args))
+}
+
+/// 用外部程序打开图片，不等待其退出
+pub fn launch_external(app: &ExternalApp, path: &Path) -> Result<(), String> {
+    let (program, args) =
+        build_command(&app.command, path).ok_or_else(|| "empty command".to_string())?;
+    Command::new(&program)
+        .args(&args)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("{program}: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build_command;
+    use std::path::Path;
+
+    #[test]
+    fn build_command_keeps_quoted_program_and_substitutes_placeholders() {
+        let path = Path::new("/photos/my trip/a b.png");
+        let (program, args) =
+            build_command(r#""/opt/My Apps/gimp" --new "{path}" -d {dir}"#, path).unwrap();
+        assert_eq!(program, "/opt/My Apps/gimp");
+        assert_eq!(
+            args,
+            vec!["--new", "/photos/my trip/a b.png", "-d", "/photos/my trip"]
+        );
+    }
+
+    #[test]
+    fn build_command_appends_path_without_placeholder() {
+        let path = Path::new("/a/b.png");
+        let (program, args) = build_command("optipng -o7", path).unwrap();
+        assert_eq!(program, "optipng");
+        assert_eq!(args, vec!["-o7", "/a/b.png"]);
+        assert!(build_command("   ", path).is_none());
+    }
+}
@@ -1,8 +1,20 @@
 use crate::model::image_meta::SUPPORTED_IMAGE_EXTENSIONS;
 use crate::ui::resources::APP_IMG;
+use egui::Color32;
+use image::{ImageFormat, RgbaImage};
+use rayon::prelude::*;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use tray_icon::Icon;
 
+/// 识别格式需要读取的文件头长度
+const SNIFF_LEN: u64 = 32;
+
+/// 可能是图片的扩展名，例如浏览器另存或下载中断留下的文件，只有这些和无扩展名的文件才读取文件头
+const SNIFF_EXTENSIONS: &[&str] = &[
+    "jpe", "jfi", "pjp", "pjpeg", "dat", "tmp", "bin", "download",
+];
+
 /// 统一的判断逻辑
 pub fn is_image(path: &Path) -> bool {
     path.extension()
@@ -11,21 +23,75 @@ pub fn is_image(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
+/// 按文件头识别图片格式，只返回能解码的格式
+pub fn sniff_format(header: &[u8]) -> Option<ImageFormat> {
+    let format = image::guess_format(header).ok()?;
+    format
+        .extensions_str()
+        .iter()
+        .any(|ext| SUPPORTED_IMAGE_EXTENSIONS.contains(ext))
+        .then_some(format)
+}
+
+/// 只读取文件头判断格式，目录或无法打开的文件返回 None
+pub fn sniff_file(path: &Path) -> Option<ImageFormat> {
+    let mut header = Vec::with_capacity(SNIFF_LEN as usize);
+    std::fs::File::open(path)
+        .ok()?
+        .take(SNIFF_LEN)
+        .read_to_end(&mut header)
+        .ok()?;
+    sniff_format(&header)
+}
+
+/// 扩展名缺失或与实际格式不符时，返回实际格式的名称
+pub fn extension_mismatch(path: &Path, actual: ImageFormat) -> Option<String> {
+    if ImageFormat::from_path(path).ok() == Some(actual) {
+        return None;
+    }
+    actual
+        .extensions_str()
+        .first()
+        .map(|ext| ext.to_uppercase())
+}
+
+/// 无扩展名或扩展名可能是图片时才需要读取文件头，视频、文档等已知扩展名直接跳过
+fn worth_sniffing(path: &Path) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .is_none_or(|s| SNIFF_EXTENSIONS.contains(&s.to_lowercase().as_str()))
+}
+
+/// 扩展名受支持，或者扩展名缺失、可疑但文件头是图片
+pub fn is_image_file(path: &Path) -> bool {
+    is_image(path) || (worth_sniffing(path) && path.is_file() && sniff_file(path).is_some())
+}
+
 pub fn collect_images(dir: &Path) -> Vec<PathBuf> {
-    // 使用 rayon 并行迭代器加速目录扫描和过滤
     let Ok(entries) = std::fs::read_dir(dir) else {
         return Vec::new();
     };
 
+    // 读取文件头的判断在网络目录上较慢，用 rayon 并行过滤
     let mut result: Vec<PathBuf> = entries
         .flatten()
+        .par_bridge()
         .map(|e| e.path())
-        .filter(|p| is_image(p))
+        .filter(|p| is_image_file(p))
         .collect();
     result.sort();
     result
 }
 
+/// 纹理像素是预乘透明度的，写文件或合成前还原为普通 RGBA
+pub fn unmultiplied_rgba(pixels: &[Color32], size: [usize; 2]) -> Option<RgbaImage> {
+    let bytes = pixels
+        .iter()
+        .flat_map(|p| p.to_srgba_unmultiplied())
+        .collect();
+    RgbaImage::from_raw(size[0] as u32, size[1] as u32, bytes)
+}
+
 pub fn load_icon() -> Option<egui::IconData> {
     let img = match image::load_from_memory(APP_IMG) {
         Ok(img) => img.into_rgba8(),
@@ -57,7 +123,8 @@ pub fn load_tray_icon() -> Result<Icon, String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{collect_images, is_image};
+    use super::{collect_images, extension_mismatch, is_image, sniff_format};
+    use image::ImageFormat;
     use std::{
         env, fs,
         path::Path,
@@ -87,6 +154,13 @@ mod tests {
         fs::write(dir.join("b.jpg"), []).expect("Image placeholder should be written");
         fs::write(dir.join("a.png"), []).expect("Image placeholder should be written");
         fs::write(dir.join("notes.txt"), []).expect("Non-image placeholder should be written");
+        fs::write(dir.join("download"), b"\x89PNG\r\n\x1a\n\0\0")
+            .expect("Extensionless image should be written");
+        fs::write(dir.join("saved.tmp"), b"\x89PNG\r\n\x1a\n\0\0")
+            .expect("Image with a temporary extension should be written");
+        // 已知不是图片的扩展名不读取文件头
+        fs::write(dir.join("clip.mp4"), b"\x89PNG\r\n\x1a\n\0\0")
+            .expect("Video placeholder should be written");
 
         let files = collect_images(&dir);
 
@@ -97,8 +171,32 @@ mod tests {
                     .map(|name| name.to_string_lossy().into_owned())
             })
             .collect();
-        assert_eq!(names, vec!["a.png", "b.jpg"]);
+        assert_eq!(names, vec!["a.png", "b.jpg", "download", "saved.tmp"]);
 
         let _ = fs::remove_dir_all(dir);
     }
+
+    #[test]
+    fn sniff_format_detects_content_and_reports_mismatch() {
+        assert_eq!(
+            sniff_format(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            Some(ImageFormat::Jpeg)
+        );
+        assert_eq!(sniff_format(b"plain text"), None);
+        // 图标格式能识别，但不在支持列表中
+        assert_eq!(sniff_format(&[0, 0, 1, 0]), None);
+
+        assert_eq!(
+            extension_mismatch(Path::new("photo.jfif"), ImageFormat::Jpeg),
+            None
+        );
+        assert_eq!(
+            extension_mismatch(Path::new("photo.png"), ImageFormat::Jpeg),
+            Some("JPG".to_string())
+        );
+        assert_eq!(
+            extension_mismatch(Path::new("image"), ImageFormat::Png),
+            Some("PNG".to_string())
+        );
+    }
 }
@@ -0,0 +1,552 @@
+use crate::i18n::lang::TextBundle;
+use crate::model::config::MetadataPolicy;
+use crate::utils::image::sniff_format;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader};
+use std::io::Cursor;
+
+const GPS_IFD_TAG: u16 = 0x8825;
+const ORIENTATION_TAG: u16 = 0x0112;
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+const XMP_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const XMP_EXTENSION_HEADER: &[u8] = b"http://ns.adobe.com/xmp/extension/\0";
+/// APP2 中的多图索引（MPF），指向主图之后附带的图片
+const MPF_HEADER: &[u8] = b"MPF\0";
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+/// WebP VP8X 标志位
+const WEBP_EXIF_FLAG: u8 = 0x08;
+const WEBP_XMP_FLAG: u8 = 0x04;
+
+/// JPEG 标记段：标记和内容
+type Segment<'a> = (u8, &'a [u8]);
+/// PNG / WebP 块：类型和内容
+pub type Chunk<'a> = ([u8; 4], &'a [u8]);
+
+impl MetadataPolicy {
+    pub fn label(self, text: &TextBundle) -> &'static str {
+        match self {
+            Self::KeepAll => text.metadata.keep_all,
+            Self::StripGps => text.metadata.strip_gps,
+            Self::StripAll => text.metadata.strip_all,
+        }
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize, big_endian: bool) -> Option<u16> {
+    let bytes: [u8; 2] = data.get(offset..offset + 2)?.try_into().ok()?;
+    Some(if big_endian {
+        u16::from_be_bytes(bytes)
+    } else {
+        u16::from_le_bytes(bytes)
+    })
+}
+
+fn read_u32(data: &[u8], offset: usize, big_endian: bool) -> Option<u32> {
+    let bytes: [u8; 4] = data.get(offset..offset + 4)?.try_into().ok()?;
+    Some(if big_endian {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    })
+}
+
+fn tiff_byte_order(tiff: &[u8]) -> Option<bool> {
+    match tiff.get(0..2)? {
+        b"MM" => Some(true),
+        b"II" => Some(false),
+        _ => None,
+    }
+}
+
+/// 在 IFD0 中查找标签，返回条目的起始位置
+fn find_ifd0_entry(tiff: &[u8], tag: u16) -> Option<usize> {
+    let big_endian = tiff_byte_order(tiff)?;
+    let ifd0 = read_u32(tiff, 4, big_endian)? as usize;
+    let count = read_u16(tiff, ifd0, big_endian)? as usize;
+    (0..count)
+        .map(|i| ifd0 + 2 + i * 12)
+        .find(|&entry| read_u16(tiff, entry, big_endian) == Some(tag))
+}
+
+/// TIFF 数据类型的单个值字节数
+fn type_size(field_type: u16) -> usize {
+    match field_type {
+        1 | 2 | 6 | 7 => 1,
+        3 | 8 => 2,
+        4 | 9 | 11 | 13 => 4,
+        5 | 10 | 12 => 8,
+        _ => 0,
+    }
+}
+
+fn zero(data: &mut [u8], offset: usize, len: usize) {
+    let end = offset.saturating_add(len).min(data.len());
+    if offset < end {
+        data[offset..end].fill(0);
+    }
+}
+
+/// 原地清除 EXIF（TIFF 结构）中的 GPS 目录：条目及其引用的数据全部置零，
+/// 其余数据的偏移保持不变。返回是否找到了 GPS 目录
+pub fn wipe_gps(tiff: &mut [u8]) -> bool {
+    let Some(big_endian) = tiff_byte_order(tiff) else {
+        return false;
+    };
+    let Some(gps) = find_ifd0_entry(tiff, GPS_IFD_TAG)
+        .and_then(|entry| read_u32(tiff, entry + 8, big_endian))
+        .map(|offset| offset as usize)
+    else {
+        return false;
+    };
+    let Some(count) = read_u16(tiff, gps, big_endian).map(usize::from) else {
+        return false;
+    };
+    for i in 0..count {
+        let entry = gps + 2 + i * 12;
+        let (Some(field_type), Some(values)) = (
+            read_u16(tiff, entry + 2, big_endian),
+            read_u32(tiff, entry + 4, big_endian),
+        ) else {
+            break;
+        };
+        // 超过 4 字节的值存放在目录之外，经纬度就在这里
+        let len = type_size(field_type).saturating_mul(values as usize);
+        if len > 4
+            && let Some(offset) = read_u32(tiff, entry + 8, big_endian)
+        {
+            zero(tiff, offset as usize, len);
+        }
+    }
+    // 条目数、条目和下一目录偏移全部置零，留下一个空目录
+    zero(tiff, gps, 2 + count * 12 + 4);
+    true
+}
+
+/// EXIF 中记录的方向，缺失时为 1
+fn orientation(tiff: &[u8]) -> u16 {
+    tiff_byte_order(tiff)
+        .zip(find_ifd0_entry(tiff, ORIENTATION_TAG))
+        .and_then(|(big_endian, entry)| read_u16(tiff, entry + 8, big_endian))
+        .unwrap_or(1)
+}
+
+/// 只含方向标记的最小 EXIF，去掉全部元数据后照片仍按原方向显示
+fn orientation_only_tiff(orientation: u16) -> Vec<u8> {
+    let mut tiff = b"MM\0\x2a\0\0\0\x08".to_vec();
+    tiff.extend(1u16.to_be_bytes());
+    tiff.extend(ORIENTATION_TAG.to_be_bytes());
+    tiff.extend(3u16.to_be_bytes());
+    tiff.extend(1u32.to_be_bytes());
+    tiff.extend(orientation.to_be_bytes());
+    tiff.extend([0, 0]);
+    tiff.extend(0u32.to_be_bytes());
+    tiff
+}
+
+/// 按策略处理一段 EXIF，返回 None 表示整段删除
+fn sanitize_exif(tiff: &[u8], policy: MetadataPolicy) -> Option<Vec<u8>> {
+    match policy {
+        MetadataPolicy::KeepAll => Some(tiff.to_vec()),
+        MetadataPolicy::StripGps => {
+            let mut tiff = tiff.to_vec();
+            wipe_gps(&mut tiff);
+            Some(tiff)
+        }
+        MetadataPolicy::StripAll => {
+            let orientation = orientation(tiff);
+            (orientation != 1).then(|| orientation_only_tiff(orientation))
+        }
+    }
+}
+
+/// XMP 里也可能带有位置信息，去除 GPS 时连同整个 XMP 包一起删除
+fn keep_xmp(xmp: &[u8], policy: MetadataPolicy) -> bool {
+    match policy {
+        MetadataPolicy::KeepAll => true,
+        MetadataPolicy::StripGps => !xmp.windows(8).any(|w| w == b"exif:GPS"),
+        MetadataPolicy::StripAll => false,
+    }
+}
+
+/// 按元数据策略处理图片文件内容，像素数据保持原样不重新编码（TIFF 去除全部元数据时除外）
+pub fn apply_metadata_policy(data: &[u8], policy: MetadataPolicy) -> Result<Vec<u8>, String> {
+    if policy == MetadataPolicy::KeepAll {
+        return Ok(data.to_vec());
+    }
+    match sniff_format(data) {
+        Some(ImageFormat::Jpeg) => sanitize_jpeg(data, policy),
+        Some(ImageFormat::Png) => sanitize_png(data, policy),
+        Some(ImageFormat::WebP) => sanitize_webp(data, policy),
+        Some(ImageFormat::Tiff) => sanitize_tiff_file(data, policy),
+        Some(ImageFormat::Gif | ImageFormat::Bmp) => Ok(data.to_vec()),
+        // 无法确认不含元数据的格式宁可失败，也不原样导出
+        _ => Err("unsupported format for metadata removal".to_string()),
+    }
+}
+
+/// 读取 JPEG / PNG / WebP 中的 EXIF 并按策略处理，用于转换格式时写入新文件
+pub fn exif_for_export(data: &[u8], policy: MetadataPolicy) -> Option<Vec<u8>> {
+    let tiff = match sniff_format(data)? {
+        ImageFormat::Jpeg => jpeg_segments(data)
+            .ok()?
+            .0
+            .into_iter()
+            .find(|(marker, payload)| *marker == 0xE1 && payload.starts_with(EXIF_HEADER))
+            .map(|(_, payload)| payload[EXIF_HEADER.len()..].to_vec()),
+        ImageFormat::Png => png_chunks(data)
+            .ok()?
+            .into_iter()
+            .find(|(kind, _)| kind == b"eXIf")
+            .map(|(_, body)| body.to_vec()),
+        ImageFormat::WebP => webp_chunks(data)
+            .ok()?
+            .into_iter()
+            .find(|(kind, _)| kind == b"EXIF")
+            .map(|(_, body)| body.strip_prefix(EXIF_HEADER).unwrap_or(body).to_vec()),
+        _ => None,
+    }?;
+    sanitize_exif(&tiff, policy)
+}
+
+/// 拆分 JPEG 图像数据之前的标记段，返回各段的标记和内容，以及图像数据的起始位置
+fn jpeg_segments(data: &[u8]) -> Result<(Vec<Segment<'_>>, usize), String> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return Err("invalid JPEG".to_string());
+    }
+    let mut segments = Vec::new();
+    let mut pos = 2;
+    loop {
+        let (Some(&0xFF), Some(&marker)) = (data.get(pos), data.get(pos + 1)) else {
+            return Err("corrupt JPEG marker".to_string());
+        };
+        match marker {
+            // 填充字节
+            0xFF => pos += 1,
+            // 扫描开始或文件结束，后面是图像数据
+            0xDA | 0xD9 => return Ok((segments, pos)),
+            0x01 | 0xD0..=0xD7 => pos += 2,
+            _ => {
+                let len = read_u16(data, pos + 2, true).ok_or("truncated JPEG")? as usize;
+                let body = data
+                    .get(pos + 4..pos + 2 + len)
+                    .ok_or("truncated JPEG segment")?;
+                segments.push((marker, body));
+                pos += 2 + len;
+            }
+        }
+    }
+}
+
+/// 从第一个扫描开始跳过主图数据，返回 EOI 之后的位置；找不到 EOI 时视为到文件末尾
+fn jpeg_primary_end(data: &[u8], image_data: usize) -> usize {
+    let mut pos = image_data;
+    while let (Some(&0xFF), Some(&marker)) = (data.get(pos), data.get(pos + 1)) {
+        match marker {
+            0xFF => {
+                pos += 1;
+                continue;
+            }
+            0xD9 => return pos + 2,
+            0x01 | 0xD0..=0xD7 => pos += 2,
+            _ => {
+                let Some(len) = read_u16(data, pos + 2, true) else {
+                    break;
+                };
+                pos += 2 + len as usize;
+            }
+        }
+        if marker != 0xDA {
+            continue;
+        }
+        // 熵编码数据中 0xFF00 是转义，RST 标记也夹在数据中
+        loop {
+            let Some(offset) = data
+                .get(pos..)
+                .and_then(|rest| rest.iter().position(|b| *b == 0xFF))
+            else {
+                return data.len();
+            };
+            pos += offset;
+            match data.get(pos + 1) {
+                Some(0x00 | 0xD0..=0xD7) => pos += 2,
+                _ => break,
+            }
+        }
+    }
+    data.len()
+}
+
+/// 主图 EOI 之后的多图、增益图等附带图片有各自的 EXIF，整体丢弃
+fn sanitize_jpeg(data: &[u8], policy: MetadataPolicy) -> Result<Vec<u8>, String> {
+    let (segments, image_data) = jpeg_segments(data)?;
+    let mut out = vec![0xFF, 0xD8];
+    let push_segment = |out: &mut Vec<u8>, marker: u8, body: &[u8]| {
+        out.extend([0xFF, marker]);
+        out.extend(((body.len() + 2) as u16).to_be_bytes());
+        out.extend(body);
+    };
+    for (marker, body) in segments {
+        let is_exif = marker == 0xE1 && body.starts_with(EXIF_HEADER);
+        let is_xmp = marker == 0xE1
+            && (body.starts_with(XMP_HEADER) || body.starts_with(XMP_EXTENSION_HEADER));
+        let is_mpf = marker == 0xE2 && body.starts_with(MPF_HEADER);
+        if is_mpf {
+            continue;
+        }
+        if is_exif {
+            if let Some(tiff) = sanitize_exif(&body[EXIF_HEADER.len()..], policy) {
+                let mut exif = EXIF_HEADER.to_vec();
+                exif.extend(tiff);
+                push_segment(&mut out, marker, &exif);
+            }
+        } else if is_xmp {
+            if keep_xmp(body, policy) {
+                push_segment(&mut out, marker, body);
+            }
+        } else {
+            // 全部去除时只保留解码需要的段：JFIF(APP0)、ICC(APP2)、Adobe(APP14) 和非 APP 段
+            let is_app = (0xE0..=0xEF).contains(&marker);
+            let needed = !is_app || matches!(marker, 0xE0 | 0xE2 | 0xEE);
+            let strip = policy == MetadataPolicy::StripAll && (!needed || marker == 0xFE);
+            if !strip {
+                push_segment(&mut out, marker, body);
+            }
+        }
+    }
+    out.extend(&data[image_data..jpeg_primary_end(data, image_data)]);
+    Ok(out)
+}
+
+fn png_chunks(data: &[u8]) -> Result<Vec<Chunk<'_>>, String> {
+    if !data.starts_with(PNG_SIGNATURE) {
+        return Err("invalid PNG".to_string());
+    }
+    let mut chunks = Vec::new();
+    let mut pos = PNG_SIGNATURE.len();
+    while pos < data.len() {
+        let len = read_u32(data, pos, true).ok_or("truncated PNG")? as usize;
+        let kind: [u8; 4] = data
+            .get(pos + 4..pos + 8)
+            .and_then(|kind| kind.try_into().ok())
+            .ok_or("truncated PNG")?;
+        let body = data
+            .get(pos + 8..pos + 8 + len)
+            .ok_or("truncated PNG chunk")?;
+        chunks.push((kind, body));
+        pos += 12 + len;
+        if &kind == b"IEND" {
+            break;
+        }
+    }
+    Ok(chunks)
+}
+
+fn crc32(bytes: impl IntoIterator<Item = u8>) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn sanitize_png(data: &[u8], policy: MetadataPolicy) -> Result<Vec<u8>, String> {
+    let mut out = PNG_SIGNATURE.to_vec();
+    let push_chunk = |out: &mut Vec<u8>, kind: &[u8; 4], body: &[u8]| {
+        out.extend((body.len() as u32).to_be_bytes());
+        out.extend(kind);
+        out.extend(body);
+        out.extend(crc32(kind.iter().chain(body).copied()).to_be_bytes());
+    };
+    for (kind, body) in png_chunks(data)? {
+        match &kind {
+            b"eXIf" => {
+                if let Some(tiff) = sanitize_exif(body, policy) {
+                    push_chunk(&mut out, &kind, &tiff);
+                }
+            }
+            b"iTXt" if body.starts_with(b"XML:com.adobe.xmp\0") => {
+                if keep_xmp(body, policy) {
+                    push_chunk(&mut out, &kind, body);
+                }
+            }
+            b"tEXt" | b"zTXt" | b"iTXt" | b"tIME" if policy == MetadataPolicy::StripAll => {}
+            _ => push_chunk(&mut out, &kind, body),
+        }
+    }
+    Ok(out)
+}
+
+/// 拆分 RIFF 容器中的 WebP 块
+pub fn webp_chunks(data: &[u8]) -> Result<Vec<Chunk<'_>>, String> {
+    if data.get(0..4) != Some(b"RIFF") || data.get(8..12) != Some(b"WEBP") {
+        return Err("invalid WebP".to_string());
+    }
+    let mut chunks = Vec::new();
+    let mut pos = 12;
+    while pos + 8 <= data.len() {
+        let kind: [u8; 4] = data[pos..pos + 4]
+            .try_into()
+            .map_err(|_| "truncated WebP")?;
+        let len = read_u32(data, pos + 4, false).ok_or("truncated WebP")? as usize;
+        let body = data
+            .get(pos + 8..pos + 8 + len)
+            .ok_or("truncated WebP chunk")?;
+        chunks.push((kind, body));
+        // 块长度为奇数时有一个填充字节
+        pos += 8 + len + (len & 1);
+    }
+    Ok(chunks)
+}
+
+fn sanitize_webp(data: &[u8], policy: MetadataPolicy) -> Result<Vec<u8>, String> {
+    let mut out = data[..12].to_vec();
+    let mut cleared_flags = 0u8;
+    for (kind, body) in webp_chunks(data)? {
+        let body = match &kind {
+            b"EXIF" => {
+                let has_header = body.starts_with(EXIF_HEADER);
+                let tiff = if has_header {
+                    &body[EXIF_HEADER.len()..]
+                } else {
+                    body
+                };
+                match sanitize_exif(tiff, policy) {
+                    Some(tiff) if has_header => [EXIF_HEADER, &tiff].concat(),
+                    Some(tiff) => tiff,
+                    None => {
+                        cleared_flags |= WEBP_EXIF_FLAG;
+                        continue;
+                    }
+                }
+            }
+            b"XMP " if !keep_xmp(body, policy) => {
+                cleared_flags |= WEBP_XMP_FLAG;
+                continue;
+            }
+            _ => body.to_vec(),
+        };
+        out.extend(kind);
+        out.extend((body.len() as u32).to_le_bytes());
+        out.extend(&body);
+        if body.len() & 1 == 1 {
+            out.push(0);
+        }
+    }
+    if out.get(12..16) == Some(b"VP8X") && out.len() > 20 {
+        out[20] &= !cleared_flags;
+    }
+    let riff_len = (out.len() - 8) as u32;
+    out[4..8].copy_from_slice(&riff_len.to_le_bytes());
+    Ok(out)
+}
+
+/// TIFF 的元数据就在文件结构里：去除 GPS 时原地清除，去除全部时按方向转正后重新编码
+fn sanitize_tiff_file(data: &[u8], policy: MetadataPolicy) -> Result<Vec<u8>, String> {
+    if policy == MetadataPolicy::StripGps {
+        let mut data = data.to_vec();
+        wipe_gps(&mut data);
+        return Ok(data);
+    }
+    let mut decoder = ImageReader::with_format(Cursor::new(data), ImageFormat::Tiff)
+        .into_decoder()
+        .map_err(|e| e.to_string())?;
+    let orientation = decoder.orientation().map_err(|e| e.to_string())?;
+    let mut img = DynamicImage::from_decoder(decoder).map_err(|e| e.to_string())?;
+    img.apply_orientation(orientation);
+    let mut out = Cursor::new(Vec::new());
+    img.write_to(&mut out, ImageFormat::Tiff)
+        .map_err(|e| e.to_string())?;
+    Ok(out.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        EXIF_HEADER, apply_metadata_policy, jpeg_segments, orientation, orientation_only_tiff,
+        wipe_gps,
+    };
+    use crate::model::config::MetadataPolicy;
+
+    /// 大端 TIFF：IFD0 含方向和 GPS 指针，GPS 目录含一个指向外部数据的纬度条目
+    fn sample_tiff() -> Vec<u8> {
+        let mut tiff = b"MM\0\x2a\0\0\0\x08".to_vec();
+        tiff.extend([0, 2]);
+        tiff.extend([0x01, 0x12, 0, 3, 0, 0, 0, 1, 0, 6, 0, 0]);
+        tiff.extend([0x88, 0x25, 0, 4, 0, 0, 0, 1, 0, 0, 0, 38]);
+        tiff.extend([0, 0, 0, 0]);
+        // GPS 目录位于 38，纬度数据位于 56
+        tiff.extend([0, 1]);
+        tiff.extend([0, 2, 0, 5, 0, 0, 0, 3, 0, 0, 0, 56]);
+        tiff.extend([0, 0, 0, 0]);
+        tiff.extend([7u8; 24]);
+        tiff
+    }
+
+    #[test]
+    fn wipe_gps_clears_directory_and_coordinates() {
+        let mut tiff = sample_tiff();
+        let len = tiff.len();
+        assert!(wipe_gps(&mut tiff));
+        assert_eq!(tiff.len(), len);
+        assert!(tiff[38..].iter().all(|b| *b == 0));
+        assert_eq!(orientation(&tiff), 6);
+    }
+
+    #[test]
+    fn strip_all_keeps_only_orientation_in_jpeg() {
+        let mut exif = EXIF_HEADER.to_vec();
+        exif.extend(sample_tiff());
+        let mut jpeg = vec![0xFF, 0xD8];
+        for (marker, body) in [(0xE1u8, exif.as_slice()), (0xFE, b"secret".as_slice())] {
+            jpeg.extend([0xFF, marker]);
+            jpeg.extend(((body.len() + 2) as u16).to_be_bytes());
+            jpeg.extend(body);
+        }
+        jpeg.extend([0xFF, 0xDA, 1, 2, 3, 0xFF, 0xD9]);
+
+        let out = apply_metadata_policy(&jpeg, MetadataPolicy::StripAll).unwrap();
+        let (segments, _) = jpeg_segments(&out).unwrap();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(
+            segments[0].1,
+            [EXIF_HEADER, &orientation_only_tiff(6)].concat()
+        );
+        assert!(out.ends_with(&[0xFF, 0xDA, 1, 2, 3, 0xFF, 0xD9]));
+    }
+
+    #[test]
+    fn strip_gps_drops_images_after_primary_eoi() {
+        let mut exif = EXIF_HEADER.to_vec();
+        exif.extend(sample_tiff());
+        let jpeg_with = |segments: &[(u8, &[u8])]| {
+            let mut jpeg = vec![0xFF, 0xD8];
+            for (marker, body) in segments {
+                jpeg.extend([0xFF, *marker]);
+                jpeg.extend(((body.len() + 2) as u16).to_be_bytes());
+                jpeg.extend(*body);
+            }
+            // 扫描头之后的熵编码数据含转义字节和 RST 标记
+            jpeg.extend([
+                0xFF, 0xDA, 0, 3, 1, 0x12, 0xFF, 0x00, 0x34, 0xFF, 0xD0, 0x56,
+            ]);
+            jpeg.extend([0xFF, 0xD9]);
+            jpeg
+        };
+        let primary = jpeg_with(&[(0xE2, b"MPF\0index".as_slice())]);
+        // 附带的第二张图保留着完整的 GPS
+        let secondary = jpeg_with(&[(0xE1, exif.as_slice())]);
+        let file = [primary.as_slice(), &secondary].concat();
+
+        let out = apply_metadata_policy(&file, MetadataPolicy::StripGps).unwrap();
+        let (segments, image_data) = jpeg_segments(&out).unwrap();
+        assert!(segments.is_empty());
+        assert_eq!(out[image_data..], primary[primary.len() - 14..]);
+        assert!(!out.windows(4).any(|w| w == b"Exif"));
+    }
+}
@@ -1,2 +1,8 @@
+pub mod animation;
 pub mod clipboard;
+pub mod external;
 pub mod image;
+pub mod metadata;
+pub mod palette;
+pub mod pdf;
+pub mod time;
@@ -0,0 +1,244 @@
+use egui::Color32;
+use serde::Serialize;
+
+/// 参与统计的最多像素数，超出时等间隔抽样
+const SAMPLE_LIMIT: usize = 16384;
+/// 透明度低于此值的像素不计入
+const MIN_ALPHA: u8 = 128;
+
+/// 主色及其在图片中所占的比例
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Swatch {
+    pub rgb: [u8; 3],
+    pub share: f32,
+}
+
+impl Swatch {
+    pub fn hex(&self) -> String {
+        let [r, g, b] = self.rgb;
+        format!("#{r:02X}{g:02X}{b:02X}")
+    }
+
+    pub fn color(&self) -> Color32 {
+        let [r, g, b] = self.rgb;
+        Color32::from_rgb(r, g, b)
+    }
+}
+
+/// 用中位切分法从抽样后的像素中提取至多 `count` 种主色，按占比从高到低排列
+pub fn dominant_colors(pixels: &[Color32], count: usize) -> Vec<Swatch> {
+    let stride = pixels.len().div_ceil(SAMPLE_LIMIT).max(1);
+    let samples: Vec<[u8; 3]> = pixels
+        .iter()
+        .step_by(stride)
+        .map(|p| p.to_srgba_unmultiplied())
+        .filter(|[_, _, _, a]| *a >= MIN_ALPHA)
+        .map(|[r, g, b, _]| [r, g, b])
+        .collect();
+    if samples.is_empty() || count == 0 {
+        return Vec::new();
+    }
+    let total = samples.len();
+
+    let mut boxes = vec![samples];
+    while boxes.len() < count {
+        // 优先切分像素多且颜色跨度大的盒子
+        let Some((index, channel)) = boxes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, b)| {
+                let (channel, range) = widest_channel(b);
+                (range > 0).then_some((i, channel, range as usize * b.len()))
+            })
+            .max_by_key(|(_, _, score)| *score)
+            .map(|(i, channel, _)| (i, channel))
+        else {
+            break;
+        };
+        let mut colors = boxes.swap_remove(index);
+        colors.sort_unstable_by_key(|c| c[channel]);
+        // 在中位值处切开，相同的颜色不会被分到两边
+        let median = colors[colors.len() / 2][channel];
+        let mut split = colors.partition_point(|c| c[channel] < median);
+        if split == 0 {
+            split = colors.partition_point(|c| c[channel] <= median);
+        }
+        let upper = colors.split_off(split);
+        boxes.push(colors);
+        boxes.push(upper);
+    }
+
+    let mut swatches: Vec<Swatch> = boxes
+        .iter()
+        .map(|colors| {
+            let sum = colors.iter().fold([0u64; 3], |mut sum, c| {
+                for (s, v) in sum.iter_mut().zip(c) {
+                    *s += *v as u64;
+                }
+                sum
+            });
+            Swatch {
+                rgb: sum.map(|s| (s as f64 / colors.len() as f64).round() as u8),
+                share: colors.len() as f32 / total as f32,
+            }
+        })
+        .collect();
+    swatches.sort_by(|a, b| b.share.total_cmp(&a.share));
+    swatches
+}
+
+/// 取值范围最大的通道及其范围
+fn widest_channel(colors: &[[u8; 3]]) -> (usize, u8) {
+    (0..3)
+        .map(|channel| {
+            let (min, max) = colors.iter().fold((u8::MAX, u8::MIN), |(min, max), c| {
+                (min.min(c[channel]), max.max(c[channel]))
+            });
+            (channel, max.saturating_sub(min))
+        })
+        .max_by_key(|(_, range)| *range)
+        .unwrap_or((0, 0))
+}
+
+/// 调色板导出格式
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PaletteFormat {
+    /// GIMP、Inkscape、Krita 使用的文本格式
+    Gpl,
+    /// Adobe 色板交换格式
+    Ase,
+    Json,
+}
+
+impl PaletteFormat {
+    pub const ALL: [Self; 3] = [Self::Gpl, Self::Ase, Self::Json];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Gpl => "GIMP Palette (.gpl)",
+            Self::Ase => "Adobe Swatch Exchange (.ase)",
+            Self::Json => "JSON (.json)",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Gpl => "gpl",
+            Self::Ase => "ase",
+            Self::Json => "json",
+        }
+    }
+
+    /// 按格式编码，`name` 作为调色板名称
+    pub fn encode(self, swatches: &[Swatch], name: &str) -> Vec<u8> {
+        match self {
+            Self::Gpl => encode_gpl(swatches, name).into_bytes(),
+            Self::Ase => encode_ase(swatches),
+            Self::Json => encode_json(swatches, name),
+        }
+    }
+}
+
+fn encode_gpl(swatches: &[Swatch], name: &str) -> String {
+    let mut out = format!(
+        "GIMP Palette\nName: {name}\nColumns: {}\n#\n",
+        swatches.len()
+    );
+    for swatch in swatches {
+        let [r, g, b] = swatch.rgb;
+        out.push_str(&format!("{r:3} {g:3} {b:3}\t{}\n", swatch.hex()));
+    }
+    out
+}
+
+fn encode_ase(swatches: &[Swatch]) -> Vec<u8> {
+    let mut out = b"ASEF".to_vec();
+    out.extend(1u16.to_be_bytes());
+    out.extend(0u16.to_be_bytes());
+    out.extend((swatches.len() as u32).to_be_bytes());
+    for swatch in swatches {
+        // 名称为以 0 结尾的 UTF-16BE，长度按字符计
+        let name: Vec<u16> = swatch.hex().encode_utf16().chain([0]).collect();
+        let mut block = Vec::new();
+        block.extend((name.len() as u16).to_be_bytes());
+        block.extend(name.iter().flat_map(|c| c.to_be_bytes()));
+        block.extend(b"RGB ");
+        for channel in swatch.rgb {
+            block.extend((channel as f32 / 255.0).to_be_bytes());
+        }
+        // 0 全局色，1 专色，2 普通色
+        block.extend(2u16.to_be_bytes());
+
+        out.extend(1u16.to_be_bytes());
+        out.extend((block.len() as u32).to_be_bytes());
+        out.extend(block);
+    }
+    out
+}
+
+#[derive(Serialize)]
+struct JsonPalette<'a> {
+    name: &'a str,
+    colors: Vec<JsonColor>,
+}
+
+#[derive(Serialize)]
+struct JsonColor {
+    hex: String,
+    rgb: [u8; 3],
+    share: f32,
+}
+
+fn encode_json(swatches: &[Swatch], name: &str) -> Vec<u8> {
+    let palette = JsonPalette {
+        name,
+        colors: swatches
+            .iter()
+            .map(|swatch| JsonColor {
+                hex: swatch.hex(),
+                rgb: swatch.rgb,
+                share: swatch.share,
+            })
+            .collect(),
+    };
+    serde_json::to_vec_pretty(&palette).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PaletteFormat, Swatch, dominant_colors};
+    use egui::Color32;
+
+    #[test]
+    fn median_cut_finds_main_colors_and_exports() {
+        // 四分之三红色、四分之一蓝色，另有被忽略的透明像素
+        let mut pixels = vec![Color32::from_rgb(250, 10, 10); 300];
+        pixels.extend(vec![Color32::from_rgb(10, 10, 240); 100]);
+        pixels.extend(vec![Color32::TRANSPARENT; 50]);
+        let swatches = dominant_colors(&pixels, 4);
+        assert_eq!(swatches.len(), 2);
+        assert_eq!(swatches[0].hex(), "#FA0A0A");
+        assert_eq!(swatches[1].rgb, [10, 10, 240]);
+        assert_eq!(swatches[0].share, 0.75);
+        assert!(dominant_colors(&[Color32::TRANSPARENT], 4).is_empty());
+
+        let palette = [Swatch {
+            rgb: [255, 128, 0],
+            share: 1.0,
+        }];
+        let gpl = String::from_utf8(PaletteFormat::Gpl.encode(&palette, "test")).unwrap();
+        assert!(gpl.starts_with("GIMP Palette\nName: test\n"));
+        assert!(gpl.contains("255 128   0\t#FF8000"));
+
+        let ase = PaletteFormat::Ase.encode(&palette, "test");
+        assert_eq!(&ase[..12], b"ASEF\0\x01\0\0\0\0\0\x01");
+        // 块长度：名称长度 2 + "#FF8000\0" 16 + 色彩模型 4 + 三个浮点 12 + 类型 2
+        assert_eq!(&ase[12..18], &[0, 1, 0, 0, 0, 36]);
+        assert_eq!(ase.len(), 18 + 36);
+
+        let json: serde_json::Value =
+            serde_json::from_slice(&PaletteFormat::Json.encode(&palette, "test")).unwrap();
+        assert_eq!(json["colors"][0]["hex"], "#FF8000");
+        assert_eq!(json["colors"][0]["rgb"][1], 128);
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt::Write as _;
+
+/// PDF 中的一页，整页是一张 JPEG 图片
+pub struct PdfPage {
+    pub width_px: u32,
+    pub height_px: u32,
+    pub jpeg: Vec<u8>,
+}
+
+/// 生成每页一张图片的最小 PDF。`dpi` 决定像素与纸张尺寸（点，1/72 英寸）的换算
+pub fn images_to_pdf(pages: &[PdfPage], dpi: f32) -> Vec<u8> {
+    let mut out = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+    // 对象编号从 1 开始：1 目录，2 页面树，之后每页依次为页面、内容流、图片
+    let mut offsets = Vec::new();
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| 3 + i * 3).collect();
+
+    offsets.push(out.len());
+    out.extend(b"1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n");
+
+    offsets.push(out.len());
+    let kids = page_ids.iter().fold(String::new(), |mut kids, id| {
+        let _ = write!(kids, "{id} 0 R ");
+        kids
+    });
+    out.extend(
+        format!(
+            "2 0 obj\n<< /Type /Pages /Kids [{}] /Count {} >>\nendobj\n",
+            kids.trim_end(),
+            pages.len()
+        )
+        .as_bytes(),
+    );
+
+    for (page, id) in pages.iter().zip(page_ids) {
+        let width = page.width_px as f32 * 72.0 / dpi;
+        let height = page.height_px as f32 * 72.0 / dpi;
+
+        offsets.push(out.len());
+        out.extend(
+            format!(
+                "{id} 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {width:.2} {height:.2}] \
+                 /Resources << /XObject << /Im0 {} 0 R >> >> /Contents {} 0 R >>\nendobj\n",
+                id + 2,
+                id + 1
+            )
+            .as_bytes(),
+        );
+
+        let content = format!("q {width:.2} 0 0 {height:.2} 0 0 cm /Im0 Do Q");
+        offsets.push(out.len());
+        out.extend(
+            format!(
+                "{} 0 obj\n<< /Length {} >>\nstream\n{content}\nendstream\nendobj\n",
+                id + 1,
+                content.len()
+            )
+            .as_bytes(),
+        );
+
+        offsets.push(out.len());
+        out.extend(
+            format!(
+                "{} 0 obj\n<< /Type /XObject /Subtype /Image /Width {} /Height {} \
+                 /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /DCTDecode /Length {} >>\nstream\n",
+                id + 2,
+                page.width_px,
+                page.height_px,
+                page.jpeg.len()
+            )
+            .as_bytes(),
+        );
+        out.extend(&page.jpeg);
+        out.extend(b"\nendstream\nendobj\n");
+    }
+
+    let xref = out.len();
+    let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", offsets.len() + 1);
+    for offset in &offsets {
+        let _ = writeln!(table, "{offset:010} 00000 n ");
+    }
+    let _ = write!(
+        table,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+        offsets.len() + 1
+    );
+    out.extend(table.as_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PdfPage, images_to_pdf};
+
+    #[test]
+    fn xref_offsets_point_at_objects() {
+        let page = |w| PdfPage {
+            width_px: w,
+            height_px: 300,
+            jpeg: vec![0xFF, 0xD8, 0xFF, 0xD9],
+        };
+        let pdf = images_to_pdf(&[page(150), page(300)], 150.0);
+        // 二进制的文件头和图片数据之外都是 ASCII，偏移量按字节比较
+        let tail = String::from_utf8_lossy(&pdf[pdf.len() - 32..]).into_owned();
+        let xref: usize = tail
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|rest| rest.lines().next())
+            .and_then(|line| line.parse().ok())
+            .unwrap();
+        let table = String::from_utf8_lossy(&pdf[xref..]).into_owned();
+        assert!(table.starts_with("xref\n0 9\n"));
+        let entries: Vec<usize> = table
+            .lines()
+            .skip(3)
+            .take(8)
+            .map(|line| line[..10].parse().unwrap())
+            .collect();
+        for (index, offset) in entries.iter().enumerate() {
+            assert!(pdf[*offset..].starts_with(format!("{} 0 obj", index + 1).as_bytes()));
+        }
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/MediaBox [0 0 72.00 144.00]"));
+    }
+}
@@ -0,0 +1,52 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 将 Unix 时间戳（秒）换算为 (年, 月, 日, 时, 分, 秒)，按 UTC 计算
+pub fn unix_timestamp_to_ymd_hms(mut secs: u64) -> (u32, u32, u32, u32, u32, u32) {
+    let second = (secs % 60) as u32;
+    secs /= 60;
+    let minute = (secs % 60) as u32;
+    secs /= 60;
+    let hour = (secs % 24) as u32;
+    let mut days = secs / 24;
+
+    let mut year = 1970u32;
+    loop {
+        let yd = if is_leap_year(year) { 366 } else { 365 };
+        if days < yd {
+            break;
+        }
+        days -= yd;
+        year += 1;
+    }
+
+    let leap = is_leap_year(year);
+    let month_days = [31, 28 + leap as u64, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+    let mut month = 1u32;
+    for &md in &month_days {
+        if days < md {
+            break;
+        }
+        days -= md;
+        month += 1;
+    }
+
+    (year, month, days as u32 + 1, hour, minute, second)
+}
+
+fn is_leap_year(year: u32) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+/// 格式化为 "YYYY-MM-DD HH:MM:SS"，与 EXIF 日期的展示格式保持一致
+pub fn format_system_time(time: SystemTime) -> Option<String> {
+    let secs = time.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    let (y, mo, d, h, mi, s) = unix_timestamp_to_ymd_hms(secs);
+    Some(format!("{y:04}-{mo:02}-{d:02} {h:02}:{mi:02}:{s:02}"))
+}
+
+/// 格式化为 "YYYYMMDD_HHMMSS"，用于生成文件名
+pub fn format_file_timestamp(time: SystemTime) -> Option<String> {
+    let secs = time.duration_since(UNIX_EPOCH).ok()?.as_secs();
+    let (y, mo, d, h, mi, s) = unix_timestamp_to_ymd_hms(secs);
+    Some(format!("{y:04}{mo:02}{d:02}_{h:02}{mi:02}{s:02}"))
+}